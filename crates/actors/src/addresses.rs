@@ -1,12 +1,20 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
 use actix::Addr;
+use irys_types::H256;
 
 use crate::{
     block_index_service::BlockIndexService,
     block_producer::BlockProducerActor,
     epoch_service::EpochServiceActor,
     mempool_service::MempoolService,
-    mining::{MiningControl, PartitionMiningActor},
+    mining::{GetMiningStatus, MiningControl, PartitionMiningActor, PartitionMiningStatus},
     packing::PackingActor,
+    shutdown::Shutdown,
+    solution_aggregator::SolutionAggregatorActor,
 };
 
 /// Serves as a kind of app state that can be passed into actix web to allow
@@ -15,10 +23,14 @@ use crate::{
 pub struct ActorAddresses {
     pub partitions: Vec<Addr<PartitionMiningActor>>,
     pub block_producer: Addr<BlockProducerActor>,
+    pub solution_aggregator: Addr<SolutionAggregatorActor>,
     pub packing: Addr<PackingActor>,
     pub mempool: Addr<MempoolService>,
     pub block_index: Addr<BlockIndexService>,
     pub epoch_service: Addr<EpochServiceActor>,
+    /// Whether any of the partition actors was last told to mine
+    pub mining_enabled: Arc<AtomicBool>,
+    pub shutdown: Shutdown,
 }
 
 impl ActorAddresses {
@@ -35,6 +47,46 @@ impl ActorAddresses {
         for part in &self.partitions {
             part.try_send(MiningControl(should_mine))?;
         }
+        self.mining_enabled.store(should_mine, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Whether the node is mining
+    pub fn is_mining(&self) -> bool {
+        self.mining_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the mining state of every partition actor
+    pub async fn mining_status(&self) -> eyre::Result<Vec<PartitionMiningStatus>> {
+        let mut statuses = Vec::with_capacity(self.partitions.len());
+        for part in &self.partitions {
+            statuses.push(part.send(GetMiningStatus).await?);
+        }
+        Ok(statuses)
+    }
+
+    /// Starts or stops mining a single partition
+    pub async fn set_partition_mining(
+        &self,
+        partition_hash: H256,
+        should_mine: bool,
+    ) -> eyre::Result<()> {
+        let mut target = None;
+        let mut others_mining = false;
+        for part in &self.partitions {
+            let status = part.send(GetMiningStatus).await?;
+            if target.is_none() && status.partition_hash == Some(partition_hash) {
+                target = Some(part);
+            } else {
+                others_mining |= status.mining;
+            }
+        }
+        let part = target
+            .ok_or_else(|| eyre::eyre!("No storage module mines partition {}", partition_hash))?;
+        part.try_send(MiningControl(should_mine))?;
+        // the node is mining as long as any of its partitions is
+        self.mining_enabled
+            .store(should_mine || others_mining, Ordering::Relaxed);
         Ok(())
     }
 }
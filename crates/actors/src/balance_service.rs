@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use irys_database::get_account_balance;
+use irys_reth_node_bridge::node::RethNodeProvider;
+use irys_types::{app_state::DatabaseProvider, Address, U256};
+use reth::transaction_pool::TransactionPool;
+use serde::{Deserialize, Serialize};
+
+/// The balance of an account as seen by this node: the balance in the latest
+/// EVM state, and what the account's pending transactions will spend from it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalance {
+    /// Balance in the latest EVM state
+    pub confirmed: U256,
+    /// Fees of the account's Irys transactions in the mempool
+    pub pending_irys_fees: U256,
+    /// Maximum cost (value + gas) of the account's transactions in the EVM pool
+    pub pending_evm: U256,
+}
+
+impl AccountBalance {
+    /// What the account can still spend once all its pending transactions are included
+    pub fn available(&self) -> U256 {
+        self.confirmed
+            .saturating_sub(self.pending_irys_fees.saturating_add(self.pending_evm))
+    }
+}
+
+/// Tracks account balances for Irys transaction admission and block selection.
+/// Owned by the mempool, which reserves the fees of the transactions it accepts
+/// until they're included in a block.
+#[derive(Debug)]
+pub struct BalanceService {
+    db: DatabaseProvider,
+    /// Used to read the pending EVM transactions, `None` when running without reth
+    reth_node: Option<RethNodeProvider>,
+    reserved_fees: HashMap<Address, U256>,
+}
+
+impl BalanceService {
+    pub fn new(db: DatabaseProvider, reth_node: Option<RethNodeProvider>) -> Self {
+        Self {
+            db,
+            reth_node,
+            reserved_fees: HashMap::new(),
+        }
+    }
+
+    /// Returns the balance of `address` broken down into its components
+    pub fn balance(&self, address: Address) -> eyre::Result<AccountBalance> {
+        Ok(AccountBalance {
+            confirmed: self.confirmed_balance(address)?,
+            pending_irys_fees: self.reserved_fees(address),
+            pending_evm: self.pending_evm(address),
+        })
+    }
+
+    /// Returns what `address` can spend on Irys fees, i.e. its confirmed balance
+    /// minus what its pending EVM transactions may spend
+    pub fn spendable_on_fees(&self, address: Address) -> eyre::Result<U256> {
+        Ok(self
+            .confirmed_balance(address)?
+            .saturating_sub(self.pending_evm(address)))
+    }
+
+    pub fn confirmed_balance(&self, address: Address) -> eyre::Result<U256> {
+        self.db.view_eyre(|tx| get_account_balance(tx, address))
+    }
+
+    pub fn reserved_fees(&self, address: Address) -> U256 {
+        self.reserved_fees
+            .get(&address)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sums the maximum cost of the transactions `address` has in the EVM pool
+    pub fn pending_evm(&self, address: Address) -> U256 {
+        let Some(reth_node) = &self.reth_node else {
+            return U256::zero();
+        };
+        reth_node
+            .pool
+            .get_transactions_by_sender(address)
+            .iter()
+            .fold(U256::zero(), |total, tx| {
+                total.saturating_add(U256::from_little_endian(&tx.cost().to_le_bytes::<32>()))
+            })
+    }
+
+    /// Reserves the fee of a pending Irys transaction
+    pub fn reserve(&mut self, address: Address, fee: U256) {
+        let reserved = self.reserved_fees.entry(address).or_default();
+        *reserved = reserved.saturating_add(fee);
+    }
+
+    /// Releases the fee of an Irys transaction that left the mempool
+    pub fn release(&mut self, address: Address, fee: U256) {
+        if let Some(reserved) = self.reserved_fees.get_mut(&address) {
+            *reserved = reserved.saturating_sub(fee);
+            if reserved.is_zero() {
+                self.reserved_fees.remove(&address);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use irys_database::{open_or_create_db, tables::IrysTables};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn reserves_and_releases_fees() -> eyre::Result<()> {
+        let path = tempdir().unwrap();
+        let db = open_or_create_db(path, IrysTables::ALL, None)?;
+        let mut balances = BalanceService::new(DatabaseProvider(Arc::new(db)), None);
+        let address = Address::random();
+
+        balances.reserve(address, U256::from(100));
+        balances.reserve(address, U256::from(50));
+        assert_eq!(
+            balances.balance(address)?,
+            AccountBalance {
+                confirmed: U256::zero(),
+                pending_irys_fees: U256::from(150),
+                pending_evm: U256::zero(),
+            }
+        );
+        assert_eq!(balances.balance(address)?.available(), U256::zero());
+
+        balances.release(address, U256::from(100));
+        assert_eq!(balances.reserved_fees(address), U256::from(50));
+        balances.release(address, U256::from(50));
+        assert!(balances.reserved_fees.is_empty());
+        Ok(())
+    }
+}
@@ -1,17 +1,27 @@
 use crate::{
-    block_index_service::BlockIndexReadGuard, block_tree_service::BlockTreeService,
-    block_validation::prevalidate_block, epoch_service::PartitionAssignmentsReadGuard,
+    block_index_service::BlockIndexReadGuard,
+    block_tree_service::BlockTreeService,
+    block_validation::{
+        prev_output_is_valid, prevalidate_block, seeds_are_valid, shadow_receipts_are_valid,
+        shadows_for_block, timestamp_is_valid, unix_millis_now, vdf_difficulties_are_valid,
+    },
+    epoch_service::PartitionAssignmentsReadGuard,
+    reth_service::{ExecutedShadowsMessage, NewPayloadMessage, RethServiceActor},
     vdf_service::VdfStepsReadGuard,
 };
 use actix::prelude::*;
+use alloy_rpc_types_engine::{ExecutionPayloadEnvelopeV1Irys, PayloadStatusEnum};
 use irys_database::{block_header_by_hash, tx_header_by_txid, Ledger};
+use irys_reth_node_bridge::{pool::check_block_pd_chunks, precompile::utils::payload_access_lists};
 use irys_types::{
     DatabaseProvider, DifficultyAdjustmentConfig, IrysBlockHeader, IrysTransactionHeader,
     StorageConfig, VDFStepsConfig,
 };
+use irys_vdf::VdfStepsFastForward;
+use nodit::interval::ii;
 use reth_db::Database;
-use std::sync::Arc;
-use tracing::info;
+use std::sync::{mpsc::Sender, Arc};
+use tracing::{info, warn};
 
 /// `BlockDiscoveryActor` listens for discovered blocks & validates them.
 #[derive(Debug)]
@@ -30,13 +40,20 @@ pub struct BlockDiscoveryActor {
     pub vdf_config: VDFStepsConfig,
     /// Store last VDF Steps
     pub vdf_steps_guard: VdfStepsReadGuard,
+    /// Hands the VDF thread steps from blocks that are ahead of our local VDF
+    pub vdf_fast_forward_sender: Sender<VdfStepsFastForward>,
 }
 
 /// When a block is discovered, either produced locally or received from
-/// a network peer, this message is broadcast.
+/// a network peer, this message is broadcast. The block carries the execution
+/// payload for its `evm_block_hash` so the payload can be imported into reth,
+/// and the shadow receipts reth produces checked against the block.
 #[derive(Message, Debug, Clone)]
 #[rtype(result = "eyre::Result<()>")]
-pub struct BlockDiscoveredMessage(pub Arc<IrysBlockHeader>);
+pub struct BlockDiscoveredMessage(
+    pub Arc<IrysBlockHeader>,
+    pub Arc<ExecutionPayloadEnvelopeV1Irys>,
+);
 
 /// Sent when a discovered block is pre-validated
 #[derive(Message, Debug, Clone)]
@@ -60,6 +77,7 @@ impl BlockDiscoveryActor {
         db: DatabaseProvider,
         vdf_config: VDFStepsConfig,
         vdf_steps_guard: VdfStepsReadGuard,
+        vdf_fast_forward_sender: Sender<VdfStepsFastForward>,
     ) -> Self {
         Self {
             block_index_guard,
@@ -69,6 +87,7 @@ impl BlockDiscoveryActor {
             db,
             vdf_config,
             vdf_steps_guard,
+            vdf_fast_forward_sender,
         }
     }
 }
@@ -78,6 +97,7 @@ impl Handler<BlockDiscoveredMessage> for BlockDiscoveryActor {
     fn handle(&mut self, msg: BlockDiscoveredMessage, _ctx: &mut Context<Self>) -> Self::Result {
         // Validate discovered block
         let new_block_header = msg.0;
+        let execution_payload = msg.1;
         let prev_block_hash = new_block_header.previous_block_hash;
 
         let previous_block_header = match self
@@ -171,6 +191,51 @@ impl Handler<BlockDiscoveredMessage> for BlockDiscoveryActor {
             }
         }
 
+        //====================================
+        // Execution payload validation
+        //------------------------------------
+        // The payload has to be the one referenced by the block. Its shadow
+        // receipts are checked once reth has imported it, see below
+        let payload_block_hash = execution_payload
+            .execution_payload
+            .payload_inner
+            .payload_inner
+            .payload_inner
+            .block_hash;
+        if payload_block_hash != new_block_header.evm_block_hash {
+            return Box::pin(async move {
+                Err(eyre::eyre!(
+                    "Execution payload {} does not match evm_block_hash {}",
+                    payload_block_hash,
+                    new_block_header.evm_block_hash
+                ))
+            });
+        }
+
+        // PD transactions have to be within the per tx & per block chunk limits
+        let transactions = &execution_payload
+            .execution_payload
+            .payload_inner
+            .payload_inner
+            .payload_inner
+            .transactions;
+        if let Err(e) = payload_access_lists(transactions).and_then(|access_lists| {
+            Ok(check_block_pd_chunks(
+                access_lists
+                    .iter()
+                    .map(|access_list| access_list.0.as_slice()),
+            )?)
+        }) {
+            return Box::pin(async move { Err(eyre::eyre!("Invalid PD transactions: {}", e)) });
+        }
+
+        let shadows = match shadows_for_block(&new_block_header, &submit_txs) {
+            Ok(shadows) => shadows,
+            Err(e) => {
+                return Box::pin(async move { Err(eyre::eyre!("Invalid shadow txs: {}", e)) });
+            }
+        };
+
         //====================================
         // Block header pre-validation
         //------------------------------------
@@ -191,7 +256,52 @@ impl Handler<BlockDiscoveredMessage> for BlockDiscoveryActor {
             new_block_header.vdf_limiter_info.output,
             new_block_header.vdf_limiter_info.prev_output
         );
+        // If the block is ahead of our local VDF, hand its steps to the VDF
+        // thread so it can verify them in parallel and jump forward instead
+        // of computing them sequentially
+        let block_step = new_block_header.vdf_limiter_info.global_step_number;
+        let is_ahead_of_vdf = block_step > self.vdf_steps_guard.read().global_step;
+        if is_ahead_of_vdf {
+            // The VDF thread verifies the steps, but takes the reset seed & difficulties
+            // as they are, so they (and the timestamp the difficulties are retargeted
+            // from) have to follow from the previous block first
+            if let Err(e) =
+                timestamp_is_valid(&new_block_header, &previous_block_header, unix_millis_now())
+                    .and_then(|_| prev_output_is_valid(&new_block_header, &previous_block_header))
+                    .and_then(|_| {
+                        seeds_are_valid(&new_block_header, &previous_block_header, &self.vdf_config)
+                    })
+                    .and_then(|_| {
+                        vdf_difficulties_are_valid(
+                            &new_block_header,
+                            &previous_block_header,
+                            &self.vdf_config,
+                        )
+                    })
+            {
+                return Box::pin(async move { Err(eyre::eyre!("Invalid VDF info: {}", e)) });
+            }
+            info!(
+                "Block {} is at VDF step {}, fast forwarding local VDF",
+                new_block_header.height, block_step
+            );
+            let fast_forward =
+                VdfStepsFastForward::from_limiter_info(&new_block_header.vdf_limiter_info);
+            if let Err(e) = self.vdf_fast_forward_sender.send(fast_forward) {
+                warn!("Unable to send VDF fast forward steps: {}", e);
+            }
+        }
+
         Box::pin(async move {
+            if is_ahead_of_vdf {
+                vdf_steps_guard
+                    .get_steps(ii(block_step, block_step))
+                    .await
+                    .map_err(|e| {
+                        eyre::eyre!("VDF step {} unavailable for validation: {}", block_step, e)
+                    })?;
+            }
+
             let block_header_clone = new_block_header.clone(); // Clone before moving
 
             let validation_future = tokio::task::spawn_blocking(move || {
@@ -210,6 +320,38 @@ impl Handler<BlockDiscoveredMessage> for BlockDiscoveryActor {
 
             match validation_future.await.unwrap().await {
                 Ok(_) => {
+                    // Only import the payload into reth once the block header is known
+                    // to be valid, reth executes it and rejects invalid state transitions
+                    let reth_service = RethServiceActor::from_registry();
+                    let status = reth_service
+                        .send(NewPayloadMessage(execution_payload.clone()))
+                        .await??;
+                    if status.status != PayloadStatusEnum::Valid {
+                        return Err(eyre::eyre!(
+                            "Execution payload {} rejected by reth: {:?}",
+                            new_block_header.evm_block_hash,
+                            status.status
+                        ));
+                    }
+
+                    // Check the shadows reth actually executed for the imported EVM
+                    // block match the ones implied by the block's ledgers
+                    let executed_shadows = reth_service
+                        .send(ExecutedShadowsMessage(new_block_header.evm_block_hash))
+                        .await??;
+                    if executed_shadows != shadows {
+                        return Err(eyre::eyre!(
+                            "Shadows executed by EVM block {} don't match the block's ledgers",
+                            new_block_header.evm_block_hash
+                        ));
+                    }
+                    shadow_receipts_are_valid(
+                        &new_block_header,
+                        &submit_txs,
+                        &execution_payload.shadow_receipts,
+                    )
+                    .map_err(|e| eyre::eyre!("Invalid shadow txs: {}", e))?;
+
                     info!("Block is valid, sending to block tree");
 
                     db.update_eyre(|tx| irys_database::insert_block_header(tx, &new_block_header))
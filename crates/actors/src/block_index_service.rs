@@ -1,4 +1,4 @@
-use crate::{calculate_chunks_added, BlockFinalizedMessage};
+use crate::{calculate_chunks_added, shutdown::Drain, BlockFinalizedMessage};
 use actix::prelude::*;
 use irys_database::{BlockIndex, BlockIndexItem, Initialized, Ledger, LedgerIndexItem};
 use irys_types::{IrysBlockHeader, IrysTransactionHeader, StorageConfig, H256, U256};
@@ -51,6 +51,12 @@ impl Handler<GetBlockIndexGuardMessage> for BlockIndexService {
     }
 }
 
+impl Handler<Drain> for BlockIndexService {
+    type Result = ();
+
+    fn handle(&mut self, _msg: Drain, _ctx: &mut Context<Self>) -> Self::Result {}
+}
+
 //==============================================================================
 // BlockIndex Actor
 //------------------------------------------------------------------------------
@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
 use actix::prelude::*;
@@ -13,7 +13,7 @@ use irys_database::{
     block_header_by_hash, cached_data_root_by_data_root, tables::IngressProofs, tx_header_by_txid,
     Ledger,
 };
-use irys_primitives::{DataShadow, IrysTxId, ShadowTx, ShadowTxType, Shadows};
+use irys_primitives::Shadows;
 use irys_reth_node_bridge::{adapter::node::RethNodeContext, node::RethNodeProvider};
 use irys_types::{
     app_state::DatabaseProvider, block_production::SolutionContext, calculate_difficulty,
@@ -22,15 +22,17 @@ use irys_types::{
     IrysTransactionHeader, PoaData, Signature, TransactionLedger, TxIngressProof, VDFLimiterInfo,
     H256, U256,
 };
+use irys_vdf::{calculate_seeds, calculate_vdf_difficulties};
 use nodit::interval::ii;
 use openssl::sha;
 use reth::{revm::primitives::B256, rpc::eth::EthApiServer as _};
 use reth_db::cursor::*;
 use reth_db::Database;
-use tracing::{debug, error, info};
+use reth_metrics::metrics::histogram;
+use tracing::{debug, error, info, warn};
 
 use crate::{
-    block_discovery::{BlockDiscoveredMessage, BlockDiscoveryActor}, block_tree_service::BlockTreeReadGuard, broadcast_mining_service::{BroadcastDifficultyUpdate, BroadcastMiningService}, epoch_service::{EpochServiceActor, GetPartitionAssignmentMessage}, mempool_service::{GetBestMempoolTxs, MempoolService}, reth_service::{BlockHashType, ForkChoiceUpdateMessage, RethServiceActor}, vdf_service::VdfStepsReadGuard
+    block_discovery::{BlockDiscoveredMessage, BlockDiscoveryActor}, block_tree_service::BlockTreeReadGuard, block_validation::shadows_for_block, broadcast_mining_service::{BroadcastDifficultyUpdate, BroadcastMiningService}, epoch_service::{EpochServiceActor, GetPartitionAssignmentMessage}, mempool_service::{GetBestMempoolTxs, MempoolService}, reth_service::{BlockHashType, ForkChoiceUpdateMessage, PrefetchPdChunksMessage, RethServiceActor}, vdf_service::VdfStepsReadGuard
 };
 
 /// Used to mock up a `BlockProducerActor`
@@ -143,10 +145,11 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
 
         // let self_addr = ctx.address();
         // let storage_config = self.storage_config.clone();
-        // let vdf_config = self.vdf_config.clone();
+        let vdf_config = self.vdf_config.clone();
         let vdf_steps = self.vdf_steps_guard.clone();
 
         AtomicResponse::new(Box::pin( async move {
+            let started = Instant::now();
             // Get the current head of the longest chain, from the block_tree, to build off of
             let (canonical_blocks, _not_onchain_count) = block_tree_guard.read().get_canonical_chain();
             let (latest_block_hash, prev_block_height, _publish_tx, _submit_tx) = canonical_blocks.last().unwrap();
@@ -313,6 +316,10 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
             };
             steps.push(solution.seed.0);
 
+            // Rotate in the reset seeds if this block crosses a VDF reset line
+            let (seed, next_seed) = calculate_seeds(&vdf_config, solution.vdf_step, &prev_block_header);
+            let (vdf_difficulty, next_vdf_difficulty) = calculate_vdf_difficulties(&vdf_config, solution.vdf_step, current_timestamp, &prev_block_header);
+
             let mut irys_block = IrysBlockHeader {
                 block_hash,
                 height: block_height,
@@ -356,32 +363,18 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
                     output: solution.seed.into_inner(),
                     last_step_checkpoints: solution.checkpoints,
                     prev_output: prev_block_header.vdf_limiter_info.output,
-                    seed: prev_block_header.vdf_limiter_info.seed,
+                    seed,
+                    next_seed,
                     steps,
-                    ..Default::default()
+                    vdf_difficulty: Some(vdf_difficulty),
+                    next_vdf_difficulty: Some(next_vdf_difficulty),
                 },
             };
 
             // RethNodeContext is a type-aware wrapper that lets us interact with the reth node
             let context =  RethNodeContext::new(reth.into()).await.map_err(|e| eyre!("Error connecting to Reth: {}", e))?;
 
-            let shadows = Shadows::new(
-                submit_txs
-                    .iter()
-                    .map(|header| ShadowTx {
-                        tx_id: IrysTxId::from_slice(header.id.as_bytes()),
-                        fee: irys_primitives::U256::from(
-                            header.total_fee(),
-                        ),
-                        address: header.signer,
-                        tx: ShadowTxType::Data(DataShadow {
-                            fee: irys_primitives::U256::from(
-                                header.total_fee(),
-                            ),
-                        }),
-                    })
-                    .collect(),
-            );
+            let shadows = Shadows::new(shadows_for_block(&irys_block, &submit_txs)?);
 
             // create a new reth payload
 
@@ -414,15 +407,19 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
                 finalized_hash: None, 
             }).await??;
 
-    
+            // unpack the chunks the payload's PD transactions read before the builder executes them
+            if let Err(e) = RethServiceActor::from_registry().send(PrefetchPdChunksMessage).await? {
+                warn!("Unable to prefetch PD chunks for block {}: {}", &block_height, e);
+            }
+
             let exec_payload = context
                 .engine_api
                 .build_payload_v1_irys(prev_block_header.evm_block_hash, payload_attrs)
                 .await
                 .unwrap();
 
-            // we can examine the execution status of generated shadow txs
-            // let shadow_receipts = exec_payload.shadow_receipts;
+            // the execution status of the generated shadow txs is checked against the
+            // block's ledgers when the block is validated, see `shadow_receipts_are_valid`
 
             let v1_payload = exec_payload
                 .clone()
@@ -437,7 +434,7 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
 
 
             let block = Arc::new(irys_block);
-            match block_discovery_addr.send(BlockDiscoveredMessage(block.clone())).await {
+            match block_discovery_addr.send(BlockDiscoveredMessage(block.clone(), Arc::new(exec_payload.clone()))).await {
                 Ok(Ok(_)) => Ok(()),
                 Ok(Err(res)) => {
                     error!("Newly produced block {} ({}) failed pre-validation: {:?}", &block.block_hash.0.to_base58(), &block.height, res);
@@ -468,6 +465,7 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
             }
 
             info!("Finished producing block {}, ({})", &block_hash.0.to_base58(),&block_height);
+            histogram!("irys_block_production_duration_seconds").record(started.elapsed().as_secs_f64());
 
             Ok(Some((block.clone(), exec_payload)))
         }
@@ -1,6 +1,6 @@
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    sync::{Arc, RwLock, RwLockReadGuard},
+    sync::{mpsc::Sender, Arc, RwLock, RwLockReadGuard},
     time::SystemTime,
 };
 
@@ -9,8 +9,10 @@ use crate::{
     block_index_service::{BlockIndexReadGuard, BlockIndexService},
     block_producer::BlockConfirmedMessage,
     chunk_migration_service::ChunkMigrationService,
+    event_service::{EventService, NodeEvent},
     mempool_service::MempoolService,
     reth_service::{BlockHashType, ForkChoiceUpdateMessage, RethServiceActor},
+    shutdown::Drain,
     validation_service::{RequestValidationMessage, ValidationService},
     BlockFinalizedMessage,
 };
@@ -22,6 +24,7 @@ use irys_types::{
     Address, BlockHash, DatabaseProvider, IrysBlockHeader, IrysTransactionHeader,
     IrysTransactionId, StorageConfig, H256, U256,
 };
+use irys_vdf::VdfResetSchedule;
 use reth_db::{transaction::DbTx, Database};
 use tracing::{debug, error, info};
 
@@ -60,6 +63,12 @@ impl Handler<GetBlockTreeGuardMessage> for BlockTreeService {
     }
 }
 
+impl Handler<Drain> for BlockTreeService {
+    type Result = ();
+
+    fn handle(&mut self, _msg: Drain, _ctx: &mut Context<Self>) -> Self::Result {}
+}
+
 //==============================================================================
 // BlockTree Actor
 //------------------------------------------------------------------------------
@@ -76,6 +85,8 @@ pub struct BlockTreeService {
     pub block_index_guard: Option<BlockIndexReadGuard>,
     /// Global storage config
     pub storage_config: StorageConfig,
+    /// Feeds the reset seed and difficulty of each confirmed block into the VDF thread
+    pub vdf_reset_schedule_sender: Option<Sender<VdfResetSchedule>>,
 }
 
 impl Actor for BlockTreeService {
@@ -99,6 +110,7 @@ impl BlockTreeService {
         miner_address: &Address,
         block_index_guard: BlockIndexReadGuard,
         storage_config: StorageConfig,
+        vdf_reset_schedule_sender: Sender<VdfResetSchedule>,
     ) -> Self {
         let cache = BlockTreeCache::initialize_from_list(block_index, db.clone());
 
@@ -108,6 +120,7 @@ impl BlockTreeService {
             miner_address: *miner_address,
             block_index_guard: Some(block_index_guard),
             storage_config,
+            vdf_reset_schedule_sender: Some(vdf_reset_schedule_sender),
         }
     }
 
@@ -148,6 +161,11 @@ impl BlockTreeService {
 
         let chunk_migration = ChunkMigrationService::from_registry();
         let block_index = BlockIndexService::from_registry();
+        EventService::publish(NodeEvent::BlockFinalized {
+            block_hash: block_header.block_hash,
+            height: block_header.height,
+            tx_ids: all_txs.iter().map(|tx| tx.id).collect(),
+        });
         let block_finalized_message = BlockFinalizedMessage {
             block_header: Arc::new(block_header),
             all_txs: Arc::new(all_txs),
@@ -178,6 +196,15 @@ impl BlockTreeService {
                 &tip_hash, &e
             )
         }
+        // The VDF applies the next_seed and next_vdf_difficulty of the latest
+        // block below a reset line when it reaches that line
+        if let Some(sender) = &self.vdf_reset_schedule_sender {
+            let schedule = VdfResetSchedule::from_limiter_info(&confirmed_block.vdf_limiter_info);
+            if let Err(e) = sender.send(schedule) {
+                error!("Unable to send reset schedule to the VDF thread: {}", e);
+            }
+        }
+
         let msg = BlockConfirmedMessage(confirmed_block.clone(), all_tx);
         MempoolService::from_registry().do_send(msg);
     }
@@ -356,6 +383,11 @@ impl Handler<ValidationResultMessage> for BlockTreeService {
 
                 // Now do mutable operations
                 if cache.mark_tip(&block_hash).is_ok() {
+                    EventService::publish(NodeEvent::NewTip {
+                        block_hash,
+                        height: arc_block.height,
+                        miner_address: arc_block.miner_address,
+                    });
                     self.notify_services_of_block_confirmation(block_hash, &arc_block, all_tx);
                 }
 
@@ -5,13 +5,17 @@ use crate::{
 use base58::ToBase58;
 use irys_database::Ledger;
 use irys_packing::{capacity_single::compute_entropy_chunk, xor_vec_u8_arrays_in_place};
+use irys_primitives::{DataShadow, IrysTxId, ShadowReceipt, ShadowTx, ShadowTxType};
 use irys_storage::ii;
 use irys_types::{
     calculate_difficulty, next_cumulative_diff, storage_config::StorageConfig, validate_path,
-    Address, DifficultyAdjustmentConfig, IrysBlockHeader, PoaData, VDFStepsConfig, H256,
+    Address, DifficultyAdjustmentConfig, IrysBlockHeader, IrysTransactionHeader, PoaData,
+    VDFStepsConfig, CONFIG, H256,
 };
-use irys_vdf::last_step_checkpoints_is_valid;
+use irys_vdf::{calculate_seeds, calculate_vdf_difficulties, last_step_checkpoints_is_valid};
 use openssl::sha;
+use reth_metrics::metrics::histogram;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, info};
 
 /// Full pre-validation steps for a block
@@ -31,22 +35,59 @@ pub async fn prevalidate_block(
         &block.block_hash.0.to_base58(),
         &block.height
     );
-    if block.chunk_hash != sha::sha256(&block.poa.chunk.0).into() {
-        return Err(eyre::eyre!(
-            "Invalid block: chunk hash distinct from PoA chunk hash"
-        ));
-    }
+    timed_stage("chunk_hash", || {
+        if block.chunk_hash != sha::sha256(&block.poa.chunk.0).into() {
+            return Err(eyre::eyre!(
+                "Invalid block: chunk hash distinct from PoA chunk hash"
+            ));
+        }
+        Ok(())
+    })?;
+
+    // Check the timestamp, the difficulty & VDF difficulty retargeting depend on it
+    timed_stage("timestamp", || {
+        timestamp_is_valid(&block, &previous_block, unix_millis_now())
+    })?;
+    debug!(
+        "timestamp_is_valid for block {} ({})",
+        &block.block_hash.0.to_base58(),
+        &block.height
+    );
 
     // Check prev_output (vdf)
-    prev_output_is_valid(&block, &previous_block)?;
+    timed_stage("prev_output", || {
+        prev_output_is_valid(&block, &previous_block)
+    })?;
     debug!(
         "prev_output_is_valid for block {} ({})",
         &block.block_hash.0.to_base58(),
         &block.height
     );
 
+    // Check the VDF reset seeds
+    timed_stage("seeds", || {
+        seeds_are_valid(&block, &previous_block, &vdf_config)
+    })?;
+    debug!(
+        "seeds_are_valid for block {} ({})",
+        &block.block_hash.0.to_base58(),
+        &block.height
+    );
+
+    // Check the VDF difficulty schedule
+    timed_stage("vdf_difficulties", || {
+        vdf_difficulties_are_valid(&block, &previous_block, &vdf_config)
+    })?;
+    debug!(
+        "vdf_difficulties_are_valid for block {} ({})",
+        &block.block_hash.0.to_base58(),
+        &block.height
+    );
+
     // Check the difficulty
-    difficulty_is_valid(&block, &previous_block, &difficulty_config)?;
+    timed_stage("difficulty", || {
+        difficulty_is_valid(&block, &previous_block, &difficulty_config)
+    })?;
 
     debug!(
         "difficulty_is_valid for block {} ({})",
@@ -55,7 +96,9 @@ pub async fn prevalidate_block(
     );
 
     // Check the cumulative difficulty
-    cumulative_difficulty_is_valid(&block, &previous_block)?;
+    timed_stage("cumulative_difficulty", || {
+        cumulative_difficulty_is_valid(&block, &previous_block)
+    })?;
     debug!(
         "cumulative_difficulty_is_valid for block {} ({})",
         &block.block_hash.0.to_base58(),
@@ -63,7 +106,7 @@ pub async fn prevalidate_block(
     );
 
     // Check the solution_hash
-    solution_hash_is_valid(&block)?;
+    timed_stage("solution_hash", || solution_hash_is_valid(&block))?;
     debug!(
         "solution_hash_is_valid for block {} ({})",
         &block.block_hash.0.to_base58(),
@@ -71,7 +114,9 @@ pub async fn prevalidate_block(
     );
 
     // Recall range check
-    recall_recall_range_is_valid(&block, &storage_config, &steps_guard)?;
+    timed_stage("recall_range", || {
+        recall_recall_range_is_valid(&block, &storage_config, &steps_guard)
+    })?;
     debug!(
         "recall_recall_range_is_valid for block {} ({})",
         &block.block_hash.0.to_base58(),
@@ -79,7 +124,11 @@ pub async fn prevalidate_block(
     );
 
     // We only check last_step_checkpoints during pre-validation
-    last_step_checkpoints_is_valid(&block.vdf_limiter_info, &vdf_config).await?;
+    let started = Instant::now();
+    let last_step_checkpoints =
+        last_step_checkpoints_is_valid(&block.vdf_limiter_info, &vdf_config).await;
+    record_stage_duration("last_step_checkpoints", started);
+    last_step_checkpoints?;
     debug!(
         "last_step_checkpoints_is_valid for block {} ({})",
         &block.block_hash.0.to_base58(),
@@ -89,6 +138,52 @@ pub async fn prevalidate_block(
     Ok(())
 }
 
+/// Runs a validation stage, recording its duration
+pub(crate) fn timed_stage<T>(stage: &'static str, validate: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = validate();
+    record_stage_duration(stage, started);
+    result
+}
+
+fn record_stage_duration(stage: &'static str, started: Instant) {
+    histogram!("irys_block_validation_duration_seconds", "stage" => stage)
+        .record(started.elapsed().as_secs_f64());
+}
+
+/// Current time in milliseconds since UNIX_EPOCH, the unit of block timestamps
+pub fn unix_millis_now() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time is before the UNIX epoch")
+        .as_millis()
+}
+
+/// Validates the block's `timestamp` is strictly after the previous block's and
+/// no more than `CONFIG.max_future_block_time_ms` ahead of `now` (in milliseconds).
+pub fn timestamp_is_valid(
+    block: &IrysBlockHeader,
+    previous_block: &IrysBlockHeader,
+    now: u128,
+) -> eyre::Result<()> {
+    if block.timestamp <= previous_block.timestamp {
+        return Err(eyre::eyre!(
+            "Invalid timestamp {} (not after the previous block's {})",
+            block.timestamp,
+            previous_block.timestamp
+        ));
+    }
+    let max_timestamp = now + CONFIG.max_future_block_time_ms as u128;
+    if block.timestamp > max_timestamp {
+        return Err(eyre::eyre!(
+            "Invalid timestamp {} (more than {}ms in the future)",
+            block.timestamp,
+            CONFIG.max_future_block_time_ms
+        ));
+    }
+    Ok(())
+}
+
 pub fn prev_output_is_valid(
     block: &IrysBlockHeader,
     previous_block: &IrysBlockHeader,
@@ -104,6 +199,69 @@ pub fn prev_output_is_valid(
     }
 }
 
+/// Validates the block's VDF `seed` and `next_seed` follow the reset rules:
+/// they rotate when the block crosses a reset line and are inherited from the
+/// previous block otherwise.
+pub fn seeds_are_valid(
+    block: &IrysBlockHeader,
+    previous_block: &IrysBlockHeader,
+    vdf_config: &VDFStepsConfig,
+) -> eyre::Result<()> {
+    let (seed, next_seed) = calculate_seeds(
+        vdf_config,
+        block.vdf_limiter_info.global_step_number,
+        previous_block,
+    );
+
+    if block.vdf_limiter_info.seed != seed {
+        return Err(eyre::eyre!(
+            "Invalid vdf_limiter.seed (expected {}, got {})",
+            &seed,
+            &block.vdf_limiter_info.seed
+        ));
+    }
+    if block.vdf_limiter_info.next_seed != next_seed {
+        return Err(eyre::eyre!(
+            "Invalid vdf_limiter.next_seed (expected {}, got {})",
+            &next_seed,
+            &block.vdf_limiter_info.next_seed
+        ));
+    }
+    Ok(())
+}
+
+/// Validates the block's `vdf_difficulty` and `next_vdf_difficulty` follow the
+/// VDF difficulty schedule: the schedule is retargeted by the first block after
+/// a reset line and is inherited from the previous block otherwise.
+pub fn vdf_difficulties_are_valid(
+    block: &IrysBlockHeader,
+    previous_block: &IrysBlockHeader,
+    vdf_config: &VDFStepsConfig,
+) -> eyre::Result<()> {
+    let (vdf_difficulty, next_vdf_difficulty) = calculate_vdf_difficulties(
+        vdf_config,
+        block.vdf_limiter_info.global_step_number,
+        block.timestamp,
+        previous_block,
+    );
+
+    if block.vdf_limiter_info.vdf_difficulty != Some(vdf_difficulty) {
+        return Err(eyre::eyre!(
+            "Invalid vdf_limiter.vdf_difficulty (expected {}, got {:?})",
+            vdf_difficulty,
+            block.vdf_limiter_info.vdf_difficulty
+        ));
+    }
+    if block.vdf_limiter_info.next_vdf_difficulty != Some(next_vdf_difficulty) {
+        return Err(eyre::eyre!(
+            "Invalid vdf_limiter.next_vdf_difficulty (expected {}, got {:?})",
+            next_vdf_difficulty,
+            block.vdf_limiter_info.next_vdf_difficulty
+        ));
+    }
+    Ok(())
+}
+
 /// Validates if a block's difficulty matches the expected difficulty calculated
 /// from previous block data.
 /// Returns Ok if valid, Err if the difficulty doesn't match the calculated value.
@@ -348,6 +506,80 @@ pub fn poa_is_valid(
     Ok(())
 }
 
+/// Builds the shadow txs the execution payload of `block` has to apply: one
+/// data shadow per Submit ledger tx, in ledger order, charging its total fee.
+///
+/// Publish ledger txs deliberately don't get a shadow: a tx is only promoted
+/// once it is in a Submit ledger, where its `total_fee()` (term and perm fee)
+/// was already charged, so charging it again on promotion would double charge
+/// its signer. A receipt for a promoted tx is rejected as an extra shadow.
+pub fn shadows_for_block(
+    block: &IrysBlockHeader,
+    submit_txs: &[IrysTransactionHeader],
+) -> eyre::Result<Vec<ShadowTx>> {
+    let submit_tx_ids = &block.ledgers[Ledger::Submit].tx_ids.0;
+    if submit_tx_ids.len() != submit_txs.len()
+        || submit_tx_ids
+            .iter()
+            .zip(submit_txs)
+            .any(|(tx_id, header)| *tx_id != header.id)
+    {
+        return Err(eyre::eyre!(
+            "Submit tx headers don't match the Submit ledger of block {}",
+            &block.block_hash.0.to_base58()
+        ));
+    }
+
+    Ok(submit_txs
+        .iter()
+        .map(|header| ShadowTx {
+            tx_id: IrysTxId::from_slice(header.id.as_bytes()),
+            fee: irys_primitives::U256::from(header.total_fee()),
+            address: header.signer,
+            tx: ShadowTxType::Data(DataShadow {
+                fee: irys_primitives::U256::from(header.total_fee()),
+            }),
+        })
+        .collect())
+}
+
+/// Validates the shadow receipts of the block's execution payload match the
+/// shadows implied by the block's ledgers, see [`shadows_for_block`]. Every
+/// expected shadow must have been applied, in order, and nothing else.
+pub fn shadow_receipts_are_valid(
+    block: &IrysBlockHeader,
+    submit_txs: &[IrysTransactionHeader],
+    shadow_receipts: &[ShadowReceipt],
+) -> eyre::Result<()> {
+    let shadows = shadows_for_block(block, submit_txs)?;
+    if shadow_receipts.len() != shadows.len() {
+        return Err(eyre::eyre!(
+            "Invalid shadow tx count (expected {}, got {})",
+            shadows.len(),
+            shadow_receipts.len()
+        ));
+    }
+
+    for (receipt, shadow) in shadow_receipts.iter().zip(&shadows) {
+        if receipt.tx_id != shadow.tx_id {
+            return Err(eyre::eyre!(
+                "Invalid shadow tx id (expected {}, got {})",
+                &shadow.tx_id,
+                &receipt.tx_id
+            ));
+        }
+        if receipt.tx_type != shadow.tx {
+            return Err(eyre::eyre!(
+                "Invalid shadow tx {} (expected {:?}, got {:?})",
+                &shadow.tx_id,
+                &shadow.tx,
+                &receipt.tx_type
+            ));
+        }
+    }
+    Ok(())
+}
+
 //==============================================================================
 // Tests
 //------------------------------------------------------------------------------
@@ -359,17 +591,23 @@ mod tests {
             EpochServiceActor, EpochServiceConfig, GetLedgersGuardMessage,
             GetPartitionAssignmentsGuardMessage, NewEpochMessage,
         },
+        vdf_service::VdfState,
         BlockFinalizedMessage,
     };
     use actix::{prelude::*, SystemRegistry};
 
     use irys_config::IrysNodeConfig;
     use irys_database::{BlockIndex, Initialized};
+    use irys_primitives::ShadowResult;
     use irys_types::{
         irys::IrysSigner, partition::PartitionAssignment, Address, Base64, H256List,
         IrysTransaction, IrysTransactionHeader, Signature, TransactionLedger, H256, U256,
     };
-    use std::sync::{Arc, RwLock};
+    use irys_vdf::step_vdf_difficulty;
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, RwLock},
+    };
     use tracing::log::LevelFilter;
     use tracing::{debug, info};
 
@@ -486,6 +724,228 @@ mod tests {
         }
     }
 
+    #[test]
+    fn seeds_rotate_at_reset_line() {
+        let vdf_config = VDFStepsConfig {
+            vdf_reset_frequency: 10,
+            ..VDFStepsConfig::default()
+        };
+
+        let mut previous_block = IrysBlockHeader::new();
+        previous_block.block_hash = H256::random();
+        previous_block.vdf_limiter_info.global_step_number = 8;
+        previous_block.vdf_limiter_info.seed = H256::random();
+        previous_block.vdf_limiter_info.next_seed = H256::random();
+
+        // Block at the reset step itself hasn't crossed the line yet
+        let mut block = IrysBlockHeader::new();
+        block.vdf_limiter_info.global_step_number = 10;
+        block.vdf_limiter_info.seed = previous_block.vdf_limiter_info.seed;
+        block.vdf_limiter_info.next_seed = previous_block.vdf_limiter_info.next_seed;
+        assert!(seeds_are_valid(&block, &previous_block, &vdf_config).is_ok());
+
+        // Once past it, next_seed becomes the seed and the previous block hash is scheduled
+        block.vdf_limiter_info.global_step_number = 11;
+        assert!(seeds_are_valid(&block, &previous_block, &vdf_config).is_err());
+        block.vdf_limiter_info.seed = previous_block.vdf_limiter_info.next_seed;
+        block.vdf_limiter_info.next_seed = previous_block.block_hash;
+        assert!(seeds_are_valid(&block, &previous_block, &vdf_config).is_ok());
+    }
+
+    #[test]
+    fn shadow_receipts_match_block_ledgers() {
+        let submit_txs: Vec<IrysTransactionHeader> = (1..=2)
+            .map(|i| IrysTransactionHeader {
+                id: H256::random(),
+                term_fee: 100 * i,
+                perm_fee: Some(10),
+                ..Default::default()
+            })
+            .collect();
+        let mut block = IrysBlockHeader::new();
+        block.ledgers[Ledger::Submit].tx_ids = H256List(submit_txs.iter().map(|h| h.id).collect());
+
+        let mut receipts: Vec<ShadowReceipt> = shadows_for_block(&block, &submit_txs)
+            .unwrap()
+            .into_iter()
+            .map(|shadow| ShadowReceipt {
+                tx_id: shadow.tx_id,
+                tx_type: shadow.tx,
+                // Failed shadows are still part of the block
+                result: ShadowResult::OutOfFunds,
+            })
+            .collect();
+        assert!(shadow_receipts_are_valid(&block, &submit_txs, &receipts).is_ok());
+
+        // Tx headers have to be the ones in the block's Submit ledger
+        assert!(shadow_receipts_are_valid(&block, &submit_txs[..1], &receipts).is_err());
+
+        // Out of order
+        receipts.swap(0, 1);
+        assert!(shadow_receipts_are_valid(&block, &submit_txs, &receipts).is_err());
+        receipts.swap(0, 1);
+
+        // Extra shadow
+        receipts.push(receipts[0].clone());
+        assert!(shadow_receipts_are_valid(&block, &submit_txs, &receipts).is_err());
+        receipts.pop();
+
+        // Promoted txs were charged when submitted, so they don't get a shadow
+        let promoted = IrysTransactionHeader {
+            id: H256::random(),
+            term_fee: 100,
+            perm_fee: Some(10),
+            ..Default::default()
+        };
+        block.ledgers[Ledger::Publish].tx_ids = H256List(vec![promoted.id]);
+        assert!(shadow_receipts_are_valid(&block, &submit_txs, &receipts).is_ok());
+        receipts.push(ShadowReceipt {
+            tx_id: IrysTxId::from_slice(promoted.id.as_bytes()),
+            tx_type: ShadowTxType::Data(DataShadow {
+                fee: irys_primitives::U256::from(promoted.total_fee()),
+            }),
+            result: ShadowResult::Success,
+        });
+        assert!(shadow_receipts_are_valid(&block, &submit_txs, &receipts).is_err());
+        receipts.pop();
+
+        // Wrong fee
+        receipts[1].tx_type = ShadowTxType::Data(DataShadow {
+            fee: irys_primitives::U256::from(1),
+        });
+        assert!(shadow_receipts_are_valid(&block, &submit_txs, &receipts).is_err());
+    }
+
+    #[test]
+    fn vdf_difficulty_retargets_after_reset_line() {
+        let vdf_config = VDFStepsConfig {
+            vdf_reset_frequency: 10,
+            vdf_difficulty: 1_000,
+            vdf_target_step_time_ms: 1_000,
+            vdf_difficulty_retarget: true,
+            ..VDFStepsConfig::default()
+        };
+
+        // The previous block crossed the reset line at step 10
+        let mut previous_block = IrysBlockHeader::new();
+        previous_block.timestamp = 100_000;
+        previous_block.vdf_limiter_info.global_step_number = 12;
+        previous_block.vdf_limiter_info.steps = H256List(vec![H256::random(); 4]);
+        previous_block.vdf_limiter_info.vdf_difficulty = Some(900);
+        previous_block.vdf_limiter_info.next_vdf_difficulty = Some(1_000);
+
+        // Steps took twice as long as the target, so the schedule moves a
+        // quarter of the way towards half the difficulty
+        let mut block = IrysBlockHeader::new();
+        block.timestamp = 104_000;
+        block.vdf_limiter_info.global_step_number = 14;
+        block.vdf_limiter_info.vdf_difficulty = Some(1_000);
+        block.vdf_limiter_info.next_vdf_difficulty = Some(1_000);
+        assert!(vdf_difficulties_are_valid(&block, &previous_block, &vdf_config).is_err());
+        block.vdf_limiter_info.next_vdf_difficulty = Some(875);
+        assert!(vdf_difficulties_are_valid(&block, &previous_block, &vdf_config).is_ok());
+
+        // Later blocks in the same reset period inherit the schedule
+        let mut next_block = IrysBlockHeader::new();
+        next_block.timestamp = 200_000;
+        next_block.vdf_limiter_info.global_step_number = 16;
+        next_block.vdf_limiter_info.steps = H256List(vec![H256::random(); 2]);
+        next_block.vdf_limiter_info.vdf_difficulty = Some(1_000);
+        next_block.vdf_limiter_info.next_vdf_difficulty = Some(875);
+        block.vdf_limiter_info.steps = H256List(vec![H256::random(); 2]);
+        assert!(vdf_difficulties_are_valid(&next_block, &block, &vdf_config).is_ok());
+
+        // And the block crossing the next reset line switches to it for the steps after it
+        let mut crossing_block = IrysBlockHeader::new();
+        crossing_block.vdf_limiter_info.global_step_number = 22;
+        crossing_block.vdf_limiter_info.steps = H256List(vec![H256::random(); 6]);
+        crossing_block.vdf_limiter_info.vdf_difficulty = Some(1_000);
+        crossing_block.vdf_limiter_info.next_vdf_difficulty = Some(875);
+        assert!(vdf_difficulties_are_valid(&crossing_block, &next_block, &vdf_config).is_ok());
+        let info = &crossing_block.vdf_limiter_info;
+        assert_eq!(step_vdf_difficulty(&vdf_config, info, 19), 1_000);
+        assert_eq!(step_vdf_difficulty(&vdf_config, info, 20), 875);
+    }
+
+    #[test]
+    fn timestamp_must_increase_and_not_be_too_far_ahead() {
+        let now = 1_000_000;
+        let mut previous_block = IrysBlockHeader::new();
+        previous_block.timestamp = now - 5_000;
+
+        let mut block = IrysBlockHeader::new();
+        block.timestamp = now;
+        assert!(timestamp_is_valid(&block, &previous_block, now).is_ok());
+
+        block.timestamp = previous_block.timestamp;
+        assert!(timestamp_is_valid(&block, &previous_block, now).is_err());
+
+        block.timestamp = now + CONFIG.max_future_block_time_ms as u128;
+        assert!(timestamp_is_valid(&block, &previous_block, now).is_ok());
+        block.timestamp += 1;
+        assert!(timestamp_is_valid(&block, &previous_block, now).is_err());
+    }
+
+    #[actix::test]
+    async fn block_with_wrong_next_vdf_difficulty_is_rejected() {
+        let context = init().await;
+        let vdf_config = VDFStepsConfig {
+            vdf_reset_frequency: 10,
+            vdf_difficulty: 1_000,
+            vdf_difficulty_retarget: true,
+            ..VDFStepsConfig::default()
+        };
+
+        let mut previous_block = IrysBlockHeader::new();
+        previous_block.vdf_limiter_info.global_step_number = 12;
+        previous_block.vdf_limiter_info.output = H256::random();
+        previous_block.vdf_limiter_info.seed = H256::random();
+        previous_block.vdf_limiter_info.next_seed = H256::random();
+        previous_block.vdf_limiter_info.vdf_difficulty = Some(1_000);
+        previous_block.vdf_limiter_info.next_vdf_difficulty = Some(1_000);
+
+        // Valid up to the VDF difficulty schedule, which it doubles ahead of the next reset line
+        let mut block = IrysBlockHeader::new();
+        block.timestamp = previous_block.timestamp + 1_000;
+        block.chunk_hash = sha::sha256(&block.poa.chunk.0).into();
+        block.vdf_limiter_info.global_step_number = 14;
+        block.vdf_limiter_info.prev_output = previous_block.vdf_limiter_info.output;
+        block.vdf_limiter_info.seed = previous_block.vdf_limiter_info.seed;
+        block.vdf_limiter_info.next_seed = previous_block.vdf_limiter_info.next_seed;
+        block.vdf_limiter_info.vdf_difficulty = Some(1_000);
+        block.vdf_limiter_info.next_vdf_difficulty = Some(2_000);
+
+        let block_index_guard = context
+            .block_index_actor
+            .send(GetBlockIndexGuardMessage)
+            .await
+            .unwrap();
+        let steps_guard = VdfStepsReadGuard::new(Arc::new(RwLock::new(VdfState {
+            global_step: 0,
+            max_seeds_num: 0,
+            seeds: VecDeque::new(),
+        })));
+
+        let err = prevalidate_block(
+            block,
+            previous_block,
+            block_index_guard,
+            context.partitions_guard,
+            context.storage_config,
+            DifficultyAdjustmentConfig::default(),
+            vdf_config,
+            steps_guard,
+            context.miner_address,
+        )
+        .await
+        .expect_err("block with a wrong next_vdf_difficulty should be rejected");
+        assert!(
+            err.to_string().contains("next_vdf_difficulty"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
     #[actix::test]
     async fn poa_test_3_complete_txs() {
         let chunk_size: usize = 32;
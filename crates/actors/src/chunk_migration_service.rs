@@ -15,7 +15,10 @@ use reth_db::Database;
 use std::sync::{Arc, RwLock};
 use tracing::error;
 
-use crate::block_producer::BlockFinalizedMessage;
+use crate::{
+    block_producer::BlockFinalizedMessage,
+    shutdown::{Drain, InFlight},
+};
 
 /// Central coordinator for chunk storage operations.
 ///
@@ -34,6 +37,8 @@ pub struct ChunkMigrationService {
     pub storage_modules: Vec<Arc<StorageModule>>,
     /// Persistent database for storing chunk metadata and indices
     pub db: Option<DatabaseProvider>,
+    /// Block migrations still writing chunks to the storage modules
+    in_flight: InFlight,
 }
 
 impl Actor for ChunkMigrationService {
@@ -53,6 +58,7 @@ impl ChunkMigrationService {
             storage_config,
             storage_modules,
             db: Some(db),
+            in_flight: InFlight::default(),
         }
     }
 }
@@ -66,6 +72,17 @@ impl SystemService for ChunkMigrationService {
     }
 }
 
+/// The migrations run as futures outside the actor's mailbox, so drained once
+/// they all completed
+impl Handler<Drain> for ChunkMigrationService {
+    type Result = ResponseFuture<()>;
+
+    fn handle(&mut self, _msg: Drain, _ctx: &mut Context<Self>) -> Self::Result {
+        let in_flight = self.in_flight.clone();
+        Box::pin(async move { in_flight.completed().await })
+    }
+}
+
 impl Handler<BlockFinalizedMessage> for ChunkMigrationService {
     type Result = ResponseFuture<eyre::Result<()>>;
 
@@ -83,6 +100,7 @@ impl Handler<BlockFinalizedMessage> for ChunkMigrationService {
         let chunk_size = self.storage_config.chunk_size as usize;
         let storage_modules = Arc::new(self.storage_modules.clone());
         let db = Arc::new(self.db.clone().unwrap());
+        let in_flight = self.in_flight.start();
 
         // Extract transactions for each ledger
         let submit_tx_count = block.ledgers[Ledger::Submit].tx_ids.len();
@@ -90,6 +108,7 @@ impl Handler<BlockFinalizedMessage> for ChunkMigrationService {
         let publish_txs = all_txs[submit_tx_count..].to_vec();
 
         Box::pin(async move {
+            let _in_flight = in_flight;
             // Process Submit ledger transactions
             process_ledger_transactions(
                 &block,
@@ -6,10 +6,12 @@ use irys_database::{block_header_by_hash, data_ledger::*, database};
 use irys_storage::{ie, StorageModuleInfo};
 use irys_types::{
     partition::{PartitionAssignment, PartitionHash},
-    DatabaseProvider, IrysBlockHeader, SimpleRNG, StorageConfig, CONFIG, H256,
+    u64_stringify, Address, DatabaseProvider, IrysBlockHeader, SimpleRNG, StorageConfig, CONFIG,
+    H256,
 };
 use openssl::sha;
 use reth_db::Database;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     sync::{Arc, RwLock, RwLockReadGuard},
@@ -20,6 +22,7 @@ use tracing::{debug, error, trace, warn};
 use crate::block_index_service::{
     BlockIndexReadGuard, BlockIndexService, GetBlockIndexGuardMessage,
 };
+use crate::event_service::{EventService, NodeEvent};
 
 /// Allows for overriding of the consensus parameters for ledgers and partitions
 #[derive(Debug, Clone)]
@@ -51,6 +54,21 @@ pub struct PartitionAssignments {
     pub data_partitions: HashMap<PartitionHash, PartitionAssignment>,
     /// Available capacity partitions mapped by partition hash
     pub capacity_partitions: HashMap<PartitionHash, PartitionAssignment>,
+    /// Assignment changes of each partition, in the order the epochs made them
+    pub history: HashMap<PartitionHash, Vec<AssignmentChange>>,
+}
+
+/// An assignment of a partition made by an epoch, a `None` ledger means it
+/// became (or was created as) a capacity partition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentChange {
+    /// Height of the epoch block that made the change
+    #[serde(with = "u64_stringify")]
+    pub epoch_height: u64,
+    pub miner_address: Address,
+    pub ledger_id: Option<u32>,
+    pub slot_index: Option<usize>,
 }
 
 /// Implementation helper functions
@@ -66,6 +84,7 @@ impl PartitionAssignments {
         Self {
             data_partitions: HashMap::new(),
             capacity_partitions: HashMap::new(),
+            history: HashMap::new(),
         }
     }
 
@@ -76,6 +95,27 @@ impl PartitionAssignments {
             .copied()
             .or(self.capacity_partitions.get(&partition_hash).copied())
     }
+
+    /// Returns the assignment changes of a partition, oldest first
+    pub fn get_history(&self, partition_hash: H256) -> &[AssignmentChange] {
+        self.history
+            .get(&partition_hash)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Records a partition's new assignment
+    fn record_change(&mut self, assignment: &PartitionAssignment, epoch_height: u64) {
+        self.history
+            .entry(assignment.partition_hash)
+            .or_default()
+            .push(AssignmentChange {
+                epoch_height,
+                miner_address: assignment.miner_address,
+                ledger_id: assignment.ledger_id,
+                slot_index: assignment.slot_index,
+            });
+    }
 }
 
 /// Temporarily track all of the ledger definitions inside the epoch service actor
@@ -89,6 +129,8 @@ pub struct EpochServiceActor {
     pub partition_assignments: Arc<RwLock<PartitionAssignments>>,
     /// Sequential list of activated partition hashes
     pub all_active_partitions: Vec<PartitionHash>,
+    /// Height of the epoch block being (or last) processed
+    pub epoch_height: u64,
     /// Current partition & ledger parameters
     pub config: EpochServiceConfig,
 }
@@ -107,7 +149,12 @@ impl Handler<NewEpochMessage> for EpochServiceActor {
     fn handle(&mut self, msg: NewEpochMessage, _ctx: &mut Self::Context) -> Self::Result {
         let new_epoch_block = msg.0;
 
-        self.perform_epoch_tasks(new_epoch_block)?;
+        self.perform_epoch_tasks(new_epoch_block.clone())?;
+
+        EventService::publish(NodeEvent::EpochTransition {
+            block_hash: new_epoch_block.block_hash,
+            epoch_height: new_epoch_block.height,
+        });
 
         Ok(())
     }
@@ -249,6 +296,7 @@ impl EpochServiceActor {
             ledgers: Arc::new(RwLock::new(Ledgers::new())),
             partition_assignments: Arc::new(RwLock::new(PartitionAssignments::new())),
             all_active_partitions: Vec::new(),
+            epoch_height: 0,
             config,
         }
     }
@@ -324,6 +372,7 @@ impl EpochServiceActor {
             "Performing epoch tasks for {} ({})",
             &new_epoch_block.block_hash, &new_epoch_block.height
         );
+        self.epoch_height = new_epoch_block.height;
 
         self.try_genesis_init(&new_epoch_block);
 
@@ -533,6 +582,7 @@ impl EpochServiceActor {
 
         debug!("Adding {} capacity partitions", &parts_to_add);
         // Compute the partition hashes for all of the added partitions
+        let first_added = self.all_active_partitions.len();
         for _i in 0..parts_to_add {
             let next_part_hash = H256(hash_sha256(&prev_partition_hash.0).unwrap());
             trace!(
@@ -558,6 +608,10 @@ impl EpochServiceActor {
                 },
             );
         }
+        for partition_hash in &self.all_active_partitions[first_added..] {
+            let assignment = pa.capacity_partitions[partition_hash];
+            pa.record_change(&assignment, self.epoch_height);
+        }
     }
 
     // Updates PartitionAssignment information about a partition hash, marking
@@ -572,6 +626,7 @@ impl EpochServiceActor {
 
             // Add to capacity pool
             pa.capacity_partitions.insert(partition_hash, assignment);
+            pa.record_change(&assignment, self.epoch_height);
         }
     }
 
@@ -594,6 +649,7 @@ impl EpochServiceActor {
             assignment.ledger_id = Some(ledger as u32);
             assignment.slot_index = Some(slot_index);
             pa.data_partitions.insert(partition_hash, assignment);
+            pa.record_change(&assignment, self.epoch_height);
         }
     }
 
@@ -831,6 +887,17 @@ mod tests {
                     }
                 )
             }
+
+            // Data partitions were created as capacity partitions, then
+            // assigned to a slot by the same epoch
+            for (partition_hash, assignment) in &pa.data_partitions {
+                let history = pa.get_history(*partition_hash);
+                assert_eq!(history.len(), 2);
+                assert_eq!(history[0].ledger_id, None);
+                assert_eq!(history[1].ledger_id, assignment.ledger_id);
+                assert_eq!(history[1].slot_index, assignment.slot_index);
+                assert!(history.iter().all(|change| change.epoch_height == 0));
+            }
         }
 
         // Debug output for verification
@@ -0,0 +1,250 @@
+use actix::prelude::*;
+use irys_types::{u64_stringify, Address, H256};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+/// Number of events buffered per subscriber, a subscriber that falls further
+/// behind than this misses the oldest events
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Events emitted by the node's actors, streamed to API subscribers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum NodeEvent {
+    /// The block tree selected a new canonical tip
+    #[serde(rename_all = "camelCase")]
+    NewTip {
+        block_hash: H256,
+        #[serde(with = "u64_stringify")]
+        height: u64,
+        miner_address: Address,
+    },
+    /// A block was migrated to the block index
+    #[serde(rename_all = "camelCase")]
+    BlockFinalized {
+        block_hash: H256,
+        #[serde(with = "u64_stringify")]
+        height: u64,
+        tx_ids: Vec<H256>,
+    },
+    /// The mempool accepted a transaction
+    #[serde(rename_all = "camelCase")]
+    TxAccepted {
+        tx_id: H256,
+        signer: Address,
+        data_root: H256,
+    },
+    /// The mempool rejected a transaction. The tx id and signer are only set once
+    /// the tx's signature was verified, they aren't for an invalid signature.
+    #[serde(rename_all = "camelCase")]
+    TxRejected {
+        tx_id: Option<H256>,
+        signer: Option<Address>,
+        data_root: H256,
+        reason: String,
+    },
+    /// A transaction was promoted to the Publish ledger with an ingress proof
+    #[serde(rename_all = "camelCase")]
+    TxPromoted {
+        tx_id: H256,
+        signer: Address,
+        data_root: H256,
+        block_hash: H256,
+        #[serde(with = "u64_stringify")]
+        height: u64,
+    },
+    /// The epoch service performed the epoch tasks of a new epoch block
+    #[serde(rename_all = "camelCase")]
+    EpochTransition {
+        block_hash: H256,
+        #[serde(with = "u64_stringify")]
+        epoch_height: u64,
+    },
+}
+
+impl NodeEvent {
+    /// The event name, used as the SSE event type
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::NewTip { .. } => "newTip",
+            Self::BlockFinalized { .. } => "blockFinalized",
+            Self::TxAccepted { .. } => "txAccepted",
+            Self::TxRejected { .. } => "txRejected",
+            Self::TxPromoted { .. } => "txPromoted",
+            Self::EpochTransition { .. } => "epochTransition",
+        }
+    }
+
+    /// Whether the event passes every filter that is set. Events without the
+    /// filtered field never match, e.g. an epoch transition has no address.
+    pub fn matches(&self, filter: &EventFilter) -> bool {
+        let address_matches = filter.address.map_or(true, |address| match self {
+            Self::NewTip { miner_address, .. } => *miner_address == address,
+            Self::TxAccepted { signer, .. } | Self::TxPromoted { signer, .. } => *signer == address,
+            Self::TxRejected { signer, .. } => *signer == Some(address),
+            Self::BlockFinalized { .. } | Self::EpochTransition { .. } => false,
+        });
+        let tx_id_matches = filter.tx_id.map_or(true, |id| match self {
+            Self::BlockFinalized { tx_ids, .. } => tx_ids.contains(&id),
+            Self::TxAccepted { tx_id, .. } | Self::TxPromoted { tx_id, .. } => *tx_id == id,
+            Self::TxRejected { tx_id, .. } => *tx_id == Some(id),
+            Self::NewTip { .. } | Self::EpochTransition { .. } => false,
+        });
+        let data_root_matches = filter.data_root.map_or(true, |root| match self {
+            Self::TxAccepted { data_root, .. }
+            | Self::TxRejected { data_root, .. }
+            | Self::TxPromoted { data_root, .. } => *data_root == root,
+            Self::NewTip { .. } | Self::BlockFinalized { .. } | Self::EpochTransition { .. } => {
+                false
+            }
+        });
+        address_matches && tx_id_matches && data_root_matches
+    }
+}
+
+/// Restricts a subscription to the events concerning an address, tx or data root
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    pub address: Option<Address>,
+    pub tx_id: Option<H256>,
+    pub data_root: Option<H256>,
+}
+
+/// Publishes an event to all current subscribers
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "()")]
+pub struct PublishEvent(pub NodeEvent);
+
+/// Returns a receiver for all events published from now on
+#[derive(Message, Debug)]
+#[rtype(result = "EventReceiver")]
+pub struct SubscribeEvents;
+
+/// Receiving half of an event subscription
+#[derive(Debug, MessageResponse)]
+pub struct EventReceiver(pub broadcast::Receiver<NodeEvent>);
+
+/// Fans the actors' events out to subscribers
+#[derive(Debug)]
+pub struct EventService {
+    sender: broadcast::Sender<NodeEvent>,
+}
+
+impl Default for EventService {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl EventService {
+    /// Publishes `event` through the registered event service
+    pub fn publish(event: NodeEvent) {
+        Self::from_registry().do_send(PublishEvent(event));
+    }
+}
+
+impl Actor for EventService {
+    type Context = Context<Self>;
+}
+
+/// Adds this actor the the local service registry
+impl Supervised for EventService {}
+
+impl SystemService for EventService {
+    fn service_started(&mut self, _ctx: &mut Context<Self>) {
+        debug!("service started: event_service (Default)");
+    }
+}
+
+impl Handler<PublishEvent> for EventService {
+    type Result = ();
+
+    fn handle(&mut self, msg: PublishEvent, _: &mut Context<Self>) {
+        // Sending only fails when there are no subscribers
+        let _ = self.sender.send(msg.0);
+    }
+}
+
+impl Handler<SubscribeEvents> for EventService {
+    type Result = EventReceiver;
+
+    fn handle(&mut self, _msg: SubscribeEvents, _: &mut Context<Self>) -> Self::Result {
+        EventReceiver(self.sender.subscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix::test]
+    async fn subscribers_receive_published_events() {
+        let event_service = EventService::default().start();
+        let EventReceiver(mut receiver) = event_service.send(SubscribeEvents).await.unwrap();
+
+        let event = NodeEvent::EpochTransition {
+            block_hash: H256::random(),
+            epoch_height: 100,
+        };
+        event_service
+            .send(PublishEvent(event.clone()))
+            .await
+            .unwrap();
+        assert_eq!(receiver.recv().await.unwrap(), event);
+    }
+
+    #[test]
+    fn filters_events() {
+        let signer = Address::random();
+        let tx_id = H256::random();
+        let data_root = H256::random();
+        let accepted = NodeEvent::TxAccepted {
+            tx_id,
+            signer,
+            data_root,
+        };
+        let finalized = NodeEvent::BlockFinalized {
+            block_hash: H256::random(),
+            height: 1,
+            tx_ids: vec![tx_id],
+        };
+
+        assert!(accepted.matches(&EventFilter::default()));
+        assert!(accepted.matches(&EventFilter {
+            address: Some(signer),
+            data_root: Some(data_root),
+            ..Default::default()
+        }));
+        assert!(!accepted.matches(&EventFilter {
+            address: Some(signer),
+            data_root: Some(H256::random()),
+            ..Default::default()
+        }));
+        assert!(finalized.matches(&EventFilter {
+            tx_id: Some(tx_id),
+            ..Default::default()
+        }));
+        assert!(!finalized.matches(&EventFilter {
+            address: Some(signer),
+            ..Default::default()
+        }));
+
+        // a tx with an invalid signature can't be attributed to its claimed signer
+        let rejected = NodeEvent::TxRejected {
+            tx_id: None,
+            signer: None,
+            data_root,
+            reason: String::from("InvalidSignature"),
+        };
+        assert!(rejected.matches(&EventFilter {
+            data_root: Some(data_root),
+            ..Default::default()
+        }));
+        assert!(!rejected.matches(&EventFilter {
+            address: Some(signer),
+            ..Default::default()
+        }));
+    }
+}
@@ -1,4 +1,5 @@
 mod addresses;
+pub mod balance_service;
 pub mod block_discovery;
 pub mod block_index_service;
 pub mod block_producer;
@@ -7,10 +8,13 @@ pub mod block_validation;
 pub mod broadcast_mining_service;
 pub mod chunk_migration_service;
 pub mod epoch_service;
+pub mod event_service;
 pub mod mempool_service;
 pub mod mining;
 pub mod packing;
 pub mod reth_service;
+pub mod shutdown;
+pub mod solution_aggregator;
 pub mod validation_service;
 pub mod vdf_service;
 
@@ -3,25 +3,30 @@ use base58::ToBase58;
 use eyre::eyre;
 use irys_database::db_cache::data_size_to_chunk_count;
 use irys_database::tables::{CachedChunks, CachedChunksIndex, IngressProofs};
-use irys_database::{insert_tx_header, tx_header_by_txid, Ledger};
+use irys_database::{insert_tx_header, insert_tx_inclusion, tx_header_by_txid, Ledger};
+use irys_reth_node_bridge::node::RethNodeProvider;
 use irys_storage::StorageModuleVec;
 use irys_types::irys::IrysSigner;
 use irys_types::{
     app_state::DatabaseProvider, chunk::UnpackedChunk, hash_sha256, validate_path,
     IrysTransactionHeader, H256,
 };
-use irys_types::{DataRoot, StorageConfig, CONFIG, U256};
+use irys_types::{Address, DataRoot, StorageConfig, CONFIG, U256};
 use reth::tasks::TaskExecutor;
 use reth_db::cursor::DbDupCursorRO;
 use reth_db::transaction::DbTx;
 use reth_db::transaction::DbTxMut;
 use reth_db::Database;
+use reth_metrics::metrics::counter;
 use std::collections::HashSet;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
 use tracing::{debug, error, info};
 
+use crate::balance_service::{AccountBalance, BalanceService};
 use crate::block_producer::BlockConfirmedMessage;
+use crate::event_service::{EventService, NodeEvent};
+use crate::shutdown::Drain;
 /// The Mempool oversees pending transactions and validation of incoming tx.
 #[derive(Debug, Default)]
 pub struct MempoolService {
@@ -36,6 +41,8 @@ pub struct MempoolService {
     invalid_tx: Vec<H256>,
     storage_config: StorageConfig,
     storage_modules: StorageModuleVec,
+    /// Account balances, reserving the fees of the txs in `valid_tx`
+    balances: Option<BalanceService>,
 }
 
 impl Actor for MempoolService {
@@ -53,16 +60,19 @@ impl SystemService for MempoolService {
 
 impl MempoolService {
     /// Create a new instance of the mempool actor passing in a reference
-    /// counted reference to a `DatabaseEnv`, a copy of reth's task executor and the miner's signer
+    /// counted reference to a `DatabaseEnv`, a copy of reth's task executor and the miner's signer.
+    /// The reth node is used to account for pending EVM txs in account balances.
     pub fn new(
         db: DatabaseProvider,
         task_exec: TaskExecutor,
         signer: IrysSigner,
         storage_config: StorageConfig,
         storage_modules: StorageModuleVec,
+        reth_node: Option<RethNodeProvider>,
     ) -> Self {
         println!("service started: mempool");
         Self {
+            balances: Some(BalanceService::new(db.clone(), reth_node)),
             db: Some(db),
             valid_tx: BTreeMap::new(),
             invalid_tx: Vec::new(),
@@ -102,6 +112,15 @@ impl TxIngressError {
     pub fn other_display(err: impl Display) -> Self {
         Self::Other(err.to_string())
     }
+    /// The variant name, used as a metric label
+    pub const fn metric_label(&self) -> &'static str {
+        match self {
+            Self::InvalidSignature => "invalid_signature",
+            Self::Unfunded => "unfunded",
+            Self::Skipped => "skipped",
+            Self::Other(_) => "other",
+        }
+    }
 }
 
 /// Message for when a new chunk is discovered by the node, either though
@@ -142,13 +161,66 @@ impl ChunkIngressError {
     pub fn other_display(err: impl Display) -> Self {
         Self::Other(err.to_string())
     }
+    /// The variant name, used as a metric label
+    pub const fn metric_label(&self) -> &'static str {
+        match self {
+            Self::InvalidProof => "invalid_proof",
+            Self::InvalidDataHash => "invalid_data_hash",
+            Self::UnknownTransaction => "unknown_transaction",
+            Self::InvalidChunkSize => "invalid_chunk_size",
+            Self::DatabaseError => "database_error",
+            Self::Other(_) => "other",
+        }
+    }
+}
+
+impl Handler<Drain> for MempoolService {
+    type Result = ();
+
+    fn handle(&mut self, _msg: Drain, _ctx: &mut Context<Self>) -> Self::Result {}
 }
 
 impl Handler<TxIngressMessage> for MempoolService {
     type Result = Result<(), TxIngressError>;
 
     fn handle(&mut self, tx_msg: TxIngressMessage, _ctx: &mut Context<Self>) -> Self::Result {
-        if self.db.is_none() {
+        let (tx_id, signer, data_root) = (tx_msg.0.id, tx_msg.0.signer, tx_msg.0.data_root);
+        let result = self.ingress_tx(tx_msg);
+        let label = result
+            .as_ref()
+            .err()
+            .map_or("ok", TxIngressError::metric_label);
+        counter!("irys_mempool_tx_ingress", "result" => label).increment(1);
+
+        match &result {
+            Ok(()) => EventService::publish(NodeEvent::TxAccepted {
+                tx_id,
+                signer,
+                data_root,
+            }),
+            // Already known txs were reported when first received
+            Err(TxIngressError::Skipped) => {}
+            // The tx id & signer of a tx with an invalid signature are only claimed
+            Err(TxIngressError::InvalidSignature) => EventService::publish(NodeEvent::TxRejected {
+                tx_id: None,
+                signer: None,
+                data_root,
+                reason: format!("{:?}", TxIngressError::InvalidSignature),
+            }),
+            Err(err) => EventService::publish(NodeEvent::TxRejected {
+                tx_id: Some(tx_id),
+                signer: Some(signer),
+                data_root,
+                reason: format!("{:?}", err),
+            }),
+        }
+        result
+    }
+}
+
+impl MempoolService {
+    fn ingress_tx(&mut self, tx_msg: TxIngressMessage) -> Result<(), TxIngressError> {
+        if self.db.is_none() || self.balances.is_none() {
             return Err(TxIngressError::Other(
                 "mempool_service not initialized".to_string(),
             ));
@@ -167,27 +239,27 @@ impl Handler<TxIngressMessage> for MempoolService {
             return Err(TxIngressError::Skipped);
         }
 
-        let db = self.db.clone().unwrap();
-
-        // TODO: Don't unwrap here
-        if irys_database::get_account_balance(&db.tx().unwrap(), tx_msg.0.signer).unwrap()
-            < U256::from(tx_msg.0.total_fee())
-        {
-            return Err(TxIngressError::Unfunded);
-        }
-
-        // Validate the transaction signature
+        // Validate the transaction signature, so the signer is known for the checks below
         if tx.is_signature_valid() {
             println!("Signature is valid");
-            self.valid_tx.insert(tx.id, tx.clone());
         } else {
             self.invalid_tx.push(tx.id);
             println!("Signature is NOT valid");
             return Err(TxIngressError::InvalidSignature);
         }
 
-        // TODO: Check if the signer has funds to post the tx
-        //return Err(TxIngressError::Unfunded);
+        let balances = self.balances.as_mut().unwrap();
+        let fee = U256::from(tx.total_fee());
+
+        // The fee has to be covered by what's left once the signer's pending txs are included
+        let balance = balances
+            .balance(tx.signer)
+            .map_err(TxIngressError::other_display)?;
+        if balance.available() < fee {
+            return Err(TxIngressError::Unfunded);
+        }
+        balances.reserve(tx.signer, fee);
+        self.valid_tx.insert(tx.id, tx.clone());
 
         // Cache the data_root in the database
 
@@ -205,6 +277,18 @@ impl Handler<ChunkIngressMessage> for MempoolService {
     type Result = Result<(), ChunkIngressError>;
 
     fn handle(&mut self, chunk_msg: ChunkIngressMessage, _ctx: &mut Context<Self>) -> Self::Result {
+        let result = self.ingress_chunk(chunk_msg);
+        let label = result
+            .as_ref()
+            .err()
+            .map_or("ok", ChunkIngressError::metric_label);
+        counter!("irys_mempool_chunk_ingress", "result" => label).increment(1);
+        result
+    }
+}
+
+impl MempoolService {
+    fn ingress_chunk(&mut self, chunk_msg: ChunkIngressMessage) -> Result<(), ChunkIngressError> {
         // TODO: maintain a shared read transaction so we have read isolation
         let chunk: UnpackedChunk = chunk_msg.0;
 
@@ -380,26 +464,26 @@ impl Handler<GetBestMempoolTxs> for MempoolService {
     type Result = Vec<IrysTransactionHeader>;
 
     fn handle(&mut self, _msg: GetBestMempoolTxs, _ctx: &mut Self::Context) -> Self::Result {
-        let db = self.db.clone().unwrap();
-        let mut fees_spent_per_address = HashMap::new();
+        let balances = self.balances.as_ref().unwrap();
+        // What each signer has left to spend on the fees of the selected txs
+        let mut spendable_per_address: HashMap<Address, U256> = HashMap::new();
 
         // TODO sort by fee
         self.valid_tx
             .iter()
             .filter(|(_, tx)| {
-                let current_spent = *fees_spent_per_address.get(&tx.signer).unwrap_or(&0_u64);
-                let valid = irys_database::get_account_balance(&db.tx().unwrap(), tx.signer)
-                    .unwrap()
-                    >= U256::from(current_spent + tx.total_fee());
-                match fees_spent_per_address.get_mut(&tx.signer) {
-                    Some(val) => {
-                        *val += tx.total_fee();
-                    }
-                    None => {
-                        fees_spent_per_address.insert(tx.signer, tx.total_fee());
-                    }
-                };
-                valid
+                let spendable = spendable_per_address.entry(tx.signer).or_insert_with(|| {
+                    balances.spendable_on_fees(tx.signer).unwrap_or_else(|e| {
+                        error!("Failed to read the balance of {}: {}", tx.signer, e);
+                        U256::zero()
+                    })
+                });
+                let fee = U256::from(tx.total_fee());
+                if *spendable < fee {
+                    return false;
+                }
+                *spendable -= fee;
+                true
             })
             .take(CONFIG.max_data_txs_per_block.try_into().unwrap())
             .map(|(_, header)| header.clone())
@@ -420,27 +504,42 @@ impl Handler<BlockConfirmedMessage> for MempoolService {
         let all_txs = &msg.1;
 
         for txid in block.ledgers[Ledger::Submit].tx_ids.iter() {
-            // Remove the submit tx from the pending valid_tx pool
-            self.valid_tx.remove(txid);
+            // Remove the submit tx from the pending valid_tx pool, its fee is now
+            // spent from the confirmed balance
+            if let Some(tx) = self.valid_tx.remove(txid) {
+                if let Some(balances) = self.balances.as_mut() {
+                    balances.release(tx.signer, U256::from(tx.total_fee()));
+                }
+            }
         }
 
         let published_txids = &block.ledgers[Ledger::Publish].tx_ids.0;
 
+        let mut_tx = self
+            .db
+            .clone()
+            .unwrap()
+            .tx_mut()
+            .map_err(|e| {
+                error!("Failed to create mdbx transaction: {}", e);
+            })
+            .unwrap();
+
+        // Index the block including each tx, so txs can be located without
+        // scanning block headers
+        for ledger in [Ledger::Submit, Ledger::Publish] {
+            for txid in block.ledgers[ledger].tx_ids.iter() {
+                if let Err(err) = insert_tx_inclusion(&mut_tx, *txid, ledger, block.block_hash) {
+                    error!("Could not index the block including tx {}: {}", txid, err);
+                }
+            }
+        }
+
         // Loop though the promoted transactions and remove their ingress proofs
         // from the mempool. In the future on a multi node network we may keep
         // ingress proofs around longer to account for re-orgs, but for now
         // we just remove them.
         if !published_txids.is_empty() {
-            let mut_tx = self
-                .db
-                .clone()
-                .unwrap()
-                .tx_mut()
-                .map_err(|e| {
-                    error!("Failed to create mdbx transaction: {}", e);
-                })
-                .unwrap();
-
             for (i, txid) in block.ledgers[Ledger::Publish].tx_ids.0.iter().enumerate() {
                 // Retrieve the promoted transactions header
                 let mut tx_header = match tx_header_by_txid(&mut_tx, txid) {
@@ -478,12 +577,20 @@ impl Handler<BlockConfirmedMessage> for MempoolService {
                     error!("DatabaseError deleting ingress proof err: {}", err);
                 }
 
+                EventService::publish(NodeEvent::TxPromoted {
+                    tx_id: *txid,
+                    signer: tx_header.signer,
+                    data_root: tx_header.data_root,
+                    block_hash: block.block_hash,
+                    height: block.height,
+                });
+
                 info!("Promoted tx:\n{:?}", tx_header);
             }
-
-            let _ = mut_tx.commit();
         }
 
+        let _ = mut_tx.commit();
+
         info!(
             "Removing confirmed tx - Block height: {} num tx: {}",
             block.height,
@@ -493,6 +600,48 @@ impl Handler<BlockConfirmedMessage> for MempoolService {
     }
 }
 
+/// Message for getting a tx from the mempool, `None` if it isn't pending
+#[derive(Message, Debug)]
+#[rtype(result = "Option<IrysTransactionHeader>")]
+pub struct GetPendingTxMessage(pub H256);
+
+impl Handler<GetPendingTxMessage> for MempoolService {
+    type Result = Option<IrysTransactionHeader>;
+
+    fn handle(&mut self, msg: GetPendingTxMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.valid_tx.get(&msg.0).cloned()
+    }
+}
+
+/// Message for getting the number of pending txs in the mempool
+#[derive(Message, Debug)]
+#[rtype(result = "usize")]
+pub struct GetMempoolSizeMessage;
+
+impl Handler<GetMempoolSizeMessage> for MempoolService {
+    type Result = usize;
+
+    fn handle(&mut self, _msg: GetMempoolSizeMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.valid_tx.len()
+    }
+}
+
+/// Message for getting the balance of an account, including its pending txs
+#[derive(Message, Debug)]
+#[rtype(result = "eyre::Result<AccountBalance>")]
+pub struct GetAccountBalanceMessage(pub Address);
+
+impl Handler<GetAccountBalanceMessage> for MempoolService {
+    type Result = eyre::Result<AccountBalance>;
+
+    fn handle(&mut self, msg: GetAccountBalanceMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.balances
+            .as_ref()
+            .ok_or_else(|| eyre!("mempool_service is uninitialized"))?
+            .balance(msg.0)
+    }
+}
+
 /// Generates an ingress proof for a specific `data_root`
 /// pulls required data from all sources
 pub fn generate_ingress_proof(
@@ -650,6 +799,7 @@ mod tests {
             IrysSigner::random_signer(),
             storage_config,
             vec![storage_module.clone()],
+            None,
         );
         let addr: Addr<MempoolService> = mempool.start();
 
@@ -12,6 +12,8 @@ use irys_types::block_production::Seed;
 use irys_types::{block_production::SolutionContext, H256, U256};
 use irys_types::{Address, AtomicVdfStepNumber, H256List, PartitionChunkOffset};
 use openssl::sha;
+use reth_metrics::metrics::counter;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -246,6 +248,14 @@ impl Handler<BroadcastMiningSeed> for PartitionMiningActor {
         match self.mine_partition_with_seed(seed.into_inner(), msg.global_step, msg.checkpoints) {
             Ok(Some(s)) => match self.block_producer_actor.try_send(SolutionFoundMessage(s)) {
                 Ok(_) => {
+                    counter!(
+                        "irys_solutions_found",
+                        "partition_hash" => self
+                            .storage_module
+                            .partition_hash()
+                            .map_or_else(|| String::from("unassigned"), |hash| hash.to_string())
+                    )
+                    .increment(1);
                     // debug!("Solution sent!");
                 }
                 Err(err) => error!("Error submitting solution to block producer {:?}", err),
@@ -298,6 +308,32 @@ impl Handler<MiningControl> for PartitionMiningActor {
     }
 }
 
+/// Mining state of a partition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, MessageResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionMiningStatus {
+    pub storage_module_id: usize,
+    pub partition_hash: Option<H256>,
+    pub mining: bool,
+}
+
+#[derive(Message, Debug)]
+#[rtype(result = "PartitionMiningStatus")]
+/// Message for getting the mining state of a partition
+pub struct GetMiningStatus;
+
+impl Handler<GetMiningStatus> for PartitionMiningActor {
+    type Result = PartitionMiningStatus;
+
+    fn handle(&mut self, _msg: GetMiningStatus, _ctx: &mut Context<Self>) -> Self::Result {
+        PartitionMiningStatus {
+            storage_module_id: self.storage_module.id,
+            partition_hash: self.storage_module.partition_hash(),
+            mining: self.should_mine,
+        }
+    }
+}
+
 pub fn hash_to_number(hash: &[u8]) -> U256 {
     U256::from_little_endian(hash)
 }
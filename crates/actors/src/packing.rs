@@ -1,6 +1,6 @@
 use std::{
     collections::{HashMap, VecDeque},
-    sync::{Arc, RwLock},
+    sync::{Arc, RwLock, RwLockWriteGuard},
     time::Duration,
 };
 
@@ -18,6 +18,7 @@ use {
 use irys_storage::{ChunkType, InclusiveInterval, StorageModule};
 use irys_types::{PartitionChunkRange, StorageConfig};
 use reth::tasks::TaskExecutor;
+use reth_metrics::metrics::counter;
 use tokio::{runtime::Handle, sync::Semaphore, time::sleep};
 use tracing::{debug, warn};
 
@@ -167,6 +168,11 @@ impl PackingActor {
                             //debug!(target: "irys::packing", "Writing chunk range {} to SM {}", &i, &storage_module.id);
                             storage_module.write_chunk(i, out, ChunkType::Entropy);
                             let _ = storage_module.sync_pending_chunks();
+                            counter!(
+                                "irys_packed_chunks",
+                                "storage_module" => storage_module_id.to_string()
+                            )
+                            .increment(1);
                             drop(permit); // drop after chunk write so the SM can apply backpressure to packing
                         });
 
@@ -222,6 +228,11 @@ impl PackingActor {
                                 );
                                 let _ = storage_module.sync_pending_chunks();
                             }
+                            counter!(
+                                "irys_packed_chunks",
+                                "storage_module" => storage_module_id.to_string()
+                            )
+                            .increment(num_chunks as u64);
                             drop(permit); // drop after chunk write so the SM can apply backpressure to packing
                         });
                         debug!(target: "irys::packing::update", "CUDA Packed chunks {} - {} for SM {} partition_hash {} mining_address {} iterations {}", chunk_range_split.start(), chunk_range_split.end(), &storage_module_id, &partition_hash, &mining_address, &entropy_packing_iterations);
@@ -295,6 +306,34 @@ pub struct Internals {
     config: PackingConfig,
 }
 
+impl Internals {
+    /// Returns the number of queued packing jobs for a storage module, and the
+    /// number of chunks they cover
+    pub fn queue_depth(&self, storage_module_id: usize) -> (usize, u64) {
+        let Some(jobs) = self.pending_jobs.get(&storage_module_id) else {
+            return (0, 0);
+        };
+        let jobs = jobs.read().unwrap();
+        let chunks = jobs
+            .iter()
+            .map(|job| (job.chunk_range.end() - job.chunk_range.start()) as u64 + 1)
+            .sum();
+        (jobs.len(), chunks)
+    }
+
+    /// Returns the chunk ranges of a storage module's queued packing jobs, the
+    /// first one being packed
+    pub fn jobs(&self, storage_module_id: usize) -> Vec<PartitionChunkRange> {
+        self.pending_jobs
+            .get(&storage_module_id)
+            .map(|jobs| {
+                let jobs = jobs.read().unwrap();
+                jobs.iter().map(|job| job.chunk_range).collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
 impl Handler<GetInternals> for PackingActor {
     type Result = Internals;
 
@@ -307,6 +346,73 @@ impl Handler<GetInternals> for PackingActor {
     }
 }
 
+/// Removes a queued packing job, the job at index 0 is being packed and can't be cancelled
+#[derive(Debug, Message, Clone)]
+#[rtype("eyre::Result<()>")]
+pub struct CancelPackingJob {
+    pub storage_module_id: usize,
+    pub index: usize,
+}
+
+impl Handler<CancelPackingJob> for PackingActor {
+    type Result = eyre::Result<()>;
+
+    fn handle(&mut self, msg: CancelPackingJob, _ctx: &mut Self::Context) -> Self::Result {
+        let mut jobs = self.queued_jobs(msg.storage_module_id, msg.index)?;
+        let job = jobs.remove(msg.index);
+        debug!(target: "irys::packing", "Cancelled packing job {:?} for SM {}", job.map(|job| job.chunk_range), &msg.storage_module_id);
+        Ok(())
+    }
+}
+
+/// Moves a queued packing job right behind the job being packed
+#[derive(Debug, Message, Clone)]
+#[rtype("eyre::Result<()>")]
+pub struct PrioritizePackingJob {
+    pub storage_module_id: usize,
+    pub index: usize,
+}
+
+impl Handler<PrioritizePackingJob> for PackingActor {
+    type Result = eyre::Result<()>;
+
+    fn handle(&mut self, msg: PrioritizePackingJob, _ctx: &mut Self::Context) -> Self::Result {
+        let mut jobs = self.queued_jobs(msg.storage_module_id, msg.index)?;
+        if let Some(job) = jobs.remove(msg.index) {
+            jobs.insert(1, job);
+        }
+        Ok(())
+    }
+}
+
+impl PackingActor {
+    /// Write guard over a storage module's job queue, if `index` is a job that
+    /// isn't being packed yet
+    fn queued_jobs(
+        &self,
+        storage_module_id: usize,
+        index: usize,
+    ) -> eyre::Result<RwLockWriteGuard<'_, VecDeque<PackingRequest>>> {
+        let jobs = self
+            .pending_jobs
+            .get(&storage_module_id)
+            .ok_or_else(|| eyre!("Unknown storage module {}", storage_module_id))?
+            .write()
+            .unwrap();
+        if index == 0 {
+            return Err(eyre!("Packing job 0 is in progress"));
+        }
+        if index >= jobs.len() {
+            return Err(eyre!(
+                "No packing job {} for storage module {}",
+                index,
+                storage_module_id
+            ));
+        }
+        Ok(jobs)
+    }
+}
+
 /// waits for any pending & active packing tasks to complete
 pub async fn wait_for_packing(
     packing_addr: Addr<PackingActor>,
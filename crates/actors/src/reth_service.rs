@@ -2,18 +2,33 @@ use actix::{
     Actor, ActorTryFutureExt as _, AtomicResponse, Context, Handler, Message, ResponseFuture,
     Supervised, SystemService, WrapFuture,
 };
+use alloy_rpc_types_engine::ExecutionPayloadEnvelopeV1Irys;
 use eyre::{eyre, OptionExt};
 use irys_database::database;
-use irys_reth_node_bridge::{adapter::node::RethNodeContext, node::RethNodeProvider};
+use irys_primitives::ShadowTx;
+use irys_reth_node_bridge::{
+    adapter::node::RethNodeContext,
+    node::RethNodeProvider,
+    precompile::prefetch::{prefetch_best_pd_chunks, prefetch_payload_pd_chunks},
+};
+use irys_storage::reth_provider::IrysRethProvider;
 use irys_types::{DatabaseProvider, H256};
-use reth::{primitives::BlockNumberOrTag, revm::primitives::B256, rpc::eth::EthApiServer as _};
+use reth::{
+    primitives::BlockNumberOrTag,
+    providers::BlockReader as _,
+    revm::primitives::B256,
+    rpc::{eth::EthApiServer as _, types::engine::PayloadStatus},
+};
 use reth_db::Database as _;
-use tracing::{debug, error, info};
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
 
 #[derive(Debug, Default)]
 pub struct RethServiceActor {
     pub handle: Option<RethNodeProvider>,
     pub db: Option<DatabaseProvider>,
+    /// Gives access to the PD chunk cache, which chunks are prefetched into before execution
+    pub irys_provider: Option<IrysRethProvider>,
     // we store a copy of the latest FCU so we can always provide reth with a "full" FCU, as the finalized field is used to control the block persistence mechanism.
     pub latest_fcu: ForkChoiceUpdate,
 }
@@ -27,10 +42,15 @@ pub struct ForkChoiceUpdate {
 
 // todo: move the entire reth process in here
 impl RethServiceActor {
-    pub fn new(handle: RethNodeProvider, database_provider: DatabaseProvider) -> Self {
+    pub fn new(
+        handle: RethNodeProvider,
+        database_provider: DatabaseProvider,
+        irys_provider: IrysRethProvider,
+    ) -> Self {
         Self {
             handle: Some(handle),
             db: Some(database_provider),
+            irys_provider: Some(irys_provider),
             latest_fcu: ForkChoiceUpdate::default(),
         }
     }
@@ -216,3 +236,109 @@ impl Handler<ForkChoiceUpdateMessage> for RethServiceActor {
         ))
     }
 }
+
+/// Hands an execution payload received alongside a block from another node to
+/// reth for execution, returning the status reth reports for it.
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "eyre::Result<PayloadStatus>")]
+pub struct NewPayloadMessage(pub Arc<ExecutionPayloadEnvelopeV1Irys>);
+
+impl Handler<NewPayloadMessage> for RethServiceActor {
+    type Result = ResponseFuture<eyre::Result<PayloadStatus>>;
+
+    fn handle(&mut self, msg: NewPayloadMessage, _ctx: &mut Self::Context) -> Self::Result {
+        let handle = self.handle.clone();
+        let irys_provider = self.irys_provider.clone();
+        Box::pin(async move {
+            let handle = handle.ok_or_eyre("Reth service is uninitialized!")?;
+            let context = RethNodeContext::new(handle.into())
+                .await
+                .map_err(|e| eyre!("Error connecting to Reth: {}", e))?;
+
+            let execution_payload = msg.0.execution_payload.clone();
+            let block_hash = execution_payload
+                .payload_inner
+                .payload_inner
+                .payload_inner
+                .block_hash;
+
+            // the PD precompile only reads unpacked chunks from the cache
+            if let Some(provider) = irys_provider.and_then(|p| p.get().cloned()) {
+                let transactions = execution_payload
+                    .payload_inner
+                    .payload_inner
+                    .payload_inner
+                    .transactions
+                    .clone();
+                tokio::task::spawn_blocking(move || {
+                    prefetch_payload_pd_chunks(&provider, &transactions)
+                })
+                .await?
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "Unable to prefetch PD chunks for payload {}: {}",
+                        &block_hash, e
+                    )
+                });
+            }
+
+            let status = context
+                .engine_api
+                .submit_execution_payload(execution_payload)
+                .await
+                .map_err(|e| eyre!("Error submitting payload {} to reth: {}", &block_hash, e))?;
+
+            debug!("Reth payload {} status: {:?}", &block_hash, &status);
+            Ok(status)
+        })
+    }
+}
+
+/// Reads the shadows reth executed for the imported EVM block `0`, as stored
+/// in its block body.
+#[derive(Message, Debug, Clone, Copy)]
+#[rtype(result = "eyre::Result<Vec<ShadowTx>>")]
+pub struct ExecutedShadowsMessage(pub B256);
+
+impl Handler<ExecutedShadowsMessage> for RethServiceActor {
+    type Result = eyre::Result<Vec<ShadowTx>>;
+
+    fn handle(&mut self, msg: ExecutedShadowsMessage, _ctx: &mut Self::Context) -> Self::Result {
+        let handle = self
+            .handle
+            .as_ref()
+            .ok_or_eyre("Reth service is uninitialized!")?;
+        let block = handle
+            .provider
+            .block_by_hash(msg.0)?
+            .ok_or_else(|| eyre!("EVM block {} not found", &msg.0))?;
+        Ok(block
+            .body
+            .shadows
+            .map(|shadows| shadows.into_iter().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Prefetches the chunks read by the PD transactions the next payload will be
+/// built from, so they're unpacked before the payload builder executes them.
+#[derive(Message, Debug, Clone, Copy)]
+#[rtype(result = "eyre::Result<()>")]
+pub struct PrefetchPdChunksMessage;
+
+impl Handler<PrefetchPdChunksMessage> for RethServiceActor {
+    type Result = ResponseFuture<eyre::Result<()>>;
+
+    fn handle(&mut self, _msg: PrefetchPdChunksMessage, _ctx: &mut Self::Context) -> Self::Result {
+        let handle = self.handle.clone();
+        let irys_provider = self.irys_provider.clone();
+        Box::pin(async move {
+            let handle = handle.ok_or_eyre("Reth service is uninitialized!")?;
+            let Some(provider) = irys_provider.and_then(|p| p.get().cloned()) else {
+                return Ok(());
+            };
+            tokio::task::spawn_blocking(move || prefetch_best_pd_chunks(&handle.pool, &provider))
+                .await?
+        })
+    }
+}
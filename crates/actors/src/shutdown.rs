@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use actix::Message;
+use tokio::sync::watch;
+
+/// Stages a node goes through when shutting down, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShutdownStage {
+    Running,
+    /// Shutdown was requested, the API servers stop accepting requests
+    Requested,
+    /// Mining and the VDF stopped and the storage modules were flushed, so
+    /// reth can exit
+    ActorsStopped,
+    /// Reth exited, the process can exit
+    Complete,
+}
+
+/// Shared handle to request a node shutdown and follow its progress
+#[derive(Debug, Clone)]
+pub struct Shutdown(Arc<watch::Sender<ShutdownStage>>);
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self(Arc::new(watch::channel(ShutdownStage::Running).0))
+    }
+}
+
+impl Shutdown {
+    pub fn stage(&self) -> ShutdownStage {
+        *self.0.borrow()
+    }
+
+    /// Moves the shutdown on to `stage`, stages never go backwards
+    pub fn advance(&self, stage: ShutdownStage) {
+        self.0.send_if_modified(|current| {
+            let advanced = stage > *current;
+            if advanced {
+                *current = stage;
+            }
+            advanced
+        });
+    }
+
+    pub fn request(&self) {
+        self.advance(ShutdownStage::Requested)
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.stage() >= ShutdownStage::Requested
+    }
+
+    /// Waits until the shutdown reaches `stage`
+    pub async fn reached(&self, stage: ShutdownStage) {
+        let mut receiver = self.0.subscribe();
+        // the sender lives as long as `self`, so this can't fail
+        let _ = receiver.wait_for(|current| *current >= stage).await;
+    }
+}
+
+/// Answered once an actor handled the messages queued before it, so the node
+/// can let actors finish their pending writes before flushing storage
+#[derive(Debug, Message, Clone)]
+#[rtype("()")]
+pub struct Drain;
+
+/// Counts the tasks an actor started that haven't completed yet, e.g. the futures
+/// of its handlers, so its [`Drain`] handler can wait for them
+#[derive(Debug, Clone)]
+pub struct InFlight(Arc<watch::Sender<usize>>);
+
+impl Default for InFlight {
+    fn default() -> Self {
+        Self(Arc::new(watch::channel(0).0))
+    }
+}
+
+impl InFlight {
+    /// Counts a task until the returned guard is dropped
+    pub fn start(&self) -> InFlightGuard {
+        self.0.send_modify(|count| *count += 1);
+        InFlightGuard(self.clone())
+    }
+
+    pub fn count(&self) -> usize {
+        *self.0.borrow()
+    }
+
+    /// Waits until every task started so far completed
+    pub async fn completed(&self) {
+        let mut receiver = self.0.subscribe();
+        // the sender lives as long as `self`, so this can't fail
+        let _ = receiver.wait_for(|count| *count == 0).await;
+    }
+}
+
+/// Completes an [`InFlight`] task when dropped
+#[derive(Debug)]
+pub struct InFlightGuard(InFlight);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0 .0.send_modify(|count| *count -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix::test]
+    async fn stages_only_advance() {
+        let shutdown = Shutdown::default();
+        assert!(!shutdown.is_requested());
+
+        let waiter = {
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move { shutdown.reached(ShutdownStage::ActorsStopped).await })
+        };
+        shutdown.request();
+        shutdown.advance(ShutdownStage::ActorsStopped);
+        waiter.await.unwrap();
+
+        shutdown.request();
+        assert_eq!(shutdown.stage(), ShutdownStage::ActorsStopped);
+    }
+
+    #[actix::test]
+    async fn waits_for_in_flight_tasks() {
+        let in_flight = InFlight::default();
+        // nothing to wait for
+        in_flight.completed().await;
+
+        let (first, second) = (in_flight.start(), in_flight.start());
+        assert_eq!(in_flight.count(), 2);
+        let waiter = {
+            let in_flight = in_flight.clone();
+            tokio::spawn(async move { in_flight.completed().await })
+        };
+        drop(first);
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        drop(second);
+        waiter.await.unwrap();
+        assert_eq!(in_flight.count(), 0);
+    }
+}
@@ -0,0 +1,208 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use actix::prelude::*;
+use alloy_rpc_types_engine::ExecutionPayloadEnvelopeV1Irys;
+use irys_types::{block_production::SolutionContext, IrysBlockHeader};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::{
+    block_producer::SolutionFoundMessage, block_tree_service::BlockTreeReadGuard,
+    mining::hash_to_number,
+};
+
+/// How long solutions for a VDF step are collected before the best one is
+/// handed to the block producer. Partitions mine the same step at roughly the
+/// same time, so this only needs to cover the spread between them.
+pub const DEFAULT_SOLUTION_COLLECTION_WINDOW: Duration = Duration::from_millis(100);
+
+/// `SolutionAggregatorActor` sits between the `PartitionMiningActors` and the
+/// `BlockProducerActor`. It collects the solutions found for each VDF step
+/// across all partitions and only forwards the best one, so partitions solving
+/// the same step don't trigger redundant block production attempts.
+#[derive(Debug)]
+pub struct SolutionAggregatorActor {
+    /// Where the best solution of each step is sent
+    pub block_producer: Recipient<SolutionFoundMessage>,
+    /// Used to discard solutions already included in a block
+    pub block_tree_guard: BlockTreeReadGuard,
+    /// How long to collect solutions for a step before submitting the best one
+    pub collection_window: Duration,
+    /// Best solution found so far for each step still being collected
+    pending: BTreeMap<u64, SolutionContext>,
+    /// The VDF step of the last solution handed to the block producer
+    last_submitted_step: u64,
+}
+
+impl SolutionAggregatorActor {
+    /// Initializes a new `SolutionAggregatorActor`
+    pub const fn new(
+        block_producer: Recipient<SolutionFoundMessage>,
+        block_tree_guard: BlockTreeReadGuard,
+        collection_window: Duration,
+    ) -> Self {
+        Self {
+            block_producer,
+            block_tree_guard,
+            collection_window,
+            pending: BTreeMap::new(),
+            last_submitted_step: 0,
+        }
+    }
+
+    /// Hands the best solution collected for `step` to the block producer and
+    /// drops any solutions left over for earlier steps.
+    fn submit(&mut self, step: u64) {
+        let Some(solution) = self.pending.remove(&step) else {
+            return;
+        };
+        self.pending.retain(|pending_step, _| *pending_step > step);
+
+        if self
+            .block_tree_guard
+            .read()
+            .is_known_solution_hash(&solution.solution_hash)
+        {
+            debug!(
+                "Discarding solution {} for step {}, already known",
+                solution.solution_hash, step
+            );
+            return;
+        }
+
+        info!(
+            "Submitting best solution {} for step {} from partition {}",
+            solution.solution_hash, step, solution.partition_hash
+        );
+        self.last_submitted_step = step;
+        self.block_producer.do_send(SolutionFoundMessage(solution));
+    }
+}
+
+impl Actor for SolutionAggregatorActor {
+    type Context = Context<Self>;
+}
+
+impl Handler<SolutionFoundMessage> for SolutionAggregatorActor {
+    type Result = eyre::Result<Option<(Arc<IrysBlockHeader>, ExecutionPayloadEnvelopeV1Irys)>>;
+
+    fn handle(&mut self, msg: SolutionFoundMessage, ctx: &mut Self::Context) -> Self::Result {
+        let solution = msg.0;
+        let step = solution.vdf_step;
+
+        if step <= self.last_submitted_step {
+            debug!(
+                "Discarding solution for step {}, already submitted a solution for step {}",
+                step, self.last_submitted_step
+            );
+            return Ok(None);
+        }
+
+        if self
+            .block_tree_guard
+            .read()
+            .is_known_solution_hash(&solution.solution_hash)
+        {
+            debug!(
+                "Discarding solution {} for step {}, already known",
+                solution.solution_hash, step
+            );
+            return Ok(None);
+        }
+
+        match self.pending.get_mut(&step) {
+            Some(best) => {
+                if hash_to_number(&solution.solution_hash.0) > hash_to_number(&best.solution_hash.0)
+                {
+                    *best = solution;
+                }
+            }
+            None => {
+                self.pending.insert(step, solution);
+                ctx.run_later(self.collection_window, move |act, _ctx| act.submit(step));
+            }
+        }
+
+        // Blocks are produced asynchronously once the collection window closes
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_producer::BlockProducerMockActor;
+    use crate::block_tree_service::BlockTreeCache;
+    use irys_types::H256;
+    use std::any::Any;
+    use std::sync::{Mutex, RwLock};
+    use tokio::time::sleep;
+
+    fn solution(vdf_step: u64, solution_hash: H256) -> SolutionContext {
+        SolutionContext {
+            vdf_step,
+            solution_hash,
+            partition_hash: H256::random(),
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn submits_best_solution_per_step() {
+        let received: Arc<Mutex<Vec<SolutionContext>>> = Arc::new(Mutex::new(Vec::new()));
+        let closure_received = received.clone();
+        let mocked_block_producer = BlockProducerMockActor::mock(Box::new(move |msg, _ctx| {
+            let solution_message: SolutionFoundMessage =
+                *msg.downcast::<SolutionFoundMessage>().unwrap();
+            closure_received.lock().unwrap().push(solution_message.0);
+
+            let inner_result = None::<(Arc<IrysBlockHeader>, ExecutionPayloadEnvelopeV1Irys)>;
+            Box::new(Some(inner_result)) as Box<dyn Any>
+        }));
+        let block_producer = mocked_block_producer.start().recipient();
+
+        let mut genesis = IrysBlockHeader::new();
+        genesis.solution_hash = H256::random();
+        let block_tree_guard =
+            BlockTreeReadGuard::new(Arc::new(RwLock::new(BlockTreeCache::new(&genesis))));
+
+        let aggregator = SolutionAggregatorActor::new(
+            block_producer,
+            block_tree_guard,
+            Duration::from_millis(20),
+        )
+        .start();
+
+        let low = H256::from_low_u64_le(1);
+        let high = H256::repeat_byte(0xff);
+        let mid = H256::from_low_u64_le(1000);
+        for solution_hash in [low, high, mid] {
+            aggregator
+                .send(SolutionFoundMessage(solution(5, solution_hash)))
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        // Solutions already in the block tree are dropped
+        aggregator
+            .send(SolutionFoundMessage(solution(6, genesis.solution_hash)))
+            .await
+            .unwrap()
+            .unwrap();
+
+        sleep(Duration::from_millis(100)).await;
+
+        // Solutions for steps at or below the last submitted one are stale
+        aggregator
+            .send(SolutionFoundMessage(solution(5, H256::random())))
+            .await
+            .unwrap()
+            .unwrap();
+        sleep(Duration::from_millis(100)).await;
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1, "Only one solution should be submitted");
+        assert_eq!(received[0].vdf_step, 5);
+        assert_eq!(received[0].solution_hash, high);
+    }
+}
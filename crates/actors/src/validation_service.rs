@@ -10,7 +10,7 @@ use tracing::error;
 use crate::{
     block_index_service::BlockIndexReadGuard,
     block_tree_service::{BlockTreeService, ValidationResult, ValidationResultMessage},
-    block_validation::poa_is_valid,
+    block_validation::{poa_is_valid, timed_stage},
     epoch_service::PartitionAssignmentsReadGuard,
 };
 
@@ -81,8 +81,9 @@ impl Handler<RequestValidationMessage> for ValidationService {
         let poa = block.poa.clone();
 
         // Spawn VDF validation first
-        let vdf_future =
-            tokio::task::spawn_blocking(move || vdf_steps_are_valid(&vdf_info, &vdf_config));
+        let vdf_future = tokio::task::spawn_blocking(move || {
+            timed_stage("vdf_steps", || vdf_steps_are_valid(&vdf_info, &vdf_config))
+        });
 
         // Wait for results before processing next message
         ctx.wait(
@@ -91,13 +92,15 @@ impl Handler<RequestValidationMessage> for ValidationService {
                     Ok(_) => {
                         // VDF passed, now spawn and run PoA validation
                         let poa_future = tokio::task::spawn_blocking(move || {
-                            poa_is_valid(
-                                &poa,
-                                &block_index_guard,
-                                &partitions_guard,
-                                &storage_config,
-                                &miner_address,
-                            )
+                            timed_stage("poa", || {
+                                poa_is_valid(
+                                    &poa,
+                                    &block_index_guard,
+                                    &partitions_guard,
+                                    &storage_config,
+                                    &miner_address,
+                                )
+                            })
                         });
 
                         match poa_future.await.unwrap() {
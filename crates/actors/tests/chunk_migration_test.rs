@@ -141,6 +141,7 @@ async fn finalize_block_test() -> eyre::Result<()> {
         arc_config.mining_signer.clone(),
         storage_config.clone(),
         storage_modules.clone(),
+        None,
     );
     SystemRegistry::set(mempool_service.start());
     let mempool_addr = MempoolService::from_registry();
@@ -1,30 +1,294 @@
-use actix_web::{body::BoxBody, HttpResponse, ResponseError};
+use actix::MailboxError;
+use actix_web::{
+    body::BoxBody,
+    error::{JsonPayloadError, PathError, QueryPayloadError},
+    HttpRequest, HttpResponse, ResponseError,
+};
+use irys_actors::mempool_service::{ChunkIngressError, TxIngressError};
 use serde::{Deserialize, Serialize};
+use tracing::debug;
 
 use awc::http::StatusCode;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug)]
 pub enum ApiError {
-    ErrNoId { id: String, err: String },
-    Internal { err: String },
+    ErrNoId {
+        id: String,
+        err: String,
+    },
+    Internal {
+        err: String,
+    },
+    /// A path, query or body parameter is malformed
+    BadRequest {
+        err: String,
+    },
+    /// The request body could not be deserialized
+    InvalidPayload {
+        err: String,
+    },
+    /// The caller exceeded an ingress rate limit
+    RateLimited {
+        err: String,
+    },
+    TxIngress(TxIngressError),
+    ChunkIngress(ChunkIngressError),
 }
 
-impl ResponseError for ApiError {
-    fn status_code(&self) -> StatusCode {
+/// Stable, machine readable error codes, clients should match on these rather
+/// than on the message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    Internal,
+    BadRequest,
+    InvalidPayload,
+    RateLimited,
+    MethodNotAllowed,
+    BadGateway,
+    Unavailable,
+    InvalidSignature,
+    Unfunded,
+    InvalidProof,
+    InvalidDataHash,
+    InvalidChunkSize,
+    UnknownTransaction,
+}
+
+impl ErrorCode {
+    pub const ALL: [Self; 14] = [
+        Self::NotFound,
+        Self::Internal,
+        Self::BadRequest,
+        Self::InvalidPayload,
+        Self::RateLimited,
+        Self::MethodNotAllowed,
+        Self::BadGateway,
+        Self::Unavailable,
+        Self::InvalidSignature,
+        Self::Unfunded,
+        Self::InvalidProof,
+        Self::InvalidDataHash,
+        Self::InvalidChunkSize,
+        Self::UnknownTransaction,
+    ];
+
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::BadRequest
+            | Self::InvalidPayload
+            | Self::InvalidSignature
+            | Self::InvalidProof
+            | Self::InvalidDataHash
+            | Self::InvalidChunkSize
+            | Self::UnknownTransaction => StatusCode::BAD_REQUEST,
+            Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            Self::BadGateway => StatusCode::BAD_GATEWAY,
+            Self::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Unfunded => StatusCode::PAYMENT_REQUIRED,
+        }
+    }
+}
+
+/// The JSON body of every error response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorResponse {
+    pub code: ErrorCode,
+    pub message: String,
+    /// The id that wasn't found, for `not_found` errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl ErrorResponse {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            id: None,
+        }
+    }
+
+    pub fn into_http_response(self) -> HttpResponse<BoxBody> {
+        HttpResponse::build(self.code.status_code()).json(self)
+    }
+}
+
+impl ApiError {
+    pub const fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::ErrNoId { .. } => ErrorCode::NotFound,
+            ApiError::Internal { .. } => ErrorCode::Internal,
+            ApiError::BadRequest { .. } => ErrorCode::BadRequest,
+            ApiError::InvalidPayload { .. } => ErrorCode::InvalidPayload,
+            ApiError::RateLimited { .. } => ErrorCode::RateLimited,
+            ApiError::TxIngress(err) => match err {
+                TxIngressError::InvalidSignature => ErrorCode::InvalidSignature,
+                TxIngressError::Unfunded => ErrorCode::Unfunded,
+                // Reposting a known tx isn't an error, handlers answer it with a 200
+                TxIngressError::Skipped | TxIngressError::Other(_) => ErrorCode::Internal,
+            },
+            ApiError::ChunkIngress(err) => match err {
+                ChunkIngressError::InvalidProof => ErrorCode::InvalidProof,
+                ChunkIngressError::InvalidDataHash => ErrorCode::InvalidDataHash,
+                ChunkIngressError::InvalidChunkSize => ErrorCode::InvalidChunkSize,
+                ChunkIngressError::UnknownTransaction => ErrorCode::UnknownTransaction,
+                ChunkIngressError::DatabaseError | ChunkIngressError::Other(_) => {
+                    ErrorCode::Internal
+                }
+            },
+        }
+    }
+
+    pub fn message(&self) -> String {
         match self {
-            ApiError::ErrNoId { .. } => StatusCode::NOT_FOUND,
-            ApiError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::ErrNoId { err, .. }
+            | ApiError::Internal { err }
+            | ApiError::BadRequest { err }
+            | ApiError::InvalidPayload { err }
+            | ApiError::RateLimited { err } => err.clone(),
+            ApiError::TxIngress(err) => match err {
+                TxIngressError::InvalidSignature => String::from("Invalid signature"),
+                TxIngressError::Unfunded => String::from("Unfunded"),
+                TxIngressError::Skipped => String::from("Transaction already processed"),
+                TxIngressError::Other(err) => format!("Failed to ingest transaction: {}", err),
+            },
+            ApiError::ChunkIngress(err) => match err {
+                ChunkIngressError::InvalidProof => String::from("Invalid proof"),
+                ChunkIngressError::InvalidDataHash => String::from("Invalid data_hash"),
+                ChunkIngressError::InvalidChunkSize => String::from("Invalid chunk size"),
+                ChunkIngressError::UnknownTransaction => String::from("Unknown transaction"),
+                ChunkIngressError::DatabaseError => String::from("Failed to store chunk"),
+                ChunkIngressError::Other(err) => format!("Failed to ingest chunk: {}", err),
+            },
         }
     }
 
+    pub fn to_response(&self) -> ErrorResponse {
+        ErrorResponse {
+            id: match self {
+                ApiError::ErrNoId { id, .. } => Some(id.clone()),
+                _ => None,
+            },
+            ..ErrorResponse::new(self.code(), self.message())
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.code().status_code()
+    }
+
     fn error_response(&self) -> HttpResponse<BoxBody> {
-        let body = serde_json::to_string(&self).unwrap();
-        let res = HttpResponse::new(self.status_code());
-        res.set_body(BoxBody::new(body))
+        self.to_response().into_http_response()
     }
 }
 impl std::fmt::Display for ApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "{}", self.message())
+    }
+}
+
+impl From<TxIngressError> for ApiError {
+    fn from(err: TxIngressError) -> Self {
+        Self::TxIngress(err)
+    }
+}
+
+impl From<ChunkIngressError> for ApiError {
+    fn from(err: ChunkIngressError) -> Self {
+        Self::ChunkIngress(err)
+    }
+}
+
+impl From<MailboxError> for ApiError {
+    fn from(err: MailboxError) -> Self {
+        Self::Internal {
+            err: format!("Failed to deliver message: {}", err),
+        }
+    }
+}
+
+/// `JsonConfig` error handler, answers malformed bodies with an error envelope
+pub fn json_error_handler(err: JsonPayloadError, req: &HttpRequest) -> actix_web::Error {
+    debug!("JSON decode error for req {:?} - {:?}", req.path(), &err);
+    ApiError::InvalidPayload {
+        err: err.to_string(),
+    }
+    .into()
+}
+
+/// `PathConfig` error handler, answers malformed path parameters with an error envelope
+pub fn path_error_handler(err: PathError, req: &HttpRequest) -> actix_web::Error {
+    debug!("Path decode error for req {:?} - {:?}", req.path(), &err);
+    ApiError::BadRequest {
+        err: err.to_string(),
+    }
+    .into()
+}
+
+/// `QueryConfig` error handler, answers malformed query strings with an error envelope
+pub fn query_error_handler(err: QueryPayloadError, req: &HttpRequest) -> actix_web::Error {
+    debug!("Query decode error for req {:?} - {:?}", req.path(), &err);
+    ApiError::BadRequest {
+        err: err.to_string(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_errors_to_codes_and_statuses() {
+        let cases = [
+            (
+                ApiError::TxIngress(TxIngressError::Unfunded),
+                "unfunded",
+                402,
+            ),
+            (
+                ApiError::ChunkIngress(ChunkIngressError::InvalidProof),
+                "invalid_proof",
+                400,
+            ),
+            (
+                ApiError::ChunkIngress(ChunkIngressError::DatabaseError),
+                "internal",
+                500,
+            ),
+            (
+                ApiError::InvalidPayload {
+                    err: String::from("EOF while parsing"),
+                },
+                "invalid_payload",
+                400,
+            ),
+        ];
+        for (err, code, status) in cases {
+            let body = serde_json::to_value(err.to_response()).unwrap();
+            assert_eq!(body["code"], code);
+            assert_eq!(err.status_code().as_u16(), status);
+        }
+
+        let not_found = ApiError::ErrNoId {
+            id: String::from("abc"),
+            err: String::from("tx not found"),
+        };
+        assert_eq!(
+            not_found.to_response(),
+            ErrorResponse {
+                code: ErrorCode::NotFound,
+                message: String::from("tx not found"),
+                id: Some(String::from("abc")),
+            }
+        );
     }
 }
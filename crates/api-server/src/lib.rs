@@ -1,25 +1,40 @@
 pub mod error;
+pub mod openapi;
+pub mod rate_limit;
 pub mod routes;
-use std::sync::Arc;
+pub mod rpc;
+use std::{net::Ipv4Addr, sync::Arc};
 
 use actix::Addr;
 use actix_cors::Cors;
 use actix_web::{
-    dev::HttpServiceFactory,
-    error::InternalError,
-    web::{self, JsonConfig},
-    App, HttpResponse, HttpServer,
+    dev::{HttpServiceFactory, Server},
+    web::{self, JsonConfig, PathConfig, QueryConfig},
+    App, HttpServer,
 };
 
+use error::{json_error_handler, path_error_handler, query_error_handler};
 use irys_actors::{
-    block_index_service::BlockIndexReadGuard, block_tree_service::BlockTreeReadGuard,
+    block_index_service::BlockIndexReadGuard,
+    block_tree_service::BlockTreeReadGuard,
+    epoch_service::{LedgersReadGuard, PartitionAssignmentsReadGuard},
     mempool_service::MempoolService,
+    shutdown::ShutdownStage,
+    vdf_service::VdfStepsReadGuard,
+    ActorAddresses,
 };
 use irys_reth_node_bridge::node::RethNodeProvider;
 use irys_storage::ChunkProvider;
 use irys_types::{app_state::DatabaseProvider, CONFIG};
-use routes::{block, get_chunk, index, network_config, post_chunk, price, proxy::proxy, tx};
-use tracing::{debug, info};
+use openapi::ApiRoute;
+use rate_limit::IngressLimits;
+use routes::{
+    account, admin, block, events, get_chunk, index, ledger, metrics, network_config, post_chunk,
+    price,
+    proxy::{proxy, RpcMethodFilter},
+    storage, tx,
+};
+use tracing::info;
 
 #[derive(Clone)]
 pub struct ApiState {
@@ -31,61 +46,201 @@ pub struct ApiState {
     pub reth_provider: Option<RethNodeProvider>,
     pub block_tree: Option<BlockTreeReadGuard>,
     pub block_index: Option<BlockIndexReadGuard>,
+    pub partition_assignments: Option<PartitionAssignmentsReadGuard>,
+    pub ledgers: Option<LedgersReadGuard>,
+    pub vdf_steps: Option<VdfStepsReadGuard>,
+    pub actor_addresses: Option<ActorAddresses>,
+}
+
+/// Declares the `/v1` routes once, generating both the actix scope served by
+/// [`routes`] and the [`ROUTES`] table the OpenAPI document is built from
+macro_rules! api_routes {
+    ($($method:ident $path:literal => $handler:path, $summary:literal;)*) => {
+        pub fn routes() -> impl HttpServiceFactory {
+            web::scope("v1")
+                $(.route($path, api_routes!(@method $method).to($handler)))*
+        }
+
+        /// Every route served by [`routes`]
+        pub const ROUTES: &[ApiRoute] = &[$(ApiRoute {
+            method: stringify!($method),
+            path: $path,
+            handler: stringify!($handler),
+            summary: $summary,
+        },)*];
+    };
+    (@method any) => {
+        web::route()
+    };
+    (@method $method:ident) => {
+        web::$method()
+    };
+}
+
+api_routes! {
+    any "/execution-rpc" => proxy, "Proxy JSON-RPC requests to the execution client";
+    get "/info" => index::info_route, "Node status";
+    get "/openapi.json" => openapi::openapi_route, "This OpenAPI document";
+    get "/network/config" => network_config::get_network_config, "Public storage config";
+    get "/block/{block_tag}" => block::get_block, "Block by hash, height or tag";
+    get "/chunk/data_root/{ledger_id}/{data_root}/{offset}" =>
+        get_chunk::get_chunk_by_data_root_offset, "Chunk by data root and offset";
+    get "/chunk/ledger/{ledger_id}/{ledger_offset}" =>
+        get_chunk::get_chunk_by_ledger_offset, "Packed chunk by ledger offset";
+    post "/chunk" => post_chunk::post_chunk, "Upload a chunk";
+    get "/tx/{tx_id}" => tx::get_tx_header_api, "Transaction header";
+    get "/tx/{tx_id}/status" => tx::get_tx_status, "Transaction lifecycle status";
+    get "/tx/{tx_id}/local/data_start_offset" =>
+        tx::get_tx_local_start_offset, "Ledger offset of a transaction's data";
+    post "/tx" => tx::post_tx, "Submit a transaction";
+    get "/price/{ledger}/{size}" => price::get_price, "Price to store data";
+    get "/account/{address}" => account::get_account_balance, "Account balance";
+    get "/storage/modules" => storage::get_storage_modules, "Storage module statuses";
+    get "/storage/modules/{id}" => storage::get_storage_module, "Storage module status";
+    get "/events" => events::subscribe_events, "Server-sent node events";
+    get "/ledgers" => ledger::get_ledgers, "Data ledgers";
+    get "/ledgers/{ledger}/slots" => ledger::get_ledger_slots, "Slots of a data ledger";
+    get "/partitions/{partition_hash}" => ledger::get_partition, "Partition assignment";
 }
 
-pub fn routes() -> impl HttpServiceFactory {
-    web::scope("v1")
+/// Privileged routes, only served by the admin listener on localhost
+pub fn admin_routes() -> impl HttpServiceFactory {
+    // the filter registered by the admin listener lets every method through
+    web::scope("admin")
         .route("/execution-rpc", web::to(proxy))
-        .route("/info", web::get().to(index::info_route))
+        .route("/mining", web::get().to(admin::mining_status))
+        .route("/mining/start", web::post().to(admin::start_mining))
+        .route("/mining/stop", web::post().to(admin::stop_mining))
         .route(
-            "/network/config",
-            web::get().to(network_config::get_network_config),
+            "/packing/{storage_module_id}",
+            web::get().to(admin::packing_jobs),
         )
-        .route("/block/{block_tag}", web::get().to(block::get_block))
         .route(
-            "/chunk/data_root/{ledger_id}/{data_root}/{offset}",
-            web::get().to(get_chunk::get_chunk_by_data_root_offset),
+            "/packing/{storage_module_id}/{index}",
+            web::delete().to(admin::cancel_packing_job),
         )
         .route(
-            "/chunk/ledger/{ledger_id}/{ledger_offset}",
-            web::get().to(get_chunk::get_chunk_by_ledger_offset),
+            "/packing/{storage_module_id}/{index}/prioritize",
+            web::post().to(admin::prioritize_packing_job),
         )
-        .route("/chunk", web::post().to(post_chunk::post_chunk))
-        .route("/tx/{tx_id}", web::get().to(tx::get_tx_header_api))
         .route(
-            "/tx/{tx_id}/local/data_start_offset",
-            web::get().to(tx::get_tx_local_start_offset),
+            "/storage/modules/{id}/scrub",
+            web::post().to(admin::scrub_storage_module),
         )
-        .route("/tx", web::post().to(tx::post_tx))
-        .route("/price/{ledger}/{size}", web::get().to(price::get_price))
+        .route(
+            "/storage/modules/{id}/resync",
+            web::post().to(admin::resync_storage_module),
+        )
+        .route(
+            "/storage/modules/{id}/flush",
+            web::post().to(admin::flush_storage_module),
+        )
+        .route("/shutdown", web::post().to(admin::shutdown))
 }
 
+/// Serves the public API, and the admin API if `admin_port` is set, until a
+/// signal is received or a node shutdown is requested
 pub async fn run_server(app_state: ApiState) {
     info!("Starting API server on port {}", CONFIG.port);
+    let shutdown = app_state
+        .actor_addresses
+        .as_ref()
+        .map(|addresses| addresses.shutdown.clone());
 
-    HttpServer::new(move || {
+    let ingress_limits = web::Data::new(IngressLimits::from_config());
+    let public_state = app_state.clone();
+    let public_server = HttpServer::new(move || {
         let awc_client = awc::Client::new();
         App::new()
-            .app_data(web::Data::new(app_state.clone()))
+            .app_data(web::Data::new(public_state.clone()))
             .app_data(web::Data::new(awc_client))
-            .app_data(
-                JsonConfig::default()
-                    .limit(1024 * 1024) // Set JSON payload limit to 1MB
-                    .error_handler(|err, req| {
-                        debug!("JSON decode error for req {:?} - {:?}", &req.path(), &err);
-                        InternalError::from_response(err, HttpResponse::BadRequest().finish())
-                            .into()
-                    }),
-            )
+            .app_data(web::Data::new(RpcMethodFilter::from_config()))
+            .app_data(ingress_limits.clone())
+            .configure(extractor_config)
             .service(routes())
             .route("/", web::get().to(index::info_route))
-            .wrap(Cors::permissive())
+            .route("/metrics", web::get().to(metrics::metrics))
+            .wrap(cors())
     })
     .bind(("0.0.0.0", CONFIG.port))
     .unwrap()
+    .run();
+    let mut handles = vec![public_server.handle()];
+
+    let admin_server = (CONFIG.admin_port != 0).then(|| {
+        info!(
+            "Starting admin API server on localhost port {}",
+            CONFIG.admin_port
+        );
+        let server = admin_server(app_state);
+        handles.push(server.handle());
+        server
+    });
+
+    let servers = async move {
+        match admin_server {
+            Some(admin_server) => futures::try_join!(public_server, admin_server).map(|_| ()),
+            None => public_server.await,
+        }
+    };
+    let stop_on_shutdown = async move {
+        match shutdown {
+            Some(shutdown) => shutdown.reached(ShutdownStage::Requested).await,
+            None => futures::future::pending().await,
+        }
+        info!("Stopping API servers");
+        for handle in handles {
+            handle.stop(true).await;
+        }
+    };
+
+    tokio::select! {
+        res = servers => res.unwrap(),
+        _ = stop_on_shutdown => {}
+    }
+}
+
+/// The admin API, only bound to localhost
+fn admin_server(app_state: ApiState) -> Server {
+    HttpServer::new(move || {
+        let awc_client = awc::Client::new();
+        App::new()
+            .app_data(web::Data::new(app_state.clone()))
+            .app_data(web::Data::new(awc_client))
+            .app_data(web::Data::new(RpcMethodFilter::new("*", "", true)))
+            .configure(extractor_config)
+            .service(admin_routes())
+    })
+    .bind((Ipv4Addr::LOCALHOST, CONFIG.admin_port))
+    .unwrap()
     .run()
-    .await
-    .unwrap();
+}
+
+/// Body limits and error envelopes for the path, query and JSON extractors
+fn extractor_config(cfg: &mut web::ServiceConfig) {
+    cfg.app_data(
+        JsonConfig::default()
+            .limit(CONFIG.api_json_body_limit)
+            .error_handler(json_error_handler),
+    )
+    .app_data(PathConfig::default().error_handler(path_error_handler))
+    .app_data(QueryConfig::default().error_handler(query_error_handler));
+}
+
+/// CORS policy allowing the configured origins
+fn cors() -> Cors {
+    let origins = CONFIG.api_cors_allowed_origins;
+    if origins.trim() == "*" {
+        return Cors::permissive();
+    }
+    origins
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+        .allow_any_method()
+        .allow_any_header()
+        .max_age(3600)
 }
 
 //==============================================================================
@@ -123,6 +278,7 @@ async fn post_tx_and_chunks_golden_path() {
         IrysSigner::random_signer(),
         storage_config.clone(),
         Arc::new(Vec::new()).to_vec(),
+        None,
     );
     SystemRegistry::set(mempool_service.start());
     let mempool_addr = MempoolService::from_registry();
@@ -140,6 +296,10 @@ async fn post_tx_and_chunks_golden_path() {
         reth_provider: None,
         block_tree: None,
         block_index: None,
+        partition_assignments: None,
+        ledgers: None,
+        vdf_steps: None,
+        actor_addresses: None,
     };
 
     // Initialize the app
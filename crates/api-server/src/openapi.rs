@@ -0,0 +1,136 @@
+use actix_web::HttpResponse;
+use serde_json::{json, Map, Value};
+
+use crate::{error::ErrorCode, ROUTES};
+
+/// A route served under `/v1`, as declared in [`crate::routes`]
+#[derive(Debug, Clone, Copy)]
+pub struct ApiRoute {
+    /// Lowercase HTTP method, `any` for routes accepting every method
+    pub method: &'static str,
+    pub path: &'static str,
+    pub handler: &'static str,
+    pub summary: &'static str,
+}
+
+pub async fn openapi_route() -> HttpResponse {
+    HttpResponse::Ok().json(openapi_spec(ROUTES))
+}
+
+/// Builds an OpenAPI 3 document describing `routes`
+pub fn openapi_spec(routes: &[ApiRoute]) -> Value {
+    let mut paths = Map::new();
+    for route in routes {
+        // the execution RPC proxy accepts any method, but JSON-RPC is POSTed
+        let method = match route.method {
+            "any" => "post",
+            method => method,
+        };
+        let mut operation = json!({
+            "summary": route.summary,
+            "operationId": route.handler.replace("::", "_"),
+            "parameters": path_parameters(route.path),
+            "responses": {
+                "200": { "description": "Success" },
+                "4XX": error_response("Client error"),
+                "5XX": error_response("Server error"),
+            },
+        });
+        if method == "post" {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": {} },
+            });
+        }
+        paths
+            .entry(route.path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("path items are objects")
+            .insert(method.to_string(), operation);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Irys node API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "servers": [{ "url": "/v1" }],
+        "paths": paths,
+        "components": {
+            "schemas": {
+                "ErrorResponse": {
+                    "type": "object",
+                    "required": ["code", "message"],
+                    "properties": {
+                        "code": {
+                            "type": "string",
+                            "enum": ErrorCode::ALL,
+                        },
+                        "message": { "type": "string" },
+                        "id": {
+                            "type": "string",
+                            "description": "The id that wasn't found, for not_found errors",
+                        },
+                    },
+                },
+            },
+        },
+    })
+}
+
+/// The `{name}` segments of `path`
+fn path_parameters(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            })
+        })
+        .collect()
+}
+
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            "application/json": {
+                "schema": { "$ref": "#/components/schemas/ErrorResponse" },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_every_route() {
+        let spec = openapi_spec(ROUTES);
+        let num_operations: usize = spec["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|item| item.as_object().unwrap().len())
+            .sum();
+        assert_eq!(num_operations, ROUTES.len());
+
+        let status = &spec["paths"]["/tx/{tx_id}/status"]["get"];
+        assert_eq!(status["operationId"], "tx_get_tx_status");
+        assert_eq!(status["parameters"][0]["name"], "tx_id");
+        assert_eq!(
+            spec["paths"]["/tx"]["post"]["requestBody"]["required"],
+            true
+        );
+        assert_eq!(
+            spec["components"]["schemas"]["ErrorResponse"]["properties"]["code"]["enum"][0],
+            "not_found"
+        );
+    }
+}
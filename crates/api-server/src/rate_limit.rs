@@ -0,0 +1,169 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use actix_web::HttpRequest;
+use irys_types::{Address, CONFIG, H256};
+
+use crate::error::ApiError;
+
+/// Number of tracked keys above which expired windows are dropped
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Fixed window counter allowing `limit` hits per key per `window`
+#[derive(Debug)]
+pub struct RateLimiter<K> {
+    /// 0 disables the limiter
+    limit: u32,
+    window: Duration,
+    hits: Mutex<HashMap<K, (Instant, u32)>>,
+}
+
+impl<K: Hash + Eq> RateLimiter<K> {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `key` used up its hits in the current window
+    pub fn is_exceeded(&self, key: &K) -> bool {
+        if self.limit == 0 {
+            return false;
+        }
+        let hits = self.hits.lock().unwrap();
+        hits.get(key)
+            .is_some_and(|(started, count)| started.elapsed() < self.window && *count >= self.limit)
+    }
+
+    /// Records a hit for `key`
+    pub fn hit(&self, key: K) {
+        self.record_hit(key, false);
+    }
+
+    /// Records a hit for `key`, returning `false` if it was over the limit
+    pub fn check(&self, key: K) -> bool {
+        self.record_hit(key, true)
+    }
+
+    /// Records a hit for `key`, unless `enforce_limit` is set and `key` used up its
+    /// hits in the current window. The check and the increment share one lock, so
+    /// concurrent requests can't both pass the check for the last hit of a window.
+    fn record_hit(&self, key: K, enforce_limit: bool) -> bool {
+        if self.limit == 0 {
+            return true;
+        }
+        let mut hits = self.hits.lock().unwrap();
+        if hits.len() > PRUNE_THRESHOLD {
+            hits.retain(|_, (started, _)| started.elapsed() < self.window);
+        }
+        let (started, count) = hits.entry(key).or_insert((Instant::now(), 0));
+        if started.elapsed() >= self.window {
+            *started = Instant::now();
+            *count = 0;
+        }
+        if enforce_limit && *count >= self.limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    /// Forgets the hits of `key`
+    pub fn reset(&self, key: &K) {
+        self.hits.lock().unwrap().remove(key);
+    }
+}
+
+/// Limits on tx & chunk ingress through the public API
+#[derive(Debug)]
+pub struct IngressLimits {
+    /// Tx and chunk posts per client IP
+    pub per_ip: RateLimiter<IpAddr>,
+    /// Txs per signer, only accepted txs count so txs with a spoofed signer
+    /// can't use up the signer's allowance
+    pub per_signer: RateLimiter<Address>,
+    /// Chunks rejected because their data root isn't known, until a tx with the
+    /// data root is posted
+    pub unknown_data_roots: RateLimiter<H256>,
+}
+
+impl IngressLimits {
+    pub fn new(per_ip: u32, per_signer: u32, unknown_data_root_chunks: u32) -> Self {
+        let minute = Duration::from_secs(60);
+        Self {
+            per_ip: RateLimiter::new(per_ip, minute),
+            per_signer: RateLimiter::new(per_signer, minute),
+            unknown_data_roots: RateLimiter::new(unknown_data_root_chunks, minute),
+        }
+    }
+
+    pub fn from_config() -> Self {
+        Self::new(
+            CONFIG.api_ingress_rate_limit_per_ip,
+            CONFIG.api_ingress_rate_limit_per_signer,
+            CONFIG.api_max_chunks_per_unknown_data_root,
+        )
+    }
+
+    /// Counts a request against its client's IP
+    pub fn check_ip(&self, req: &HttpRequest) -> Result<(), ApiError> {
+        // requests without a peer address come from in-process test services
+        let Some(peer) = req.peer_addr() else {
+            return Ok(());
+        };
+        if self.per_ip.check(peer.ip()) {
+            Ok(())
+        } else {
+            Err(ApiError::RateLimited {
+                err: format!("Too many requests from {}", peer.ip()),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_hits_per_key_and_window() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(50));
+        assert!(limiter.check(1));
+        assert!(limiter.check(1));
+        assert!(!limiter.check(1));
+        assert!(limiter.check(2));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(limiter.check(1));
+
+        limiter.hit(1);
+        assert!(limiter.is_exceeded(&1));
+        limiter.reset(&1);
+        assert!(!limiter.is_exceeded(&1));
+
+        let disabled = RateLimiter::new(0, Duration::from_secs(60));
+        assert!((0..100).all(|_| disabled.check(1)));
+    }
+
+    #[test]
+    fn concurrent_checks_dont_exceed_the_limit() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60));
+        let passed = std::thread::scope(|scope| {
+            let handles = (0..8)
+                .map(|_| scope.spawn(|| (0..10).filter(|_| limiter.check(1)).count()))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .sum::<usize>()
+        });
+        assert_eq!(passed, 10);
+    }
+}
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use actix_web::{
+    web::{self, Json},
+    HttpResponse,
+};
+use base58::FromBase58;
+use irys_actors::{balance_service::AccountBalance, mempool_service::GetAccountBalanceMessage};
+use irys_types::{Address, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ApiError, ApiState};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalanceResponse {
+    #[serde(flatten)]
+    pub balance: AccountBalance,
+    /// Confirmed balance minus the pending Irys fees & EVM spend
+    pub available: U256,
+}
+
+/// Returns the balance of an account (base58 or 0x-prefixed hex address),
+/// including what its pending Irys and EVM transactions will spend
+pub async fn get_account_balance(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let address = parse_address(&path).ok_or(ApiError::BadRequest {
+        err: String::from("Invalid address"),
+    })?;
+
+    let balance = state
+        .mempool
+        .send(GetAccountBalanceMessage(address))
+        .await?
+        .map_err(|err| ApiError::Internal {
+            err: format!("Failed to read account balance: {}", err),
+        })?;
+    Ok(HttpResponse::Ok().json(AccountBalanceResponse {
+        available: balance.available(),
+        balance,
+    }))
+}
+
+pub(crate) fn parse_address(address: &str) -> Option<Address> {
+    if address.starts_with("0x") {
+        return Address::from_str(address).ok();
+    }
+    let bytes = address.from_base58().ok()?;
+    (bytes.len() == 20).then(|| Address::from_slice(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base58::ToBase58;
+
+    #[test]
+    fn parses_base58_and_hex_addresses() {
+        let address = Address::random();
+        assert_eq!(parse_address(&address.0.to_base58()), Some(address));
+        assert_eq!(parse_address(&address.to_string()), Some(address));
+        assert_eq!(parse_address("not an address"), None);
+        assert_eq!(parse_address(&[1_u8; 19].to_base58()), None);
+    }
+}
@@ -0,0 +1,284 @@
+use std::sync::Arc;
+
+use actix_web::{
+    web::{self, Json},
+    HttpResponse,
+};
+use irys_actors::{
+    mining::PartitionMiningStatus,
+    packing::{CancelPackingJob, GetInternals, PackingRequest, PrioritizePackingJob},
+    ActorAddresses,
+};
+use irys_storage::{
+    find_invalid_packing_starts, ii, ChunkType, InclusiveInterval as _, Interval, StorageModule,
+};
+use irys_types::H256;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{error::ApiError, ApiState};
+
+/// An inclusive range of partition relative chunk offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl From<Interval<u32>> for ChunkRange {
+    fn from(interval: Interval<u32>) -> Self {
+        Self {
+            start: interval.start(),
+            end: interval.end(),
+        }
+    }
+}
+
+/// A queued packing job, the job at index 0 is being packed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackingJob {
+    pub index: usize,
+    pub in_progress: bool,
+    pub chunk_range: ChunkRange,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MiningQuery {
+    /// Only start or stop mining this partition, rather than all of them
+    pub partition_hash: Option<H256>,
+}
+
+pub async fn mining_status(
+    state: web::Data<ApiState>,
+) -> Result<Json<Vec<PartitionMiningStatus>>, ApiError> {
+    let statuses = actor_addresses(&state)?
+        .mining_status()
+        .await
+        .map_err(internal)?;
+    Ok(Json(statuses))
+}
+
+pub async fn start_mining(
+    state: web::Data<ApiState>,
+    query: web::Query<MiningQuery>,
+) -> Result<HttpResponse, ApiError> {
+    set_mining(&state, query.partition_hash, true).await
+}
+
+pub async fn stop_mining(
+    state: web::Data<ApiState>,
+    query: web::Query<MiningQuery>,
+) -> Result<HttpResponse, ApiError> {
+    set_mining(&state, query.partition_hash, false).await
+}
+
+async fn set_mining(
+    state: &ApiState,
+    partition_hash: Option<H256>,
+    should_mine: bool,
+) -> Result<HttpResponse, ApiError> {
+    let addresses = actor_addresses(state)?;
+    match partition_hash {
+        Some(partition_hash) => addresses
+            .set_partition_mining(partition_hash, should_mine)
+            .await
+            .map_err(|e| ApiError::ErrNoId {
+                id: partition_hash.to_string(),
+                err: e.to_string(),
+            })?,
+        None => addresses.set_mining(should_mine).map_err(internal)?,
+    }
+    info!(
+        "Mining {} for {:?} via the admin API",
+        if should_mine { "started" } else { "stopped" },
+        partition_hash
+    );
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Lists a storage module's packing queue, in packing order
+pub async fn packing_jobs(
+    state: web::Data<ApiState>,
+    path: web::Path<usize>,
+) -> Result<Json<Vec<PackingJob>>, ApiError> {
+    let sm = storage_module(&state, path.into_inner())?;
+    let internals = actor_addresses(&state)?
+        .packing
+        .send(GetInternals())
+        .await?;
+    Ok(Json(
+        internals
+            .jobs(sm.id)
+            .into_iter()
+            .enumerate()
+            .map(|(index, range)| PackingJob {
+                index,
+                in_progress: index == 0,
+                chunk_range: ChunkRange {
+                    start: range.start(),
+                    end: range.end(),
+                },
+            })
+            .collect(),
+    ))
+}
+
+pub async fn cancel_packing_job(
+    state: web::Data<ApiState>,
+    path: web::Path<(usize, usize)>,
+) -> Result<HttpResponse, ApiError> {
+    let (storage_module_id, index) = path.into_inner();
+    actor_addresses(&state)?
+        .packing
+        .send(CancelPackingJob {
+            storage_module_id,
+            index,
+        })
+        .await?
+        .map_err(bad_request)?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Moves a queued packing job right behind the job being packed
+pub async fn prioritize_packing_job(
+    state: web::Data<ApiState>,
+    path: web::Path<(usize, usize)>,
+) -> Result<HttpResponse, ApiError> {
+    let (storage_module_id, index) = path.into_inner();
+    actor_addresses(&state)?
+        .packing
+        .send(PrioritizePackingJob {
+            storage_module_id,
+            index,
+        })
+        .await?
+        .map_err(bad_request)?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Checks a storage module's packing, and repacks the chunks from each point
+/// the packing goes bad to the end of its entropy range
+pub async fn scrub_storage_module(
+    state: web::Data<ApiState>,
+    path: web::Path<usize>,
+) -> Result<Json<Vec<ChunkRange>>, ApiError> {
+    let addresses = actor_addresses(&state)?;
+    let sm = storage_module(&state, path.into_inner())?;
+    if sm.partition_hash().is_none() {
+        return Err(ApiError::BadRequest {
+            err: format!("Storage module {} isn't assigned a partition", sm.id),
+        });
+    }
+
+    let invalid = blocking({
+        let sm = sm.clone();
+        move || {
+            let entropy = sm.get_intervals(ChunkType::Entropy);
+            let mut invalid = Vec::new();
+            for start in find_invalid_packing_starts(sm.clone()) {
+                let Some(range) = entropy.iter().find(|range| range.contains(start)) else {
+                    continue;
+                };
+                let interval = ii(start, range.end());
+                sm.mark_uninitialized(interval)?;
+                invalid.push(interval);
+            }
+            Ok(invalid)
+        }
+    })
+    .await?;
+
+    info!(
+        "Scrub of storage module {} found {} invalid ranges",
+        sm.id,
+        invalid.len()
+    );
+    Ok(Json(request_packing(addresses, &sm, invalid)))
+}
+
+/// Requests packing for every uninitialized chunk of a storage module
+pub async fn resync_storage_module(
+    state: web::Data<ApiState>,
+    path: web::Path<usize>,
+) -> Result<Json<Vec<ChunkRange>>, ApiError> {
+    let addresses = actor_addresses(&state)?;
+    let sm = storage_module(&state, path.into_inner())?;
+    let uninitialized = sm.get_intervals(ChunkType::Uninitialized);
+    Ok(Json(request_packing(addresses, &sm, uninitialized)))
+}
+
+/// Writes a storage module's pending chunks to disk
+pub async fn flush_storage_module(
+    state: web::Data<ApiState>,
+    path: web::Path<usize>,
+) -> Result<HttpResponse, ApiError> {
+    let sm = storage_module(&state, path.into_inner())?;
+    blocking(move || sm.flush_pending_chunks()).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Starts a graceful shutdown of the node, the API stops once in-flight
+/// requests are answered
+pub async fn shutdown(state: web::Data<ApiState>) -> Result<HttpResponse, ApiError> {
+    info!("Shutdown requested via the admin API");
+    actor_addresses(&state)?.shutdown.request();
+    Ok(HttpResponse::Accepted().finish())
+}
+
+fn actor_addresses(state: &ApiState) -> Result<&ActorAddresses, ApiError> {
+    state.actor_addresses.as_ref().ok_or(ApiError::Internal {
+        err: String::from("The node's actors aren't available"),
+    })
+}
+
+fn storage_module(state: &ApiState, id: usize) -> Result<Arc<StorageModule>, ApiError> {
+    state
+        .chunk_provider
+        .storage_modules
+        .iter()
+        .find(|sm| sm.id == id)
+        .cloned()
+        .ok_or(ApiError::ErrNoId {
+            id: id.to_string(),
+            err: String::from("Storage module not found"),
+        })
+}
+
+fn request_packing(
+    addresses: &ActorAddresses,
+    sm: &Arc<StorageModule>,
+    intervals: Vec<Interval<u32>>,
+) -> Vec<ChunkRange> {
+    intervals
+        .into_iter()
+        .map(|interval| {
+            addresses.packing.do_send(PackingRequest {
+                storage_module: sm.clone(),
+                chunk_range: interval.into(),
+            });
+            interval.into()
+        })
+        .collect()
+}
+
+/// Runs blocking storage IO off the server's worker thread
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> eyre::Result<T> + Send + 'static,
+) -> Result<T, ApiError> {
+    web::block(f).await.map_err(internal)?.map_err(internal)
+}
+
+fn internal(err: impl std::fmt::Display) -> ApiError {
+    ApiError::Internal {
+        err: err.to_string(),
+    }
+}
+
+fn bad_request(err: impl std::fmt::Display) -> ApiError {
+    ApiError::BadRequest {
+        err: err.to_string(),
+    }
+}
@@ -18,8 +18,16 @@ pub async fn get_block(
     state: web::Data<ApiState>,
     path: web::Path<String>,
 ) -> Result<Json<CombinedBlockHeader>, ApiError> {
-    let tag_param = BlockParam::from_str(&path).map_err(|_| ApiError::ErrNoId {
-        id: path.to_string(),
+    get_block_by_tag(&state, &path).map(web::Json)
+}
+
+/// Returns the block for a block tag: `latest`, a block height or a base58 block hash
+pub fn get_block_by_tag(
+    state: &ApiState,
+    block_tag: &str,
+) -> Result<CombinedBlockHeader, ApiError> {
+    let tag_param = BlockParam::from_str(block_tag).map_err(|_| ApiError::ErrNoId {
+        id: block_tag.to_string(),
         err: String::from("Invalid block tag"),
     })?;
 
@@ -59,7 +67,7 @@ pub async fn get_block(
                     err: String::from("Block height out of range"),
                 })?)
                 .ok_or(ApiError::ErrNoId {
-                    id: path.to_string(),
+                    id: block_tag.to_string(),
                     err: String::from("Invalid block height"),
                 })?;
             r.block_hash
@@ -71,13 +79,10 @@ pub async fn get_block(
         }
         BlockParam::Hash(hash) => hash,
     };
-    get_block_by_hash(&state, block_hash)
+    get_block_by_hash(state, block_hash)
 }
 
-fn get_block_by_hash(
-    state: &web::Data<ApiState>,
-    block_hash: H256,
-) -> Result<Json<CombinedBlockHeader>, ApiError> {
+fn get_block_by_hash(state: &ApiState, block_hash: H256) -> Result<CombinedBlockHeader, ApiError> {
     let irys_header = match state
         .db
         .view_eyre(|tx| database::block_header_by_hash(tx, &block_hash))
@@ -130,7 +135,7 @@ fn get_block_by_hash(
         },
     };
 
-    Ok(web::Json(cbh))
+    Ok(cbh)
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -183,7 +188,7 @@ impl FromStr for BlockParam {
 
 #[cfg(test)]
 mod tests {
-    use crate::routes;
+    use crate::{error::ErrorResponse, routes};
 
     use super::*;
     use actix::{Actor, ArbiterService, SystemRegistry, SystemService as _};
@@ -233,6 +238,7 @@ mod tests {
             IrysSigner::random_signer(),
             storage_config.clone(),
             Arc::new(Vec::new()).to_vec(),
+            None,
         );
         SystemRegistry::set(mempool_service.start());
         let mempool_addr = MempoolService::from_registry();
@@ -245,6 +251,10 @@ mod tests {
         let app_state = ApiState {
             reth_provider: None,
             block_index: None,
+            partition_assignments: None,
+            ledgers: None,
+            vdf_steps: None,
+            actor_addresses: None,
             block_tree: None,
             db: DatabaseProvider(db_arc.clone()),
             mempool: mempool_addr,
@@ -289,6 +299,7 @@ mod tests {
             IrysSigner::random_signer(),
             storage_config.clone(),
             Arc::new(Vec::new()).to_vec(),
+            None,
         );
         SystemRegistry::set(mempool_service.start());
         let mempool_addr = MempoolService::from_registry();
@@ -301,6 +312,10 @@ mod tests {
         let app_state = ApiState {
             reth_provider: None,
             block_index: None,
+            partition_assignments: None,
+            ledgers: None,
+            vdf_steps: None,
+            actor_addresses: None,
             block_tree: None,
             db: DatabaseProvider(db_arc.clone()),
             mempool: mempool_addr,
@@ -321,12 +336,12 @@ mod tests {
 
         let resp = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
-        let result: ApiError = test::read_body_json(resp).await;
+        let result: ErrorResponse = test::read_body_json(resp).await;
         let blk_error = ApiError::ErrNoId {
             id: blk.block_hash.to_string(),
             err: String::from("block hash not found"),
         };
-        assert_eq!(blk_error, result);
+        assert_eq!(blk_error.to_response(), result);
         Ok(())
     }
 }
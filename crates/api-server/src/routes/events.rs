@@ -0,0 +1,93 @@
+use actix::SystemService as _;
+use actix_web::{
+    web::{self, Bytes},
+    HttpResponse,
+};
+use futures::stream;
+use irys_actors::event_service::{
+    EventFilter, EventReceiver, EventService, NodeEvent, SubscribeEvents,
+};
+use irys_types::H256;
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::account::parse_address;
+use crate::error::ApiError;
+
+/// Optional filters, all set filters must match for an event to be sent
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventsQuery {
+    /// Base58 or 0x-prefixed hex address of a miner or tx signer
+    pub address: Option<String>,
+    pub tx_id: Option<H256>,
+    pub data_root: Option<H256>,
+}
+
+/// Streams node events (new tips, finalized blocks, tx ingress & promotion and
+/// epoch transitions) as server-sent events
+pub async fn subscribe_events(query: web::Query<EventsQuery>) -> Result<HttpResponse, ApiError> {
+    let query = query.into_inner();
+    let address = match query.address.as_deref() {
+        Some(address) => Some(parse_address(address).ok_or(ApiError::BadRequest {
+            err: String::from("Invalid address"),
+        })?),
+        None => None,
+    };
+    let filter = EventFilter {
+        address,
+        tx_id: query.tx_id,
+        data_root: query.data_root,
+    };
+
+    let EventReceiver(receiver) = EventService::from_registry().send(SubscribeEvents).await?;
+
+    let events = stream::unfold((receiver, filter), |(mut receiver, filter)| async move {
+        loop {
+            let frame = match receiver.recv().await {
+                Ok(event) if event.matches(&filter) => sse_frame(&event),
+                Ok(_) => continue,
+                // Let the client know it fell behind rather than silently dropping events
+                Err(RecvError::Lagged(missed)) => format!(": missed {} events\n\n", missed),
+                Err(RecvError::Closed) => return None,
+            };
+            return Some((
+                Ok::<_, actix_web::Error>(Bytes::from(frame)),
+                (receiver, filter),
+            ));
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(events))
+}
+
+fn sse_frame(event: &NodeEvent) -> String {
+    format!(
+        "event: {}\ndata: {}\n\n",
+        event.name(),
+        serde_json::to_string(event).expect("node events serialize to JSON")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use irys_types::Address;
+
+    use super::*;
+
+    #[test]
+    fn formats_sse_frames() {
+        let event = NodeEvent::NewTip {
+            block_hash: H256::zero(),
+            height: 5,
+            miner_address: Address::ZERO,
+        };
+        let frame = sse_frame(&event);
+        assert!(frame.starts_with("event: newTip\ndata: {\"type\":\"newTip\""));
+        assert!(frame.contains("\"height\":\"5\""));
+        assert!(frame.ends_with("}\n\n"));
+    }
+}
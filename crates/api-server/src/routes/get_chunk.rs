@@ -1,4 +1,4 @@
-use crate::ApiState;
+use crate::{error::ApiError, ApiState};
 use actix_web::{
     web::{self},
     HttpResponse,
@@ -17,21 +17,16 @@ pub struct LedgerChunkApiPath {
 pub async fn get_chunk_by_ledger_offset(
     state: web::Data<ApiState>,
     path: web::Path<LedgerChunkApiPath>,
-) -> actix_web::Result<HttpResponse> {
-    let ledger = match Ledger::try_from(path.ledger_id) {
-        Ok(l) => l,
-        Err(e) => return Ok(HttpResponse::BadRequest().body(format!("Invalid ledger id: {}", e))),
-    };
+) -> Result<HttpResponse, ApiError> {
+    let ledger = parse_ledger(path.ledger_id)?;
 
     match state
         .chunk_provider
         .get_chunk_by_ledger_offset(ledger, path.ledger_offset)
     {
         Ok(Some(chunk)) => Ok(HttpResponse::Ok().json(ChunkFormat::Packed(chunk))),
-        Ok(None) => Ok(HttpResponse::NotFound().body("Chunk not found")),
-        Err(e) => {
-            Ok(HttpResponse::InternalServerError().body(format!("Error retrieving chunk: {}", e)))
-        }
+        Ok(None) => Err(chunk_not_found(path.ledger_offset)),
+        Err(e) => Err(chunk_error(e)),
     }
 }
 
@@ -45,20 +40,34 @@ pub struct DataRootChunkApiPath {
 pub async fn get_chunk_by_data_root_offset(
     state: web::Data<ApiState>,
     path: web::Path<DataRootChunkApiPath>,
-) -> actix_web::Result<HttpResponse> {
-    let ledger = match Ledger::try_from(path.ledger_id) {
-        Ok(l) => l,
-        Err(e) => return Ok(HttpResponse::BadRequest().body(format!("Invalid ledger id: {}", e))),
-    };
+) -> Result<HttpResponse, ApiError> {
+    let ledger = parse_ledger(path.ledger_id)?;
 
     match state
         .chunk_provider
         .get_chunk_by_data_root(ledger, path.data_root, path.offset)
     {
         Ok(Some(chunk)) => Ok(HttpResponse::Ok().json(chunk)),
-        Ok(None) => Ok(HttpResponse::NotFound().body("Chunk not found")),
-        Err(e) => {
-            Ok(HttpResponse::InternalServerError().body(format!("Error retrieving chunk: {}", e)))
-        }
+        Ok(None) => Err(chunk_not_found(path.offset)),
+        Err(e) => Err(chunk_error(e)),
+    }
+}
+
+fn parse_ledger(ledger_id: u32) -> Result<Ledger, ApiError> {
+    Ledger::try_from(ledger_id).map_err(|e| ApiError::BadRequest {
+        err: format!("Invalid ledger id: {}", e),
+    })
+}
+
+fn chunk_not_found(offset: impl ToString) -> ApiError {
+    ApiError::ErrNoId {
+        id: offset.to_string(),
+        err: String::from("Chunk not found"),
+    }
+}
+
+fn chunk_error(e: impl std::fmt::Display) -> ApiError {
+    ApiError::Internal {
+        err: format!("Error retrieving chunk: {}", e),
     }
 }
@@ -1,5 +1,106 @@
-use actix_web::HttpResponse;
+use actix_web::{
+    web::{self, Json},
+    Result,
+};
+use irys_actors::mempool_service::GetMempoolSizeMessage;
+use irys_storage::ChunkType;
+use irys_types::{u64_stringify, CONFIG, H256, U256};
+use reth::network::PeersInfo as _;
+use serde::{Deserialize, Serialize};
 
-pub async fn info_route() -> HttpResponse {
-    HttpResponse::Ok().body("Hirys world!")
+use crate::ApiState;
+
+/// Status of the node, cheap enough to be polled every second
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    pub version: String,
+    #[serde(with = "u64_stringify")]
+    pub chain_id: u64,
+    /// Tip of the block tree, the block with the highest cumulative difficulty
+    pub tip_hash: H256,
+    #[serde(with = "u64_stringify")]
+    pub tip_height: u64,
+    pub cumulative_difficulty: U256,
+    /// Height of the latest block in the block index
+    #[serde(with = "u64_stringify")]
+    pub finalized_height: u64,
+    #[serde(with = "u64_stringify")]
+    pub vdf_global_step: u64,
+    /// Number of pending txs in the mempool
+    #[serde(with = "u64_stringify")]
+    pub mempool_size: u64,
+    pub mining: bool,
+    #[serde(with = "u64_stringify")]
+    pub peer_count: u64,
+    pub storage_modules: Vec<StorageModuleStatus>,
+}
+
+/// Packing and sync progress of a storage module
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageModuleStatus {
+    pub id: usize,
+    pub partition_hash: Option<H256>,
+    #[serde(with = "u64_stringify")]
+    pub num_chunks: u64,
+    /// Chunks packed with entropy, including those holding data
+    #[serde(with = "u64_stringify")]
+    pub packed_chunks: u64,
+    /// Chunks holding (synced) data
+    #[serde(with = "u64_stringify")]
+    pub data_chunks: u64,
+}
+
+pub async fn info_route(state: web::Data<ApiState>) -> Result<Json<NodeInfo>> {
+    let mut info = NodeInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        chain_id: CONFIG.irys_chain_id,
+        ..Default::default()
+    };
+
+    if let Some(block_tree) = &state.block_tree {
+        let cache = block_tree.read();
+        info.tip_hash = cache.tip;
+        if let Some(tip) = cache.get_block(&cache.tip) {
+            info.tip_height = tip.height;
+            info.cumulative_difficulty = tip.cumulative_diff;
+        }
+    }
+    if let Some(block_index) = &state.block_index {
+        info.finalized_height = block_index.read().num_blocks().saturating_sub(1);
+    }
+    if let Some(vdf_steps) = &state.vdf_steps {
+        info.vdf_global_step = vdf_steps.read().global_step;
+    }
+    if let Some(actor_addresses) = &state.actor_addresses {
+        info.mining = actor_addresses.is_mining();
+    }
+    if let Some(reth_provider) = &state.reth_provider {
+        info.peer_count = reth_provider.network.num_connected_peers() as u64;
+    }
+    // a delivery failure just means the mempool is busy/stopping, report what we have
+    info.mempool_size = state
+        .mempool
+        .send(GetMempoolSizeMessage)
+        .await
+        .unwrap_or_default() as u64;
+
+    info.storage_modules = state
+        .chunk_provider
+        .storage_modules
+        .iter()
+        .map(|sm| {
+            let data_chunks = sm.count_chunks(ChunkType::Data);
+            StorageModuleStatus {
+                id: sm.id,
+                partition_hash: sm.partition_hash(),
+                num_chunks: sm.storage_config.num_chunks_in_partition,
+                packed_chunks: sm.count_chunks(ChunkType::Entropy) + data_chunks,
+                data_chunks,
+            }
+        })
+        .collect();
+
+    Ok(Json(info))
 }
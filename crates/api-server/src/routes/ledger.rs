@@ -0,0 +1,155 @@
+use actix_web::{
+    web::{self, Json},
+    Result,
+};
+use irys_actors::epoch_service::{
+    AssignmentChange, LedgersReadGuard, PartitionAssignmentsReadGuard,
+};
+use irys_database::{data_ledger::LedgerSlot, Ledger};
+use irys_types::{option_u64_stringify, u64_stringify, Address, H256};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ApiError, ApiState};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerSummary {
+    pub ledger_id: u32,
+    pub ledger: Ledger,
+    pub num_slots: usize,
+    pub num_expired_slots: usize,
+    pub num_partitions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotDetails {
+    pub slot_index: usize,
+    pub partitions: Vec<SlotPartition>,
+    pub is_expired: bool,
+    /// Block height of the most recently added data
+    #[serde(with = "u64_stringify")]
+    pub last_height: u64,
+    /// Epoch height at which the slot expires if no more data is added to it,
+    /// only set for term ledgers
+    #[serde(default, with = "option_u64_stringify")]
+    pub expiry_height: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotPartition {
+    pub partition_hash: H256,
+    /// The miner holding the partition
+    pub miner_address: Option<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionDetails {
+    pub partition_hash: H256,
+    pub miner_address: Address,
+    /// `None` for capacity partitions
+    pub ledger_id: Option<u32>,
+    pub slot_index: Option<usize>,
+    /// Assignment changes made by each epoch, oldest first
+    pub history: Vec<AssignmentChange>,
+}
+
+pub async fn get_ledgers(state: web::Data<ApiState>) -> Result<Json<Vec<LedgerSummary>>, ApiError> {
+    let ledgers_guard = ledgers_guard(&state)?;
+    let ledgers = ledgers_guard.read();
+    Ok(Json(
+        Ledger::iter()
+            .map(|ledger| {
+                let slots = ledgers.get_slots(ledger);
+                LedgerSummary {
+                    ledger_id: ledger.get_id(),
+                    ledger,
+                    num_slots: slots.len(),
+                    num_expired_slots: slots.iter().filter(|slot| slot.is_expired).count(),
+                    num_partitions: slots.iter().map(|slot| slot.partitions.len()).sum(),
+                }
+            })
+            .collect(),
+    ))
+}
+
+pub async fn get_ledger_slots(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+) -> Result<Json<Vec<SlotDetails>>, ApiError> {
+    let ledger = Ledger::from_url(&path).map_err(|e| ApiError::ErrNoId {
+        id: path.to_string(),
+        err: e.to_string(),
+    })?;
+    let ledgers_guard = ledgers_guard(&state)?;
+    let assignments_guard = partition_assignments_guard(&state)?;
+    let ledgers = ledgers_guard.read();
+    let assignments = assignments_guard.read();
+
+    let slot_details = |(slot_index, slot): (usize, &LedgerSlot)| SlotDetails {
+        slot_index,
+        partitions: slot
+            .partitions
+            .iter()
+            .map(|partition_hash| SlotPartition {
+                partition_hash: *partition_hash,
+                miner_address: assignments
+                    .get_assignment(*partition_hash)
+                    .map(|assignment| assignment.miner_address),
+            })
+            .collect(),
+        is_expired: slot.is_expired,
+        last_height: slot.last_height,
+        expiry_height: ledgers.get_slot_expiry_height(ledger, slot_index),
+    };
+    Ok(Json(
+        ledgers
+            .get_slots(ledger)
+            .iter()
+            .enumerate()
+            .map(slot_details)
+            .collect(),
+    ))
+}
+
+pub async fn get_partition(
+    state: web::Data<ApiState>,
+    path: web::Path<H256>,
+) -> Result<Json<PartitionDetails>, ApiError> {
+    let partition_hash = path.into_inner();
+    let assignments_guard = partition_assignments_guard(&state)?;
+    let assignments = assignments_guard.read();
+    let assignment =
+        assignments
+            .get_assignment(partition_hash)
+            .ok_or_else(|| ApiError::ErrNoId {
+                id: partition_hash.to_string(),
+                err: String::from("Partition not found"),
+            })?;
+    Ok(Json(PartitionDetails {
+        partition_hash,
+        miner_address: assignment.miner_address,
+        ledger_id: assignment.ledger_id,
+        slot_index: assignment.slot_index,
+        history: assignments.get_history(partition_hash).to_vec(),
+    }))
+}
+
+fn ledgers_guard(state: &ApiState) -> Result<LedgersReadGuard, ApiError> {
+    state.ledgers.clone().ok_or(ApiError::Internal {
+        err: String::from("ledgers error"),
+    })
+}
+
+fn partition_assignments_guard(
+    state: &ApiState,
+) -> Result<PartitionAssignmentsReadGuard, ApiError> {
+    state
+        .partition_assignments
+        .clone()
+        .ok_or(ApiError::Internal {
+            err: String::from("partition assignments error"),
+        })
+}
@@ -0,0 +1,12 @@
+use actix_web::HttpResponse;
+use reth_node_metrics::recorder::install_prometheus_recorder;
+
+/// Renders the Irys and Reth metrics in the Prometheus text exposition format
+pub async fn metrics() -> HttpResponse {
+    let handle = install_prometheus_recorder().handle();
+    // drains the histograms' buckets, normally done by reth's metrics server
+    handle.run_upkeep();
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(handle.render())
+}
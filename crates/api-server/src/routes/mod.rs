@@ -1,8 +1,14 @@
+pub mod account;
+pub mod admin;
 pub mod block;
+pub mod events;
 pub mod get_chunk;
 pub mod index;
+pub mod ledger;
+pub mod metrics;
 pub mod network_config;
 pub mod post_chunk;
 pub mod price;
 pub mod proxy;
+pub mod storage;
 pub mod tx;
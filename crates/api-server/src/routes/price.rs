@@ -3,10 +3,19 @@ use irys_config::{PRICE_PER_CHUNK_5_EPOCH, PRICE_PER_CHUNK_PERM};
 use irys_database::Ledger;
 use irys_types::CONFIG;
 
-pub async fn get_price(path: Path<(String, u64)>) -> actix_web::Result<HttpResponse> {
+use crate::error::ApiError;
+
+pub async fn get_price(path: Path<(String, u64)>) -> Result<HttpResponse, ApiError> {
     let size = path.1;
-    let ledger = Ledger::from_url(&path.0);
+    let ledger = Ledger::from_url(&path.0).map_err(|_| ApiError::BadRequest {
+        err: String::from("Ledger type not support"),
+    })?;
+
+    Ok(HttpResponse::Ok().body(price_for(ledger, size).to_string()))
+}
 
+/// Price to store `size` bytes of data in `ledger`
+pub fn price_for(ledger: Ledger, size: u64) -> u128 {
     let num_of_chunks = if size < CONFIG.chunk_size {
         1u128
     } else {
@@ -14,14 +23,9 @@ pub async fn get_price(path: Path<(String, u64)>) -> actix_web::Result<HttpRespo
         (size % CONFIG.chunk_size + 1) as u128
     };
 
-    if let Ok(l) = ledger {
-        let final_price = match l {
-            Ledger::Publish => PRICE_PER_CHUNK_PERM,
-            Ledger::Submit => PRICE_PER_CHUNK_5_EPOCH,
-        } * num_of_chunks;
-
-        Ok(HttpResponse::Ok().body(final_price.to_string()))
-    } else {
-        Ok(HttpResponse::BadRequest().body("Ledger type not support"))
+    match ledger {
+        Ledger::Publish => PRICE_PER_CHUNK_PERM,
+        Ledger::Submit => PRICE_PER_CHUNK_5_EPOCH,
     }
+    *num_of_chunks
 }
@@ -1,8 +1,58 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Instant,
+};
+
 use actix_web::{
-    web::{Data, Payload},
+    http::{header, Method},
+    web::{Bytes, Data, Payload},
     HttpRequest, HttpResponse,
 };
 use awc::Client;
+use irys_reth_node_bridge::rpc::DEV_RPC_METHODS;
+use irys_types::CONFIG;
+use reth_metrics::metrics::{counter, histogram};
+use serde_json::{json, Value};
+
+use crate::{
+    error::{ErrorCode, ErrorResponse},
+    ApiState,
+};
+
+/// Largest request body (single or batch JSON-RPC request) the proxy accepts
+pub const MAX_PROXY_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+/// Methods the proxy metrics report under their own name, on top of the methods
+/// allowed by name (without a wildcard). Any other method is reported as `other`,
+/// so callers can't create arbitrary label values.
+const LABELLED_RPC_METHODS: [&str; 26] = [
+    "eth_blockNumber",
+    "eth_call",
+    "eth_chainId",
+    "eth_estimateGas",
+    "eth_feeHistory",
+    "eth_gasPrice",
+    "eth_getBalance",
+    "eth_getBlockByHash",
+    "eth_getBlockByNumber",
+    "eth_getBlockReceipts",
+    "eth_getCode",
+    "eth_getLogs",
+    "eth_getStorageAt",
+    "eth_getTransactionByHash",
+    "eth_getTransactionCount",
+    "eth_getTransactionReceipt",
+    "eth_maxPriorityFeePerGas",
+    "eth_sendRawTransaction",
+    "eth_syncing",
+    "net_version",
+    "web3_clientVersion",
+    "irys_getAccount",
+    "irys_getAccounts",
+    "irys_getCommitments",
+    "irys_getPeerId",
+    "irys_ping",
+];
 
 #[derive(Debug)]
 pub enum ProxyError {
@@ -10,6 +60,8 @@ pub enum ProxyError {
     #[allow(unused)]
     ParseError(String),
     MethodNotAllowed,
+    /// The Reth RPC server isn't running (yet)
+    Unavailable,
 }
 
 impl std::fmt::Display for ProxyError {
@@ -18,58 +70,213 @@ impl std::fmt::Display for ProxyError {
             ProxyError::RequestError(e) => write!(f, "Request error: {}", e),
             ProxyError::ParseError(e) => write!(f, "Parse error: {}", e),
             ProxyError::MethodNotAllowed => write!(f, "Method not allowed"),
+            ProxyError::Unavailable => write!(f, "Execution RPC unavailable"),
         }
     }
 }
 
 impl actix_web::ResponseError for ProxyError {
     fn error_response(&self) -> HttpResponse {
-        match self {
-            ProxyError::RequestError(_) => HttpResponse::BadGateway().finish(),
-            ProxyError::ParseError(_) => HttpResponse::BadRequest().finish(),
-            ProxyError::MethodNotAllowed => HttpResponse::MethodNotAllowed().finish(),
+        let code = match self {
+            ProxyError::RequestError(_) => ErrorCode::BadGateway,
+            ProxyError::ParseError(_) => ErrorCode::InvalidPayload,
+            ProxyError::MethodNotAllowed => ErrorCode::MethodNotAllowed,
+            ProxyError::Unavailable => ErrorCode::Unavailable,
+        };
+        ErrorResponse::new(code, self.to_string()).into_http_response()
+    }
+}
+
+/// Decides which JSON-RPC methods the execution RPC proxy forwards to Reth
+#[derive(Debug, Clone)]
+pub struct RpcMethodFilter {
+    allowed: Vec<String>,
+    denied: Vec<String>,
+}
+
+impl RpcMethodFilter {
+    /// Builds a filter from comma separated method patterns, a trailing `*` matches
+    /// a prefix. The dev methods are denied unless `dev_mode` is set.
+    pub fn new(allowed: &str, denied: &str, dev_mode: bool) -> Self {
+        let patterns = |list: &str| -> Vec<String> {
+            list.split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        let mut denied = patterns(denied);
+        if !dev_mode {
+            denied.extend(DEV_RPC_METHODS.iter().map(|method| method.to_string()));
+        }
+        Self {
+            allowed: patterns(allowed),
+            denied,
+        }
+    }
+
+    pub fn from_config() -> Self {
+        Self::new(
+            CONFIG.rpc_proxy_allowed_methods,
+            CONFIG.rpc_proxy_denied_methods,
+            CONFIG.dev_mode,
+        )
+    }
+
+    pub fn is_allowed(&self, method: &str) -> bool {
+        let matches = |pattern: &String| match pattern.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => method == pattern,
+        };
+        self.allowed.iter().any(matches) && !self.denied.iter().any(matches)
+    }
+
+    /// The method of a request as a metric label: one of [`LABELLED_RPC_METHODS`] or
+    /// a method allowed by name, `other` for anything else
+    pub fn method_label(&self, request: &Value) -> String {
+        let method = request_method(request);
+        if LABELLED_RPC_METHODS.contains(&method) || self.allowed.iter().any(|m| m == method) {
+            method.to_string()
+        } else {
+            "other".to_string()
         }
     }
 }
 
+impl Default for RpcMethodFilter {
+    fn default() -> Self {
+        Self::from_config()
+    }
+}
+
+/// Forwards JSON-RPC requests (single or batched) to the Reth RPC server, rejecting
+/// the methods denied by the [`RpcMethodFilter`]. Requests without a body (e.g.
+/// `GET` health checks) carry no method to filter, and are forwarded as they are.
 pub async fn proxy(
     req: HttpRequest,
     payload: Payload,
     client: Data<Client>,
+    state: Data<ApiState>,
+    filter: Data<RpcMethodFilter>,
 ) -> Result<HttpResponse, ProxyError> {
-    // TODO: make this configurable!
-    let target_uri = "http://localhost:8545";
-
-    // Create a new client request
-    let mut client_req = match *req.method() {
-        actix_web::http::Method::GET => client.get(target_uri),
-        actix_web::http::Method::POST => client.post(target_uri),
-        actix_web::http::Method::PUT => client.put(target_uri),
-        actix_web::http::Method::DELETE => client.delete(target_uri),
-        actix_web::http::Method::HEAD => client.head(target_uri),
-        actix_web::http::Method::OPTIONS => client.options(target_uri),
-        actix_web::http::Method::PATCH => client.patch(target_uri),
+    let target_uri = execution_rpc_uri(&state).ok_or(ProxyError::Unavailable)?;
+    match *req.method() {
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::DELETE => {
+            return forward(&req, &client, &target_uri, None).await
+        }
+        Method::POST | Method::PUT | Method::PATCH => {}
         _ => return Err(ProxyError::MethodNotAllowed),
-    };
+    }
+
+    let body = payload
+        .to_bytes_limited(MAX_PROXY_BODY_SIZE)
+        .await
+        .map_err(|_| ProxyError::ParseError("Request body too large".to_string()))?
+        .map_err(|e| ProxyError::ParseError(e.to_string()))?;
+    let request: Value =
+        serde_json::from_slice(&body).map_err(|e| ProxyError::ParseError(e.to_string()))?;
+
+    match request {
+        Value::Array(batch) => proxy_batch(&client, &target_uri, batch, &filter).await,
+        request => {
+            let method = filter.method_label(&request);
+            if !filter.is_allowed(request_method(&request)) {
+                counter!("irys_execution_rpc_requests", "method" => method, "result" => "denied")
+                    .increment(1);
+                return Ok(HttpResponse::Ok().json(method_not_allowed(&request)));
+            }
+            counter!(
+                "irys_execution_rpc_requests",
+                "method" => method.clone(),
+                "result" => "forwarded"
+            )
+            .increment(1);
+            let started = Instant::now();
+            let response = forward(&req, &client, &target_uri, Some(body)).await;
+            histogram!("irys_execution_rpc_request_duration_seconds", "method" => method)
+                .record(started.elapsed().as_secs_f64());
+            response
+        }
+    }
+}
+
+/// Forwards the allowed requests of a batch as a single batch, answering the
+/// denied ones with an error in place
+async fn proxy_batch(
+    client: &Client,
+    target_uri: &str,
+    batch: Vec<Value>,
+    filter: &RpcMethodFilter,
+) -> Result<HttpResponse, ProxyError> {
+    let (allowed, denied): (Vec<Value>, Vec<Value>) = batch
+        .into_iter()
+        .partition(|request| filter.is_allowed(request_method(request)));
+
+    let mut responses: Vec<Value> = denied.iter().map(method_not_allowed).collect();
+    for request in &denied {
+        counter!(
+            "irys_execution_rpc_requests",
+            "method" => filter.method_label(request),
+            "result" => "denied"
+        )
+        .increment(1);
+    }
+    if allowed.is_empty() {
+        return Ok(HttpResponse::Ok().json(responses));
+    }
+    for request in &allowed {
+        counter!(
+            "irys_execution_rpc_requests",
+            "method" => filter.method_label(request),
+            "result" => "forwarded"
+        )
+        .increment(1);
+    }
+
+    let started = Instant::now();
+    let mut response = client
+        .post(target_uri)
+        .send_json(&allowed)
+        .await
+        .map_err(ProxyError::RequestError)?;
+    let forwarded: Value = response
+        .json()
+        .limit(MAX_PROXY_BODY_SIZE)
+        .await
+        .map_err(|e| ProxyError::ParseError(e.to_string()))?;
+    histogram!("irys_execution_rpc_request_duration_seconds", "method" => "batch")
+        .record(started.elapsed().as_secs_f64());
+
+    match forwarded {
+        Value::Array(forwarded) => responses.extend(forwarded),
+        // i.e. the whole batch was rejected
+        error => responses.push(error),
+    }
+    Ok(HttpResponse::Ok().json(responses))
+}
+
+/// Forwards a single request with the same HTTP method, streaming back Reth's response
+async fn forward(
+    req: &HttpRequest,
+    client: &Client,
+    target_uri: &str,
+    body: Option<Bytes>,
+) -> Result<HttpResponse, ProxyError> {
+    let mut client_req = client.request(req.method().clone(), target_uri);
 
     // Forward relevant headers
     for (header_name, header_value) in req.headers() {
-        // Skip hop-by-hop headers
-        if !is_hop_by_hop_header(header_name.as_str()) {
+        // Skip hop-by-hop headers, the body was buffered so its length may have changed
+        if !is_hop_by_hop_header(header_name.as_str()) && header_name != header::CONTENT_LENGTH {
             client_req = client_req.insert_header((header_name.clone(), header_value.clone()));
         }
     }
 
-    // Send the request with the payload for methods that support it
-    let response = match req.method() {
-        &actix_web::http::Method::POST
-        | &actix_web::http::Method::PUT
-        | &actix_web::http::Method::PATCH => client_req
-            .send_stream(payload)
-            .await
-            .map_err(ProxyError::RequestError)?,
-        _ => client_req.send().await.map_err(ProxyError::RequestError)?,
-    };
+    let response = match body {
+        Some(body) => client_req.send_body(body).await,
+        None => client_req.send().await,
+    }
+    .map_err(ProxyError::RequestError)?;
 
     // Build response
     let mut client_response = HttpResponse::build(response.status());
@@ -85,6 +292,41 @@ pub async fn proxy(
     Ok(client_response.streaming(response))
 }
 
+/// The URI of the Reth HTTP RPC server, as actually bound
+fn execution_rpc_uri(state: &ApiState) -> Option<String> {
+    let addr = state
+        .reth_provider
+        .as_ref()?
+        .rpc_server_handles
+        .rpc
+        .http_local_addr()?;
+    // the server listens on all interfaces, connect over loopback
+    let addr = if addr.ip().is_unspecified() {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), addr.port())
+    } else {
+        addr
+    };
+    Some(format!("http://{}", addr))
+}
+
+fn request_method(request: &Value) -> &str {
+    request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+fn method_not_allowed(request: &Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": request.get("id").cloned().unwrap_or(Value::Null),
+        "error": {
+            "code": -32601,
+            "message": format!("Method {} is not allowed", request_method(request)),
+        },
+    })
+}
+
 fn is_hop_by_hop_header(header: &str) -> bool {
     let hop_by_hop_headers = [
         "connection",
@@ -99,3 +341,42 @@ fn is_hop_by_hop_header(header: &str) -> bool {
 
     hop_by_hop_headers.contains(&header.to_lowercase().as_str())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_methods() {
+        let filter = RpcMethodFilter::new("eth_*, net_version", "eth_sign", false);
+        assert!(filter.is_allowed("eth_call"));
+        assert!(filter.is_allowed("net_version"));
+        assert!(!filter.is_allowed("net_peerCount"));
+        assert!(!filter.is_allowed("eth_sign"));
+        assert!(!filter.is_allowed(""));
+
+        let filter = RpcMethodFilter::new("*", "admin_*,debug_*", false);
+        assert!(filter.is_allowed("irys_getAccount"));
+        assert!(!filter.is_allowed("admin_addPeer"));
+        assert!(!filter.is_allowed("debug_traceTransaction"));
+        assert!(!filter.is_allowed("irys_createEthTx"));
+        assert!(!filter.is_allowed("irys_addPeer"));
+
+        let filter = RpcMethodFilter::new("*", CONFIG.rpc_proxy_denied_methods, true);
+        assert!(filter.is_allowed("irys_createEthTx"));
+        assert!(!filter.is_allowed("irys_addPeer"));
+        assert!(!filter.is_allowed("irys_removePeer"));
+    }
+
+    #[test]
+    fn labels_methods() {
+        let filter = RpcMethodFilter::new("*, custom_method", "", false);
+        let label = |method: &str| filter.method_label(&json!({ "method": method }));
+        assert_eq!(label("eth_call"), "eth_call");
+        assert_eq!(label("custom_method"), "custom_method");
+        // allowed by the wildcard, but not known
+        assert_eq!(label("eth_madeUpMethod1"), "other");
+        assert_eq!(label("eth call\n"), "other");
+        assert_eq!(filter.method_label(&json!({"id": 1})), "other");
+    }
+}
@@ -0,0 +1,240 @@
+use std::path::{Path, PathBuf};
+
+use actix_web::{
+    web::{self, Json},
+    Result,
+};
+use irys_actors::packing::{GetInternals, Internals};
+use irys_storage::{ChunkType, InclusiveInterval as _, StorageModule};
+use irys_types::{u64_stringify, H256};
+use serde::{Deserialize, Serialize};
+use sysinfo::Disks;
+
+use crate::{error::ApiError, ApiState};
+
+/// Status of a storage module: its assignment, the state of its chunks and disks,
+/// and its pending packing work
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageModuleDetails {
+    pub id: usize,
+    pub partition_hash: Option<H256>,
+    pub ledger_id: Option<u32>,
+    pub slot_index: Option<usize>,
+    /// Storage state of each chunk range, in ascending order
+    pub intervals: Vec<ChunkInterval>,
+    pub chunk_counts: ChunkCounts,
+    /// Chunks written to the module but not yet synced to disk
+    pub pending_writes: usize,
+    pub submodules: Vec<SubmoduleDetails>,
+    pub packing_queue: PackingQueue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkInterval {
+    pub start: u32,
+    /// Inclusive
+    pub end: u32,
+    pub chunk_type: ChunkType,
+    #[serde(with = "u64_stringify")]
+    pub num_chunks: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkCounts {
+    #[serde(with = "u64_stringify")]
+    pub entropy: u64,
+    #[serde(with = "u64_stringify")]
+    pub data: u64,
+    #[serde(with = "u64_stringify")]
+    pub uninitialized: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmoduleDetails {
+    pub start: u32,
+    /// Inclusive
+    pub end: u32,
+    pub path: PathBuf,
+    /// Free space in bytes on the disk holding the submodule, if it can be determined
+    pub available_space: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackingQueue {
+    pub jobs: usize,
+    #[serde(with = "u64_stringify")]
+    pub chunks: u64,
+}
+
+pub async fn get_storage_modules(
+    state: web::Data<ApiState>,
+) -> Result<Json<Vec<StorageModuleDetails>>, ApiError> {
+    let internals = packing_internals(&state).await?;
+    let disks = Disks::new_with_refreshed_list();
+    Ok(Json(
+        state
+            .chunk_provider
+            .storage_modules
+            .iter()
+            .map(|sm| storage_module_details(sm, internals.as_ref(), &disks))
+            .collect(),
+    ))
+}
+
+pub async fn get_storage_module(
+    state: web::Data<ApiState>,
+    path: web::Path<usize>,
+) -> Result<Json<StorageModuleDetails>, ApiError> {
+    let id = path.into_inner();
+    let sm = state
+        .chunk_provider
+        .storage_modules
+        .iter()
+        .find(|sm| sm.id == id)
+        .ok_or(ApiError::ErrNoId {
+            id: id.to_string(),
+            err: String::from("Storage module not found"),
+        })?;
+    let internals = packing_internals(&state).await?;
+    let disks = Disks::new_with_refreshed_list();
+    Ok(Json(storage_module_details(sm, internals.as_ref(), &disks)))
+}
+
+/// The packing actor's queues, `None` if the node runs without one
+async fn packing_internals(state: &ApiState) -> Result<Option<Internals>, ApiError> {
+    let Some(actor_addresses) = &state.actor_addresses else {
+        return Ok(None);
+    };
+    actor_addresses
+        .packing
+        .send(GetInternals())
+        .await
+        .map(Some)
+        .map_err(|e| ApiError::Internal {
+            err: format!("Failed to query the packing actor: {}", e),
+        })
+}
+
+fn storage_module_details(
+    sm: &StorageModule,
+    internals: Option<&Internals>,
+    disks: &Disks,
+) -> StorageModuleDetails {
+    let intervals: Vec<ChunkInterval> = sm
+        .intervals()
+        .into_iter()
+        .map(|(interval, chunk_type)| ChunkInterval {
+            start: interval.start(),
+            end: interval.end(),
+            chunk_type,
+            num_chunks: (interval.end() - interval.start()) as u64 + 1,
+        })
+        .collect();
+
+    let mut chunk_counts = ChunkCounts::default();
+    for interval in &intervals {
+        match interval.chunk_type {
+            ChunkType::Entropy => chunk_counts.entropy += interval.num_chunks,
+            ChunkType::Data => chunk_counts.data += interval.num_chunks,
+            ChunkType::Uninitialized => chunk_counts.uninitialized += interval.num_chunks,
+        }
+    }
+
+    let (jobs, chunks) = internals.map_or((0, 0), |internals| internals.queue_depth(sm.id));
+
+    StorageModuleDetails {
+        id: sm.id,
+        partition_hash: sm.partition_hash(),
+        ledger_id: sm.partition_assignment.and_then(|pa| pa.ledger_id),
+        slot_index: sm.partition_assignment.and_then(|pa| pa.slot_index),
+        intervals,
+        chunk_counts,
+        pending_writes: sm.pending_writes_count(),
+        submodules: sm
+            .submodule_paths()
+            .into_iter()
+            .map(|(interval, path)| SubmoduleDetails {
+                start: interval.start(),
+                end: interval.end(),
+                available_space: available_space(disks, &path),
+                path,
+            })
+            .collect(),
+        packing_queue: PackingQueue { jobs, chunks },
+    }
+}
+
+/// Free space on the disk `path` is on, i.e. the disk with the longest mount point
+/// containing it
+fn available_space(disks: &Disks, path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+#[cfg(test)]
+mod tests {
+    use irys_storage::{ii, initialize_storage_files, StorageModuleInfo};
+    use irys_types::{partition::PartitionAssignment, Address, StorageConfig};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn reports_storage_module_details() -> eyre::Result<()> {
+        let assignment = PartitionAssignment {
+            partition_hash: H256::random(),
+            miner_address: Address::random(),
+            ledger_id: Some(1),
+            slot_index: Some(0),
+        };
+        let infos = vec![StorageModuleInfo {
+            id: 0,
+            partition_assignment: Some(assignment),
+            submodules: vec![(ii(0, 4), "hdd0".into()), (ii(5, 9), "hdd1".into())],
+        }];
+        let tmp_dir = tempdir()?;
+        let base_path = tmp_dir.path().to_path_buf();
+        initialize_storage_files(&base_path, &infos, &vec![])?;
+        let storage_config = StorageConfig {
+            min_writes_before_sync: 1,
+            num_chunks_in_partition: 10,
+            chunk_size: 32,
+            ..Default::default()
+        };
+        let sm = StorageModule::new(&base_path, &infos[0], storage_config)?;
+        for offset in 0..3 {
+            sm.write_chunk(offset, vec![0; 32], ChunkType::Entropy);
+        }
+        sm.sync_pending_chunks()?;
+        sm.write_chunk(3, vec![0; 32], ChunkType::Entropy);
+
+        let details = storage_module_details(&sm, None, &Disks::new_with_refreshed_list());
+        assert_eq!(details.partition_hash, Some(assignment.partition_hash));
+        assert_eq!((details.ledger_id, details.slot_index), (Some(1), Some(0)));
+        assert_eq!(
+            details.chunk_counts,
+            ChunkCounts {
+                entropy: 3,
+                data: 0,
+                uninitialized: 7,
+            }
+        );
+        assert_eq!(details.intervals.len(), 2);
+        assert_eq!(details.pending_writes, 1);
+        assert_eq!(details.submodules.len(), 2);
+        assert_eq!(details.submodules[1].start, 5);
+        assert!(details.submodules[1].path.ends_with("hdd1"));
+        assert_eq!(details.packing_queue, PackingQueue::default());
+        Ok(())
+    }
+}
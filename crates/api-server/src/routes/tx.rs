@@ -1,15 +1,18 @@
 use crate::error::ApiError;
+use crate::rate_limit::IngressLimits;
 use crate::ApiState;
 use actix_web::{
     web::{self, Json},
-    HttpResponse, Result,
+    HttpRequest, HttpResponse, Result,
 };
-use awc::http::StatusCode;
-use irys_actors::mempool_service::{TxIngressError, TxIngressMessage};
-use irys_database::{database, Ledger};
-use irys_types::{u64_stringify, IrysTransactionHeader, H256};
+use irys_actors::mempool_service::{GetPendingTxMessage, TxIngressError, TxIngressMessage};
+use irys_database::{
+    database, db_cache::data_size_to_chunk_count, tables::CachedChunksIndex, tables::IngressProofs,
+    Ledger,
+};
+use irys_types::{u64_stringify, IrysTransactionHeader, CONFIG, H256};
 use log::info;
-use reth_db::Database;
+use reth_db::{cursor::DbDupCursorRO as _, transaction::DbTx as _, Database};
 use serde::{Deserialize, Serialize};
 
 /// Handles the HTTP POST request for adding a transaction to the mempool.
@@ -19,39 +22,37 @@ use serde::{Deserialize, Serialize};
 /// delivery and transaction validation.
 pub async fn post_tx(
     state: web::Data<ApiState>,
+    limits: Option<web::Data<IngressLimits>>,
+    req: HttpRequest,
     body: Json<IrysTransactionHeader>,
-) -> actix_web::Result<HttpResponse> {
+) -> Result<HttpResponse, ApiError> {
     let tx = body.into_inner();
+    let (signer, data_root) = (tx.signer, tx.data_root);
+    if let Some(limits) = &limits {
+        limits.check_ip(&req)?;
+        if limits.per_signer.is_exceeded(&signer) {
+            return Err(ApiError::RateLimited {
+                err: format!("Too many transactions from {}", signer),
+            });
+        }
+    }
 
     // Validate transaction is valid. Check balances etc etc.
     let tx_ingress_msg = TxIngressMessage(tx);
-    let msg_result = state.mempool.send(tx_ingress_msg).await;
-
-    // Handle failure to deliver the message (e.g., actor unresponsive or unavailable)
-    if let Err(err) = msg_result {
-        return Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
-            .body(format!("Failed to deliver transaction: {:?}", err)));
+    let result = state.mempool.send(tx_ingress_msg).await?;
+    if let (Some(limits), Ok(())) = (&limits, &result) {
+        limits.per_signer.hit(signer);
+        // Chunks for the data root are no longer for an unknown tx
+        limits.unknown_data_roots.reset(&data_root);
     }
 
-    // If message delivery succeeded, check for validation errors within the response
-    let inner_result = msg_result.unwrap();
-    if let Err(err) = inner_result {
-        return match err {
-            TxIngressError::InvalidSignature => Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
-                .body(format!("Invalid Signature: {:?}", err))),
-            TxIngressError::Unfunded => Ok(HttpResponse::build(StatusCode::PAYMENT_REQUIRED)
-                .body(format!("Unfunded: {:?}", err))),
-            TxIngressError::Skipped => Ok(HttpResponse::Ok()
-                .body("Already processed: the transaction was previously handled")),
-            TxIngressError::Other(err) => {
-                Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(format!("Failed to deliver transaction: {:?}", err)))
-            }
-        };
+    match result {
+        Ok(()) => Ok(HttpResponse::Ok().finish()),
+        Err(TxIngressError::Skipped) => Ok(
+            HttpResponse::Ok().body("Already processed: the transaction was previously handled")
+        ),
+        Err(err) => Err(err.into()),
     }
-
-    // If everything succeeded, return an HTTP 200 OK response
-    Ok(HttpResponse::Ok().finish())
 }
 
 pub async fn get_tx_header_api(
@@ -63,10 +64,7 @@ pub async fn get_tx_header_api(
     get_tx_header(&state, tx_id).map(web::Json)
 }
 
-pub fn get_tx_header(
-    state: &web::Data<ApiState>,
-    tx_id: H256,
-) -> Result<IrysTransactionHeader, ApiError> {
+pub fn get_tx_header(state: &ApiState, tx_id: H256) -> Result<IrysTransactionHeader, ApiError> {
     match state
         .db
         .view_eyre(|tx| database::tx_header_by_txid(tx, &tx_id))
@@ -120,13 +118,243 @@ pub struct TxOffset {
     pub data_start_offset: u64,
 }
 
+/// Where a transaction is in its lifecycle, from most to least advanced:
+/// promoted, ingress proof generated, migrated, included, pending
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxLifecycle {
+    /// In the mempool, waiting to be included in a block
+    Pending,
+    /// In the mempool, with some but not all of its chunks uploaded
+    ChunksPartiallyUploaded,
+    /// Included in a block of the Submit ledger
+    Included,
+    /// Included, and its chunks were migrated to the storage modules
+    Migrated,
+    /// All chunks were uploaded and an ingress proof was generated
+    IngressProofGenerated,
+    /// Promoted to the Publish ledger
+    Promoted,
+    /// Its Submit ledger term ended without it being promoted
+    Expired,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatus {
+    pub status: TxLifecycle,
+    /// Number of the tx's chunks in the chunk cache
+    pub cached_chunks: u32,
+    pub expected_chunks: u32,
+    /// The Submit ledger block including the tx
+    pub submit_block: Option<TxBlock>,
+    /// The Publish ledger block promoting the tx
+    pub publish_block: Option<TxBlock>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxBlock {
+    pub block_hash: H256,
+    #[serde(with = "u64_stringify")]
+    pub height: u64,
+    #[serde(with = "u64_stringify")]
+    pub confirmations: u64,
+}
+
+pub async fn get_tx_status(
+    state: web::Data<ApiState>,
+    path: web::Path<H256>,
+) -> Result<Json<TxStatus>, ApiError> {
+    let tx_id: H256 = path.into_inner();
+    let pending = state
+        .mempool
+        .send(GetPendingTxMessage(tx_id))
+        .await
+        .map_err(|e| ApiError::Internal {
+            err: format!("Failed to query the mempool: {}", e),
+        })?;
+    let is_pending = pending.is_some();
+    let tx_header = match pending {
+        Some(tx_header) => tx_header,
+        None => get_tx_header(&state, tx_id)?,
+    };
+
+    let db_error = |_| ApiError::Internal {
+        err: String::from("db error"),
+    };
+    let read_tx = state.db.tx().map_err(db_error)?;
+    let cached_chunks = read_tx
+        .cursor_dup_read::<CachedChunksIndex>()
+        .map_err(db_error)?
+        .dup_count(tx_header.data_root)
+        .map_err(db_error)?
+        .unwrap_or_default();
+    let has_ingress_proof = read_tx
+        .get::<IngressProofs>(tx_header.data_root)
+        .map_err(db_error)?
+        .is_some();
+    drop(read_tx);
+    let expected_chunks = data_size_to_chunk_count(tx_header.data_size, CONFIG.chunk_size)
+        .map_err(|e| ApiError::Internal { err: e.to_string() })?;
+
+    let (submit_block, publish_block) = find_tx_blocks(&state, tx_id)?;
+
+    let status = TxProgress {
+        is_pending,
+        cached_chunks,
+        expected_chunks,
+        has_ingress_proof,
+        is_promoted: publish_block.is_some() || tx_header.ingress_proofs.is_some(),
+        submit_confirmations: submit_block.as_ref().map(|block| block.confirmations),
+        is_migrated: submit_block.is_some() && is_migrated(&state, &tx_header),
+    }
+    .lifecycle(submit_term_length());
+
+    Ok(Json(TxStatus {
+        status,
+        cached_chunks,
+        expected_chunks,
+        submit_block,
+        publish_block,
+    }))
+}
+
+/// What the node knows of a tx, to tell its [`TxLifecycle`] status
+#[derive(Clone, Copy, Debug, Default)]
+struct TxProgress {
+    is_pending: bool,
+    cached_chunks: u32,
+    expected_chunks: u32,
+    has_ingress_proof: bool,
+    is_promoted: bool,
+    /// Confirmations of the canonical Submit ledger block including the tx
+    submit_confirmations: Option<u64>,
+    is_migrated: bool,
+}
+
+impl TxProgress {
+    fn lifecycle(&self, submit_term_length: u64) -> TxLifecycle {
+        if self.is_promoted {
+            TxLifecycle::Promoted
+        } else if self.has_ingress_proof {
+            TxLifecycle::IngressProofGenerated
+        } else if let Some(confirmations) = self.submit_confirmations {
+            if confirmations >= submit_term_length {
+                TxLifecycle::Expired
+            } else if self.is_migrated {
+                TxLifecycle::Migrated
+            } else {
+                TxLifecycle::Included
+            }
+        } else if self.is_pending {
+            if self.cached_chunks > 0 && self.cached_chunks < self.expected_chunks {
+                TxLifecycle::ChunksPartiallyUploaded
+            } else {
+                TxLifecycle::Pending
+            }
+        } else {
+            // known but neither pending nor in a canonical block
+            TxLifecycle::Expired
+        }
+    }
+}
+
+/// The number of blocks a tx stays in the Submit ledger
+fn submit_term_length() -> u64 {
+    CONFIG.submit_ledger_epoch_length * CONFIG.num_blocks_in_epoch
+}
+
+fn is_migrated(state: &ApiState, tx_header: &IrysTransactionHeader) -> bool {
+    state
+        .chunk_provider
+        .get_ledger_offsets_for_data_root(Ledger::Submit, tx_header.data_root)
+        .is_ok_and(|offsets| offsets.is_some())
+}
+
+/// Finds the canonical blocks including a tx in the Submit and Publish ledgers: the
+/// block tree's canonical chain first, then the blocks recorded by the tx's
+/// [`TxInclusion`](irys_database::db_index::TxInclusion) if still in the block index
+fn find_tx_blocks(
+    state: &ApiState,
+    tx_id: H256,
+) -> Result<(Option<TxBlock>, Option<TxBlock>), ApiError> {
+    let block_tree_guard = state.block_tree.clone().ok_or(ApiError::Internal {
+        err: String::from("block tree error"),
+    })?;
+    let (canonical_chain, _) = block_tree_guard.read().get_canonical_chain();
+    let Some((_, tip_height, _, _)) = canonical_chain.last() else {
+        return Ok((None, None));
+    };
+    let tip_height = *tip_height;
+    let tx_block = |block_hash: H256, height: u64| TxBlock {
+        block_hash,
+        height,
+        confirmations: tip_height.saturating_sub(height),
+    };
+
+    let mut submit_block = None;
+    let mut publish_block = None;
+    for (block_hash, height, publish_txs, submit_txs) in canonical_chain.iter().rev() {
+        if submit_block.is_none() && submit_txs.contains(&tx_id) {
+            submit_block = Some(tx_block(*block_hash, *height));
+        }
+        if publish_block.is_none() && publish_txs.contains(&tx_id) {
+            publish_block = Some(tx_block(*block_hash, *height));
+        }
+    }
+    if submit_block.is_some() && publish_block.is_some() {
+        return Ok((submit_block, publish_block));
+    }
+
+    // older blocks are recorded as they are confirmed, the block index tells
+    // whether they are still canonical
+    let db_error = |e: eyre::Report| ApiError::Internal { err: e.to_string() };
+    let Some(inclusion) = state
+        .db
+        .view_eyre(|tx| database::tx_inclusion_by_txid(tx, &tx_id))
+        .map_err(db_error)?
+    else {
+        return Ok((submit_block, publish_block));
+    };
+    let block_index_guard = state.block_index.clone().ok_or(ApiError::Internal {
+        err: String::from("block index error"),
+    })?;
+    let canonical_block = |block_hash: Option<H256>| -> Result<Option<TxBlock>, ApiError> {
+        let Some(block_hash) = block_hash else {
+            return Ok(None);
+        };
+        let Some(block) = state
+            .db
+            .view_eyre(|tx| database::block_header_by_hash(tx, &block_hash))
+            .map_err(db_error)?
+        else {
+            return Ok(None);
+        };
+        Ok(block_index_guard
+            .read()
+            .get_item(block.height as usize)
+            .filter(|item| item.block_hash == block_hash)
+            .map(|_| tx_block(block_hash, block.height)))
+    };
+    if submit_block.is_none() {
+        submit_block = canonical_block(inclusion.submit_block_hash)?;
+    }
+    if publish_block.is_none() {
+        publish_block = canonical_block(inclusion.publish_block_hash)?;
+    }
+
+    Ok((submit_block, publish_block))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::routes;
+    use crate::{error::ErrorResponse, routes};
 
     use super::*;
     use actix::{Actor, ArbiterService, SystemRegistry, SystemService as _};
     use actix_web::{middleware::Logger, test, App, Error};
+    use awc::http::StatusCode;
     use base58::ToBase58;
     use database::open_or_create_db;
     use irys_actors::mempool_service::MempoolService;
@@ -138,6 +366,72 @@ mod tests {
     use tempfile::tempdir;
     use tracing::{error, info};
 
+    #[test]
+    fn test_tx_lifecycle() {
+        let term = 10;
+        let pending = TxProgress {
+            is_pending: true,
+            expected_chunks: 4,
+            ..Default::default()
+        };
+        assert_eq!(pending.lifecycle(term), TxLifecycle::Pending);
+
+        let partial = TxProgress {
+            cached_chunks: 2,
+            ..pending
+        };
+        assert_eq!(
+            partial.lifecycle(term),
+            TxLifecycle::ChunksPartiallyUploaded
+        );
+        let uploaded = TxProgress {
+            cached_chunks: 4,
+            ..pending
+        };
+        assert_eq!(uploaded.lifecycle(term), TxLifecycle::Pending);
+
+        let included = TxProgress {
+            is_pending: false,
+            submit_confirmations: Some(1),
+            ..partial
+        };
+        assert_eq!(included.lifecycle(term), TxLifecycle::Included);
+        let migrated = TxProgress {
+            is_migrated: true,
+            ..included
+        };
+        assert_eq!(migrated.lifecycle(term), TxLifecycle::Migrated);
+
+        let proven = TxProgress {
+            has_ingress_proof: true,
+            ..migrated
+        };
+        assert_eq!(proven.lifecycle(term), TxLifecycle::IngressProofGenerated);
+        let promoted = TxProgress {
+            is_promoted: true,
+            ..proven
+        };
+        assert_eq!(promoted.lifecycle(term), TxLifecycle::Promoted);
+
+        // the Submit term ended without a promotion
+        let expired = TxProgress {
+            submit_confirmations: Some(term),
+            ..migrated
+        };
+        assert_eq!(expired.lifecycle(term), TxLifecycle::Expired);
+        let dropped = TxProgress {
+            is_pending: false,
+            ..pending
+        };
+        assert_eq!(dropped.lifecycle(term), TxLifecycle::Expired);
+        // promotion outlives the Submit term
+        let promoted = TxProgress {
+            submit_confirmations: Some(term),
+            ..promoted
+        };
+        assert_eq!(promoted.lifecycle(term), TxLifecycle::Promoted);
+    }
+
     #[actix_web::test]
     async fn test_get_tx() -> eyre::Result<()> {
         //std::env::set_var("RUST_LOG", "debug");
@@ -167,6 +461,7 @@ mod tests {
             IrysSigner::random_signer(),
             storage_config.clone(),
             Arc::new(Vec::new()).to_vec(),
+            None,
         );
         SystemRegistry::set(mempool_service.start());
         let mempool_addr = MempoolService::from_registry();
@@ -180,6 +475,10 @@ mod tests {
         let app_state = ApiState {
             reth_provider: None,
             block_index: None,
+            partition_assignments: None,
+            ledgers: None,
+            vdf_steps: None,
+            actor_addresses: None,
             block_tree: None,
             db: DatabaseProvider(arc_db.clone()),
             mempool: mempool_addr,
@@ -226,6 +525,7 @@ mod tests {
             IrysSigner::random_signer(),
             storage_config.clone(),
             Arc::new(Vec::new()).to_vec(),
+            None,
         );
         SystemRegistry::set(mempool_service.start());
         let mempool_addr = MempoolService::from_registry();
@@ -239,6 +539,10 @@ mod tests {
         let app_state = ApiState {
             reth_provider: None,
             block_index: None,
+            partition_assignments: None,
+            ledgers: None,
+            vdf_steps: None,
+            actor_addresses: None,
             block_tree: None,
             db: DatabaseProvider(db_arc.clone()),
             mempool: mempool_addr,
@@ -259,12 +563,12 @@ mod tests {
 
         let resp = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
-        let result: ApiError = test::read_body_json(resp).await;
+        let result: ErrorResponse = test::read_body_json(resp).await;
         let tx_error = ApiError::ErrNoId {
             id: tx.id.to_string(),
             err: String::from("tx not found"),
         };
-        assert_eq!(tx_error, result);
+        assert_eq!(tx_error.to_response(), result);
         Ok(())
     }
 }
@@ -0,0 +1,255 @@
+use std::sync::{Arc, OnceLock};
+
+use irys_database::Ledger;
+use irys_primitives::{CommitmentStatus, IrysTxId};
+use irys_reth_node_bridge::rpc::commitment_status_at;
+use irys_types::{
+    partition::PartitionAssignment, u64_stringify, Address, IrysTransactionHeader, H256,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ApiError,
+    routes::{
+        block::{get_block_by_tag, CombinedBlockHeader},
+        price::price_for,
+        tx::get_tx_header,
+    },
+    ApiState,
+};
+
+/// The `irys_` JSON-RPC namespace, served by the reth RPC server alongside `eth_`.
+/// Methods read the same state as the HTTP API routes.
+#[rpc(server, client, namespace = "irys")]
+pub trait IrysApi {
+    /// Returns a block by tag: `latest`, a block height or a base58 block hash
+    #[method(name = "getBlock")]
+    fn get_block(&self, block_tag: String) -> RpcResult<CombinedBlockHeader>;
+
+    #[method(name = "getTransaction")]
+    fn get_transaction(&self, tx_id: H256) -> RpcResult<IrysTransactionHeader>;
+
+    /// Returns the number of chunks in each ledger as of the latest indexed block
+    #[method(name = "getLedgerSizes")]
+    fn get_ledger_sizes(&self) -> RpcResult<Vec<LedgerSize>>;
+
+    #[method(name = "getPartitionAssignment")]
+    fn get_partition_assignment(
+        &self,
+        partition_hash: H256,
+    ) -> RpcResult<Option<PartitionAssignment>>;
+
+    /// Returns the data & capacity partitions assigned to `miner_address`
+    #[method(name = "getPartitionAssignments")]
+    fn get_partition_assignments(
+        &self,
+        miner_address: Address,
+    ) -> RpcResult<Vec<PartitionAssignment>>;
+
+    /// Returns the status of the stake or pledge commitment `address` made with
+    /// tx `tx_id`, as of the latest block
+    #[method(name = "getCommitmentStatus")]
+    fn get_commitment_status(
+        &self,
+        address: Address,
+        tx_id: H256,
+    ) -> RpcResult<Option<CommitmentStatus>>;
+
+    /// Returns the price (as a decimal string) to store `size` bytes in `ledger`
+    #[method(name = "getPrice")]
+    fn get_price(&self, ledger: String, size: u64) -> RpcResult<String>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerSize {
+    pub ledger: Ledger,
+    #[serde(with = "u64_stringify")]
+    pub num_chunks: u64,
+}
+
+/// Implements the `irys_` namespace. The RPC server is started with reth, before
+/// the Irys services exist, so the [`ApiState`] is set once they're started.
+#[derive(Clone, Default)]
+pub struct IrysRpc {
+    pub state: Arc<OnceLock<ApiState>>,
+}
+
+impl IrysRpc {
+    fn state(&self) -> RpcResult<&ApiState> {
+        self.state.get().ok_or(ErrorObjectOwned::owned::<String>(
+            -32082,
+            "node is still starting",
+            None,
+        ))
+    }
+}
+
+impl From<ApiError> for ErrorObjectOwned {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::ErrNoId { id, err } => ErrorObjectOwned::owned(-32080, err, Some(id)),
+            ApiError::Internal { err } => ErrorObjectOwned::owned::<String>(-32081, err, None),
+            other => ErrorObjectOwned::owned(-32602, other.message(), Some(other.code())),
+        }
+    }
+}
+
+impl IrysApiServer for IrysRpc {
+    fn get_block(&self, block_tag: String) -> RpcResult<CombinedBlockHeader> {
+        Ok(get_block_by_tag(self.state()?, &block_tag)?)
+    }
+
+    fn get_transaction(&self, tx_id: H256) -> RpcResult<IrysTransactionHeader> {
+        Ok(get_tx_header(self.state()?, tx_id)?)
+    }
+
+    fn get_ledger_sizes(&self) -> RpcResult<Vec<LedgerSize>> {
+        let block_index = self
+            .state()?
+            .block_index
+            .clone()
+            .ok_or(ApiError::Internal {
+                err: String::from("block index error"),
+            })?;
+        let guard = block_index.read();
+        let Some(latest) = guard.get_latest_item() else {
+            return Ok(vec![]);
+        };
+        Ok(Ledger::iter()
+            .map(|ledger| LedgerSize {
+                ledger,
+                num_chunks: latest.ledgers[ledger].max_chunk_offset,
+            })
+            .collect())
+    }
+
+    fn get_partition_assignment(
+        &self,
+        partition_hash: H256,
+    ) -> RpcResult<Option<PartitionAssignment>> {
+        let partition_assignments =
+            self.state()?
+                .partition_assignments
+                .clone()
+                .ok_or(ApiError::Internal {
+                    err: String::from("partition assignments error"),
+                })?;
+        let assignment = partition_assignments.read().get_assignment(partition_hash);
+        Ok(assignment)
+    }
+
+    fn get_partition_assignments(
+        &self,
+        miner_address: Address,
+    ) -> RpcResult<Vec<PartitionAssignment>> {
+        let partition_assignments =
+            self.state()?
+                .partition_assignments
+                .clone()
+                .ok_or(ApiError::Internal {
+                    err: String::from("partition assignments error"),
+                })?;
+        let guard = partition_assignments.read();
+        Ok(guard
+            .data_partitions
+            .values()
+            .chain(guard.capacity_partitions.values())
+            .filter(|assignment| assignment.miner_address == miner_address)
+            .copied()
+            .collect())
+    }
+
+    fn get_commitment_status(
+        &self,
+        address: Address,
+        tx_id: H256,
+    ) -> RpcResult<Option<CommitmentStatus>> {
+        let reth = self
+            .state()?
+            .reth_provider
+            .clone()
+            .ok_or(ApiError::Internal {
+                err: String::from("reth provider error"),
+            })?;
+        commitment_status_at(
+            &reth.provider,
+            address,
+            IrysTxId::from_slice(tx_id.as_bytes()),
+            None,
+        )
+    }
+
+    fn get_price(&self, ledger: String, size: u64) -> RpcResult<String> {
+        let ledger = Ledger::from_url(&ledger).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, "Ledger type not supported", Some(e.to_string()))
+        })?;
+        Ok(price_for(ledger, size).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix::{Actor, SystemRegistry, SystemService as _};
+    use irys_actors::mempool_service::MempoolService;
+    use irys_database::{database, open_or_create_db, tables::IrysTables};
+    use irys_storage::ChunkProvider;
+    use irys_types::{app_state::DatabaseProvider, irys::IrysSigner, StorageConfig};
+    use reth::tasks::TaskManager;
+    use reth_db::Database as _;
+    use tempfile::tempdir;
+
+    #[actix_web::test]
+    async fn get_transaction_reads_the_api_state() -> eyre::Result<()> {
+        let path = tempdir().unwrap();
+        let db = open_or_create_db(path, IrysTables::ALL, None).unwrap();
+        let tx_header = IrysTransactionHeader::default();
+        db.update(|tx| -> eyre::Result<()> { database::insert_tx_header(tx, &tx_header) })??;
+        let arc_db = Arc::new(db);
+
+        let task_manager = TaskManager::current();
+        let storage_config = StorageConfig::default();
+        let mempool_service = MempoolService::new(
+            DatabaseProvider(arc_db.clone()),
+            task_manager.executor(),
+            IrysSigner::random_signer(),
+            storage_config.clone(),
+            vec![],
+            None,
+        );
+        SystemRegistry::set(mempool_service.start());
+        let chunk_provider =
+            ChunkProvider::new(storage_config, vec![], DatabaseProvider(arc_db.clone()));
+
+        let irys_rpc = IrysRpc::default();
+        let state = irys_rpc.state.clone();
+        let module = irys_rpc.into_rpc();
+
+        // the node is still starting
+        let res: Result<IrysTransactionHeader, _> =
+            module.call("irys_getTransaction", [tx_header.id]).await;
+        assert!(res.is_err());
+
+        let _ = state.set(ApiState {
+            reth_provider: None,
+            block_index: None,
+            partition_assignments: None,
+            ledgers: None,
+            vdf_steps: None,
+            actor_addresses: None,
+            block_tree: None,
+            db: DatabaseProvider(arc_db.clone()),
+            mempool: MempoolService::from_registry(),
+            chunk_provider: Arc::new(chunk_provider),
+        });
+        let res: IrysTransactionHeader = module.call("irys_getTransaction", [tx_header.id]).await?;
+        assert_eq!(res, tx_header);
+
+        let res: Result<IrysTransactionHeader, _> =
+            module.call("irys_getTransaction", [H256::random()]).await;
+        assert!(res.is_err());
+        Ok(())
+    }
+}
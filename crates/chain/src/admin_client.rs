@@ -0,0 +1,170 @@
+use awc::http::Method;
+use clap::Subcommand;
+use eyre::eyre;
+use irys_types::CONFIG;
+
+/// Node operations served by the admin API
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    /// Shows whether each partition is being mined
+    MiningStatus,
+    /// Starts mining, on every partition unless one is given
+    StartMining {
+        #[arg(long)]
+        partition_hash: Option<String>,
+    },
+    /// Stops mining, on every partition unless one is given
+    StopMining {
+        #[arg(long)]
+        partition_hash: Option<String>,
+    },
+    /// Lists a storage module's packing queue
+    Packing { storage_module_id: usize },
+    /// Removes a queued packing job
+    CancelPacking {
+        storage_module_id: usize,
+        index: usize,
+    },
+    /// Moves a queued packing job right behind the job being packed
+    PrioritizePacking {
+        storage_module_id: usize,
+        index: usize,
+    },
+    /// Checks a storage module's packing and repacks invalid chunks
+    Scrub { storage_module_id: usize },
+    /// Requests packing for a storage module's uninitialized chunks
+    Resync { storage_module_id: usize },
+    /// Writes a storage module's pending chunks to disk
+    Flush { storage_module_id: usize },
+    /// Gracefully shuts the node down
+    Shutdown,
+}
+
+impl AdminCommand {
+    /// Method and path, relative to the admin API root, of the command's request
+    pub fn request(&self) -> (Method, String) {
+        match self {
+            Self::MiningStatus => (Method::GET, String::from("/mining")),
+            Self::StartMining { partition_hash } => {
+                (Method::POST, mining_path("start", partition_hash))
+            }
+            Self::StopMining { partition_hash } => {
+                (Method::POST, mining_path("stop", partition_hash))
+            }
+            Self::Packing { storage_module_id } => {
+                (Method::GET, format!("/packing/{}", storage_module_id))
+            }
+            Self::CancelPacking {
+                storage_module_id,
+                index,
+            } => (
+                Method::DELETE,
+                format!("/packing/{}/{}", storage_module_id, index),
+            ),
+            Self::PrioritizePacking {
+                storage_module_id,
+                index,
+            } => (
+                Method::POST,
+                format!("/packing/{}/{}/prioritize", storage_module_id, index),
+            ),
+            Self::Scrub { storage_module_id } => (
+                Method::POST,
+                format!("/storage/modules/{}/scrub", storage_module_id),
+            ),
+            Self::Resync { storage_module_id } => (
+                Method::POST,
+                format!("/storage/modules/{}/resync", storage_module_id),
+            ),
+            Self::Flush { storage_module_id } => (
+                Method::POST,
+                format!("/storage/modules/{}/flush", storage_module_id),
+            ),
+            Self::Shutdown => (Method::POST, String::from("/shutdown")),
+        }
+    }
+}
+
+fn mining_path(action: &str, partition_hash: &Option<String>) -> String {
+    match partition_hash {
+        Some(partition_hash) => format!("/mining/{}?partitionHash={}", action, partition_hash),
+        None => format!("/mining/{}", action),
+    }
+}
+
+/// The admin API of the local node, an error if it's disabled (`admin_port` is 0)
+pub fn default_admin_url() -> eyre::Result<String> {
+    admin_url(CONFIG.admin_port)
+}
+
+fn admin_url(admin_port: u16) -> eyre::Result<String> {
+    if admin_port == 0 {
+        return Err(eyre!(
+            "The admin API is disabled (admin_port is 0), set admin_port in the node config or pass --url"
+        ));
+    }
+    Ok(format!("http://127.0.0.1:{}/admin", admin_port))
+}
+
+/// Sends `command` to the admin API at `base_url`, returning the response body
+pub async fn send_admin_command(base_url: &str, command: &AdminCommand) -> eyre::Result<String> {
+    let (method, path) = command.request();
+    let url = format!("{}{}", base_url.trim_end_matches('/'), path);
+    // awc's connection pool spawns local tasks
+    tokio::task::LocalSet::new()
+        .run_until(async move {
+            let mut response = awc::Client::default()
+                .request(method, &url)
+                .send()
+                .await
+                .map_err(|e| eyre!("Request to {} failed: {}", url, e))?;
+            let body = response
+                .body()
+                .await
+                .map_err(|e| eyre!("Failed to read the response from {}: {}", url, e))?;
+            let body = String::from_utf8_lossy(&body).into_owned();
+            if response.status().is_success() {
+                Ok(body)
+            } else {
+                Err(eyre!("{} returned {}: {}", url, response.status(), body))
+            }
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_commands_to_admin_routes() {
+        assert_eq!(
+            AdminCommand::StopMining {
+                partition_hash: Some(String::from("abc"))
+            }
+            .request(),
+            (Method::POST, String::from("/mining/stop?partitionHash=abc"))
+        );
+        assert_eq!(
+            AdminCommand::CancelPacking {
+                storage_module_id: 2,
+                index: 3
+            }
+            .request(),
+            (Method::DELETE, String::from("/packing/2/3"))
+        );
+        assert_eq!(
+            AdminCommand::Flush {
+                storage_module_id: 1
+            }
+            .request(),
+            (Method::POST, String::from("/storage/modules/1/flush"))
+        );
+    }
+
+    #[test]
+    fn admin_url_requires_an_admin_port() {
+        assert!(admin_url(0).is_err());
+        assert_eq!(admin_url(8081).unwrap(), "http://127.0.0.1:8081/admin");
+    }
+}
@@ -17,13 +17,17 @@ use irys_actors::{
     mempool_service::MempoolService,
     mining::PartitionMiningActor,
     packing::{PackingActor, PackingRequest},
+    shutdown::{Drain, Shutdown, ShutdownStage},
+    solution_aggregator::{SolutionAggregatorActor, DEFAULT_SOLUTION_COLLECTION_WINDOW},
     validation_service::ValidationService,
     vdf_service::{GetVdfStateMessage, VdfService, VdfStepsReadGuard},
     ActorAddresses, BlockFinalizedMessage,
 };
-use irys_api_server::{run_server, ApiState};
-use irys_config::{decode_hex, IrysNodeConfig, STORAGE_SUBMODULES_CONFIG};
-use irys_database::database;
+use irys_api_server::{
+    rpc::{IrysApiServer as _, IrysRpc},
+    run_server, ApiState,
+};
+use irys_config::{decode_hex, genesis::IrysGenesis, IrysNodeConfig, STORAGE_SUBMODULES_CONFIG};
 use irys_packing::{PackingType, PACKING_TYPE};
 use irys_reth_node_bridge::adapter::node::RethNodeContext;
 pub use irys_reth_node_bridge::node::{
@@ -32,44 +36,59 @@ pub use irys_reth_node_bridge::node::{
 
 use irys_storage::{
     initialize_storage_files,
+    pd_chunk_cache::{PdChunkCache, DEFAULT_PD_CHUNK_CACHE_CAPACITY},
     reth_provider::{IrysRethProvider, IrysRethProviderInner},
     ChunkProvider, ChunkType, StorageModule, StorageModuleVec,
 };
 use irys_types::{
-    app_state::DatabaseProvider, calculate_initial_difficulty, irys::IrysSigner,
-    vdf_config::VDFStepsConfig, StorageConfig, CHUNK_SIZE, CONFIG, H256,
+    app_state::DatabaseProvider, irys::IrysSigner, vdf_config::VDFStepsConfig,
+    DifficultyAdjustmentConfig, StorageConfig, CHUNK_SIZE, CONFIG,
 };
+use irys_vdf::{step_vdf_difficulty, VdfResetSchedule, VdfStepsFastForward};
+use jsonrpsee::Methods;
 use reth::rpc::eth::EthApiServer as _;
 use reth::{
     builder::FullNode,
     chainspec::ChainSpec,
     core::irys_ext::NodeExitReason,
+    providers::BlockReader as _,
     tasks::{TaskExecutor, TaskManager},
 };
 use reth_cli_runner::{run_to_completion_or_panic, run_until_ctrl_c};
 use reth_db::{Database as _, HasName, HasTableType};
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::{
     sync::{mpsc, Arc, OnceLock, RwLock},
     time::{SystemTime, UNIX_EPOCH},
 };
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use tokio::{
     runtime::Handle,
     sync::oneshot::{self},
 };
 
+use crate::genesis::{all_partition_assignments, generate_genesis};
+use crate::recovery::{reconcile_block_index, reset_chunks_past_ledger_end};
 use crate::vdf::run_vdf;
 use irys_testing_utils::utils::setup_tracing_and_temp_dir;
 
-pub async fn start() -> eyre::Result<IrysNodeCtx> {
+/// Starts a node, with the network's genesis if given (otherwise one is built at startup)
+pub async fn start(genesis: Option<IrysGenesis>) -> eyre::Result<IrysNodeCtx> {
     let config: IrysNodeConfig = IrysNodeConfig {
         mining_signer: IrysSigner::mainnet_from_slice(&decode_hex(CONFIG.mining_key).unwrap()),
+        genesis,
         ..IrysNodeConfig::default()
     };
 
-    let storage_config = StorageConfig {
+    let storage_config = storage_config_from_config(&config);
+
+    start_irys_node(config, storage_config).await
+}
+
+/// The [`StorageConfig`] of a node running with the `CONFIG` parameters
+pub fn storage_config_from_config(config: &IrysNodeConfig) -> StorageConfig {
+    StorageConfig {
         chunk_size: CONFIG.chunk_size,
         num_chunks_in_partition: CONFIG.num_chunks_in_partition,
         num_chunks_in_recall_range: CONFIG.num_chunks_in_recall_range,
@@ -78,9 +97,7 @@ pub async fn start() -> eyre::Result<IrysNodeCtx> {
         min_writes_before_sync: 1,
         entropy_packing_iterations: CONFIG.entropy_packing_iterations,
         chunk_migration_depth: CONFIG.chunk_migration_depth, // Testnet / single node config
-    };
-
-    start_irys_node(config, storage_config).await
+    }
 }
 
 pub async fn start_for_testing(config: IrysNodeConfig) -> eyre::Result<IrysNodeCtx> {
@@ -132,6 +149,24 @@ pub struct IrysNodeCtx {
     pub storage_config: StorageConfig,
 }
 
+impl IrysNodeCtx {
+    /// Gracefully stops the node: stops the API servers, mining and the VDF, lets
+    /// the actors finish their queued work, flushes the storage modules, then
+    /// waits for reth to exit
+    pub async fn stop(&self) {
+        self.actor_addresses.shutdown.request();
+        self.stopped().await
+    }
+
+    /// Waits until the node stopped, whether on a shutdown request or a signal
+    pub async fn stopped(&self) {
+        self.actor_addresses
+            .shutdown
+            .reached(ShutdownStage::Complete)
+            .await
+    }
+}
+
 pub async fn start_irys_node(
     node_config: IrysNodeConfig,
     storage_config: StorageConfig,
@@ -144,26 +179,28 @@ pub async fn start_irys_node(
 
     let (reth_handle_sender, reth_handle_receiver) =
         oneshot::channel::<FullNode<RethNode, RethNodeAddOns>>();
-    let (irys_node_handle_sender, irys_node_handle_receiver) = oneshot::channel::<IrysNodeCtx>();
-    let (reth_chainspec, mut irys_genesis) = node_config.chainspec_builder.build();
+    let (irys_node_handle_sender, irys_node_handle_receiver) =
+        oneshot::channel::<eyre::Result<IrysNodeCtx>>();
+    let (reth_chainspec, irys_genesis) = match &node_config.genesis {
+        Some(genesis) => {
+            let chain_spec = genesis.chain_spec()?;
+            info!("Using genesis block {}", &genesis.header.block_hash);
+            (chain_spec, genesis.header.clone())
+        }
+        // no genesis file: build a fresh genesis (dev & testing)
+        None => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            let genesis = generate_genesis(&node_config, &storage_config, now.as_millis())?;
+            (genesis.chain_spec()?, genesis.header)
+        }
+    };
     let arc_config = Arc::new(node_config);
-    let mut difficulty_adjustment_config = CONFIG.clone().into();
-
-    // TODO: Hard coding 3 for storage module count isn't great here,
-    // eventually we'll want to relate this to the genesis config
-    irys_genesis.diff =
-        calculate_initial_difficulty(&difficulty_adjustment_config, &storage_config, 3).unwrap();
-
+    let mut difficulty_adjustment_config: DifficultyAdjustmentConfig = CONFIG.clone().into();
     difficulty_adjustment_config.target_block_time = 5;
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-    irys_genesis.timestamp = now.as_millis();
-    irys_genesis.last_diff_timestamp = irys_genesis.timestamp;
     let arc_genesis = Arc::new(irys_genesis);
 
     let mut storage_modules: StorageModuleVec = Vec::new();
 
-    let at_genesis;
-    let latest_block_index;
     let block_index: Arc<RwLock<BlockIndex<Initialized>>> = Arc::new(RwLock::new({
         let mut idx = BlockIndex::default();
         if !CONFIG.persist_data_on_restart {
@@ -172,17 +209,7 @@ pub async fn start_irys_node(
         } else {
             debug!("Not resetting block index");
         }
-        let i = idx.init(arc_config.clone()).await.unwrap();
-
-        at_genesis = i.get_item(0).is_none();
-        if at_genesis {
-            debug!("At genesis!")
-        } else {
-            debug!("Not at genesis!")
-        }
-        latest_block_index = i.get_latest_item().cloned();
-
-        i
+        idx.init(arc_config.clone()).await.unwrap()
     }));
 
     let cloned_arc = arc_config.clone();
@@ -194,6 +221,14 @@ pub async fn start_irys_node(
     // clone as this gets `move`d into the thread
     let irys_provider_1 = irys_provider.clone();
 
+    // the `irys_` RPC namespace is served by Reth, but reads the Irys node's state
+    let irys_rpc = IrysRpc::default();
+    let irys_rpc_state = irys_rpc.state.clone();
+
+    // shared by the actor & reth threads, so they can coordinate their exit
+    let shutdown = Shutdown::default();
+    let reth_shutdown = shutdown.clone();
+
     std::thread::Builder::new()
         .name("actor-main-thread".to_string())
         .stack_size(32 * 1024 * 1024)
@@ -205,14 +240,30 @@ pub async fn start_irys_node(
                 let db = DatabaseProvider(reth_node.provider.database.db.clone());
                 let vdf_config = VDFStepsConfig::default();
 
-                let latest_block = latest_block_index
-                    .map(|b| {
-                        database::block_header_by_hash(&db.tx().unwrap(), &b.block_hash)
-                            .unwrap()
-                            .unwrap()
-                    })
-                    .map(Arc::new)
-                    .unwrap_or(arc_genesis.clone());
+                // After a crash, reth, the block headers & the block index may disagree on
+                // the latest block: resume from the latest block all of them have
+                let latest_block = match reconcile_block_index(
+                    &mut block_index.write().unwrap(),
+                    &db,
+                    |header| Ok(reth_node.provider.block_by_hash(header.evm_block_hash)?.is_some()),
+                ) {
+                    Ok(latest_block) => latest_block,
+                    Err(e) => {
+                        // hand the error to `start_irys_node`, and let reth exit as well
+                        let _ = irys_node_handle_sender.send(Err(e));
+                        shutdown.advance(ShutdownStage::ActorsStopped);
+                        System::current().stop();
+                        return;
+                    }
+                };
+
+                let at_genesis = latest_block.is_none();
+                if at_genesis {
+                    debug!("At genesis!")
+                } else {
+                    debug!("Not at genesis!")
+                }
+                let latest_block = latest_block.map(Arc::new).unwrap_or(arc_genesis.clone());
 
                 // Initialize the epoch_service actor to handle partition ledger assignments
                 let config = EpochServiceConfig {
@@ -223,7 +274,7 @@ pub async fn start_irys_node(
                 let miner_address = node_config.mining_signer.address();
                 debug!("Miner address {:?}", miner_address);
 
-                let reth_service = RethServiceActor::new(reth_node.clone(), db.clone());
+                let reth_service = RethServiceActor::new(reth_node.clone(), db.clone(), irys_provider_1.clone());
                 let reth_arbiter = Arbiter::new();
                 SystemRegistry::set(RethServiceActor::start_in_arbiter(
                     &reth_arbiter.handle(),
@@ -267,11 +318,15 @@ pub async fn start_irys_node(
                         &latest, &safe, &finalized
                     );
 
-                    assert_eq!(
-                        latest.unwrap().unwrap().header.number,
-                        latest_block.height,
-                        "CRITICAL FAILURE: Reth is out of sync with Irys block index!"
-                    );
+                    // reth may be ahead of the block index if the node stopped before
+                    // finalizing the blocks reth imported, the FCU below resets its head
+                    let reth_height = latest.ok().flatten().map(|block| block.header.number);
+                    if reth_height != Some(latest_block.height) {
+                        warn!(
+                            "Reth's latest block {:?} isn't the latest indexed block {}, resetting reth's head",
+                            reth_height, latest_block.height
+                        );
+                    }
                 }
 
                 RethServiceActor::from_registry()
@@ -307,6 +362,17 @@ pub async fn start_irys_node(
 
                 let mut epoch_service = EpochServiceActor::new(Some(config));
                 epoch_service.initialize(&db).await;
+                if let (true, Some(genesis)) = (at_genesis, &node_config.genesis) {
+                    if let Err(e) = genesis
+                        .verify_partition_assignments(all_partition_assignments(&epoch_service))
+                    {
+                        // hand the error to `start_irys_node`, and let reth exit as well
+                        let _ = irys_node_handle_sender.send(Err(e));
+                        shutdown.advance(ShutdownStage::ActorsStopped);
+                        System::current().stop();
+                        return;
+                    }
+                }
                 let epoch_service_actor_addr = epoch_service.start();
 
                 // Retrieve ledger assignments
@@ -361,12 +427,21 @@ pub async fn start_irys_node(
                     // arc_module.pack_with_zeros();
                 }
 
+                // drop the chunks migrated for blocks the block index was rolled back from
+                let latest_item = block_index.read().unwrap().get_latest_item().cloned();
+                if let Err(e) =
+                    reset_chunks_past_ledger_end(&storage_modules, latest_item.as_ref())
+                {
+                    error!("Failed to reset chunks past the end of the ledgers: {}", e);
+                }
+
                 let mempool_service = MempoolService::new(
                     db.clone(),
                     reth_node.task_executor.clone(),
                     node_config.mining_signer.clone(),
                     storage_config.clone(),
                     storage_modules.clone(),
+                    Some(reth_node.clone()),
                 );
                 let mempool_arbiter = Arbiter::new();
                 SystemRegistry::set(MempoolService::start_in_arbiter(
@@ -395,7 +470,9 @@ pub async fn start_irys_node(
                     |_| validation_service,
                 ));
 
-                let (_new_seed_tx, _new_seed_rx) = mpsc::channel::<H256>();
+                // Confirmed blocks feed the VDF thread the seed and difficulty to apply at the
+                // next reset line
+                let (reset_schedule_tx, reset_schedule_rx) = mpsc::channel::<VdfResetSchedule>();
 
                 let block_tree_service = BlockTreeService::new(
                     db.clone(),
@@ -403,6 +480,7 @@ pub async fn start_irys_node(
                     &miner_address,
                     block_index_guard.clone(),
                     storage_config.clone(),
+                    reset_schedule_tx,
                 );
                 let block_tree_arbiter = Arbiter::new();
                 SystemRegistry::set(BlockTreeService::start_in_arbiter(
@@ -431,6 +509,9 @@ pub async fn start_irys_node(
                 let (global_step_number, seed) = vdf_steps_guard.read().get_last_step_and_seed();
                 info!("Starting at global step number: {}", global_step_number);
 
+                let (vdf_fast_forward_tx, vdf_fast_forward_rx) =
+                    mpsc::channel::<VdfStepsFastForward>();
+
                 let block_discovery_actor = BlockDiscoveryActor {
                     block_index_guard: block_index_guard.clone(),
                     partition_assignments_guard: partition_assignments_guard.clone(),
@@ -439,6 +520,7 @@ pub async fn start_irys_node(
                     db: db.clone(),
                     vdf_config: vdf_config.clone(),
                     vdf_steps_guard: vdf_steps_guard.clone(),
+                    vdf_fast_forward_sender: vdf_fast_forward_tx,
                 };
                 let block_discovery_arbiter = Arbiter::new();
                 let block_discovery_addr = BlockDiscoveryActor::start_in_arbiter(
@@ -464,6 +546,19 @@ pub async fn start_irys_node(
                         block_producer_actor
                     });
 
+                // Partitions hand their solutions to the aggregator, which forwards the
+                // best one of each VDF step to the block producer
+                let solution_aggregator = SolutionAggregatorActor::new(
+                    block_producer_addr.clone().recipient(),
+                    block_tree_guard.clone(),
+                    DEFAULT_SOLUTION_COLLECTION_WINDOW,
+                );
+                let solution_aggregator_arbiter = Arbiter::new();
+                let solution_aggregator_addr = SolutionAggregatorActor::start_in_arbiter(
+                    &solution_aggregator_arbiter.handle(),
+                    |_| solution_aggregator,
+                );
+
                 let broadcast_arbiter = Arbiter::new();
                 let broadcast_mining_service =
                     BroadcastMiningService::start_in_arbiter(&broadcast_arbiter.handle(), |_| {
@@ -479,7 +574,7 @@ pub async fn start_irys_node(
                     let partition_mining_actor = PartitionMiningActor::new(
                         miner_address,
                         db.clone(),
-                        block_producer_addr.clone().recipient(),
+                        solution_aggregator_addr.clone().recipient(),
                         sm.clone(),
                         false, // do not start mining automatically
                         vdf_steps_guard.clone(),
@@ -530,16 +625,24 @@ pub async fn start_irys_node(
                     .await
                     .unwrap();
 
-                let (_new_seed_tx, new_seed_rx) = mpsc::channel::<H256>();
                 let (shutdown_tx, shutdown_rx) = mpsc::channel();
 
                 let vdf_config2 = vdf_config.clone();
+                let vdf_steps_guard2 = vdf_steps_guard.clone();
                 let seed = seed.map_or(arc_genesis.vdf_limiter_info.output, |seed| seed.0);
-                let vdf_reset_seed = latest_block.vdf_limiter_info.seed;
+                // The next reset line uses the next_seed and next_vdf_difficulty of the
+                // latest block below it
+                let reset_schedule =
+                    VdfResetSchedule::from_limiter_info(&latest_block.vdf_limiter_info);
+                let vdf_difficulty = step_vdf_difficulty(
+                    &vdf_config,
+                    &latest_block.vdf_limiter_info,
+                    global_step_number,
+                );
 
                 info!(
-                    "Starting VDF thread seed {:?} reset_seed {:?} step_number: {:?}",
-                    seed, arc_genesis.vdf_limiter_info.seed, global_step_number
+                    "Starting VDF thread seed {:?} reset_schedule {:?} vdf_difficulty {} step_number: {:?}",
+                    seed, reset_schedule, vdf_difficulty, global_step_number
                 );
 
                 let vdf_thread_handler = std::thread::spawn(move || {
@@ -557,11 +660,14 @@ pub async fn start_irys_node(
                         vdf_config2,
                         global_step_number,
                         seed,
-                        vdf_reset_seed,
-                        new_seed_rx,
+                        vdf_difficulty,
+                        reset_schedule,
+                        reset_schedule_rx,
+                        vdf_fast_forward_rx,
                         shutdown_rx,
                         broadcast_mining_service.clone(),
                         vdf_service.clone(),
+                        vdf_steps_guard2,
                         atomic_global_step_number.clone(),
                     )
                 });
@@ -569,10 +675,13 @@ pub async fn start_irys_node(
                 let actor_addresses = ActorAddresses {
                     partitions: part_actors_clone,
                     block_producer: block_producer_addr,
+                    solution_aggregator: solution_aggregator_addr,
                     packing: packing_actor_addr,
                     mempool: mempool_addr.clone(),
                     block_index: block_index_actor_addr,
                     epoch_service: epoch_service_actor_addr,
+                    mining_enabled: Arc::new(AtomicBool::new(false)),
+                    shutdown,
                 };
 
                 let chunk_provider =
@@ -584,10 +693,15 @@ pub async fn start_irys_node(
                     .set(IrysRethProviderInner {
                         db: reth_node.provider.database.db.clone(),
                         chunk_provider: arc_chunk_provider.clone(),
+                        block_index: block_index.clone(),
+                        pd_chunk_cache: Arc::new(PdChunkCache::new(
+                            arc_chunk_provider.clone(),
+                            DEFAULT_PD_CHUNK_CACHE_CAPACITY,
+                        )),
                     })
                     .expect("Unable to set IrysRethProvider OnceLock");
 
-                let _ = irys_node_handle_sender.send(IrysNodeCtx {
+                let _ = irys_node_handle_sender.send(Ok(IrysNodeCtx {
                     actor_addresses: actor_addresses.clone(),
                     reth_handle: reth_node.clone(),
                     db: db.clone(),
@@ -597,23 +711,56 @@ pub async fn start_irys_node(
                     vdf_steps_guard: vdf_steps_guard.clone(),
                     vdf_config: vdf_config.clone(),
                     storage_config: storage_config.clone(),
-                });
+                }));
 
-                run_server(ApiState {
+                let api_state = ApiState {
                     mempool: mempool_addr,
                     chunk_provider: arc_chunk_provider.clone(),
                     db,
                     reth_provider: Some(reth_node.clone()),
                     block_tree: Some(block_tree_guard.clone()),
                     block_index: Some(block_index_guard.clone()),
-                })
-                .await;
+                    partition_assignments: Some(partition_assignments_guard.clone()),
+                    ledgers: Some(ledgers_guard.clone()),
+                    vdf_steps: Some(vdf_steps_guard.clone()),
+                    actor_addresses: Some(actor_addresses.clone()),
+                };
+                irys_rpc_state
+                    .set(api_state.clone())
+                    .unwrap_or_else(|_| panic!("Unable to set the irys RPC ApiState OnceLock"));
+
+                // returns once the servers stopped, on a shutdown request or a signal
+                run_server(api_state).await;
+                actor_addresses.shutdown.request();
+                info!("Shutting down");
+
+                if let Err(e) = actor_addresses.stop_mining() {
+                    error!("Failed to stop mining: {}", e);
+                }
 
                 // Send shutdown signal
                 shutdown_tx.send(()).unwrap();
 
                 // Wait for vdf thread to finish & save steps
                 vdf_thread_handler.join().unwrap();
+
+                // let the actors finish the work already queued, in the order finalized
+                // blocks go through them. The chunk migration service answers once the
+                // migrations it started wrote their chunks to the storage modules.
+                let _ = BlockTreeService::from_registry().send(Drain).await;
+                let _ = BlockIndexService::from_registry().send(Drain).await;
+                let _ = ChunkMigrationService::from_registry().send(Drain).await;
+                let _ = MempoolService::from_registry().send(Drain).await;
+
+                for sm in &storage_modules {
+                    if let Err(e) = sm.flush_pending_chunks() {
+                        error!("Failed to flush storage module {}: {}", sm.id, e);
+                    }
+                }
+
+                // reth exits once the node stopped writing to it
+                actor_addresses.shutdown.advance(ShutdownStage::ActorsStopped);
+                System::current().stop();
             });
         })?;
 
@@ -628,14 +775,25 @@ pub async fn start_irys_node(
             let mut task_manager = TaskManager::new(tokio_runtime.handle().clone());
             let exec: reth::tasks::TaskExecutor = task_manager.executor();
 
+            let reth_node = start_reth_node(exec, reth_chainspec, node_config, IrysTables::ALL, reth_handle_sender, irys_provider, irys_rpc.into_rpc().into());
             tokio_runtime.block_on(run_to_completion_or_panic(
                 &mut task_manager,
-                run_until_ctrl_c(start_reth_node(exec, reth_chainspec, node_config, IrysTables::ALL, reth_handle_sender, irys_provider)),
+                run_until_ctrl_c(async {
+                    tokio::select! {
+                        exit_reason = reth_node => exit_reason,
+                        _ = reth_shutdown.reached(ShutdownStage::ActorsStopped) => Ok(NodeExitReason::Normal),
+                    }
+                }),
             )).unwrap();
+
+            // if reth exited first, let the node stop writing to it before completing
+            reth_shutdown.request();
+            tokio_runtime.block_on(reth_shutdown.reached(ShutdownStage::ActorsStopped));
+            reth_shutdown.advance(ShutdownStage::Complete);
         })?;
 
     // wait for the full handle to be send over by the actix thread
-    Ok(irys_node_handle_receiver.await?)
+    irys_node_handle_receiver.await?
 }
 
 async fn start_reth_node<T: HasName + HasTableType>(
@@ -645,6 +803,7 @@ async fn start_reth_node<T: HasName + HasTableType>(
     tables: &[T],
     sender: oneshot::Sender<FullNode<RethNode, RethNodeAddOns>>,
     irys_provider: IrysRethProvider,
+    irys_rpc: Methods,
 ) -> eyre::Result<NodeExitReason> {
     let node_handle = irys_reth_node_bridge::run_node(
         Arc::new(chainspec),
@@ -652,6 +811,7 @@ async fn start_reth_node<T: HasName + HasTableType>(
         irys_config,
         tables,
         irys_provider,
+        irys_rpc,
     )
     .await?;
     sender
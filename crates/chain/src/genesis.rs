@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use eyre::eyre;
+use irys_actors::epoch_service::{EpochServiceActor, EpochServiceConfig};
+use irys_config::{genesis::IrysGenesis, IrysNodeConfig};
+use irys_types::{
+    calculate_initial_difficulty, hash_sha256, partition::PartitionAssignment, StorageConfig,
+    CONFIG, H256,
+};
+
+/// Generates the genesis of a new network. The output only depends on the node's
+/// chainspec (EVM alloc), the storage config and `timestamp`, so it can be regenerated
+/// (and checked) by anyone with the same inputs.
+pub fn generate_genesis(
+    node_config: &IrysNodeConfig,
+    storage_config: &StorageConfig,
+    timestamp: u128,
+) -> eyre::Result<IrysGenesis> {
+    let (chain_spec, mut header) = node_config.chainspec_builder.build();
+
+    // TODO: Hard coding 3 for storage module count isn't great here,
+    // eventually we'll want to relate this to the genesis config
+    header.diff = calculate_initial_difficulty(&CONFIG.clone().into(), storage_config, 3)
+        .map_err(|e| eyre!(e))?;
+    header.timestamp = timestamp;
+    header.last_diff_timestamp = timestamp;
+    // seeds the genesis partition hashes, derived rather than random for determinism
+    header.last_epoch_hash = H256(hash_sha256(
+        &[
+            chain_spec.genesis_hash().as_slice(),
+            &timestamp.to_le_bytes(),
+        ]
+        .concat(),
+    )?);
+    header.block_hash = IrysGenesis::header_hash(&header)?;
+
+    let mut epoch_service = EpochServiceActor::new(Some(EpochServiceConfig {
+        storage_config: storage_config.clone(),
+        ..EpochServiceConfig::default()
+    }));
+    epoch_service
+        .perform_epoch_tasks(Arc::new(header.clone()))
+        .map_err(|e| eyre!("Genesis epoch tasks failed: {:?}", e))?;
+    let mut partition_assignments = all_partition_assignments(&epoch_service);
+    partition_assignments.sort_unstable_by_key(|assignment| assignment.partition_hash);
+
+    Ok(IrysGenesis {
+        header,
+        commitments: IrysGenesis::alloc_commitments(&chain_spec.genesis),
+        evm: chain_spec.genesis.clone(),
+        partition_assignments,
+    })
+}
+
+/// The data & capacity partition assignments of the epoch service
+pub fn all_partition_assignments(epoch_service: &EpochServiceActor) -> Vec<PartitionAssignment> {
+    let partition_assignments = epoch_service.partition_assignments.read().unwrap();
+    partition_assignments
+        .data_partitions
+        .values()
+        .chain(partition_assignments.capacity_partitions.values())
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use irys_types::{irys::IrysSigner, Address};
+    use reth_primitives::irys_primitives::{Commitment, Commitments};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn generates_loadable_deterministic_genesis() -> eyre::Result<()> {
+        let signer = IrysSigner::random_signer();
+        let node_config = IrysNodeConfig {
+            mining_signer: signer.clone(),
+            ..IrysNodeConfig::mainnet()
+        };
+        let storage_config = StorageConfig {
+            miner_address: signer.address(),
+            ..StorageConfig::default()
+        };
+
+        let genesis = generate_genesis(&node_config, &storage_config, 1_700_000_000_000)?;
+        assert_eq!(
+            genesis,
+            generate_genesis(&node_config, &storage_config, 1_700_000_000_000)?
+        );
+        assert!(!genesis.partition_assignments.is_empty());
+
+        let dir = tempdir()?;
+        let path = dir.path().join("genesis.json");
+        genesis.write(&path)?;
+        assert_eq!(IrysGenesis::load(&path)?, genesis);
+
+        // any change to the header invalidates the genesis hash
+        let mut tampered = genesis.clone();
+        tampered.header.timestamp += 1;
+        assert!(tampered.chain_spec().is_err());
+
+        // the commitments section has to match the EVM alloc
+        let mut tampered = genesis.clone();
+        tampered
+            .commitments
+            .insert(signer.address(), Commitments(vec![Commitment::default()]));
+        assert!(tampered.chain_spec().is_err());
+
+        // partitions have to be assigned to the same miners
+        genesis.verify_partition_assignments(genesis.partition_assignments.clone())?;
+        let mut reassigned = genesis.partition_assignments.clone();
+        reassigned[0].miner_address = Address::random();
+        assert!(genesis.verify_partition_assignments(reassigned).is_err());
+        Ok(())
+    }
+}
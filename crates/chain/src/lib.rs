@@ -1,3 +1,6 @@
+pub mod admin_client;
 pub mod chain;
+pub mod genesis;
+pub mod recovery;
 pub mod vdf;
 pub use chain::*;
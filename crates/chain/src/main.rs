@@ -1,8 +1,19 @@
-use clap::{command, Parser};
-use irys_chain::chain::start;
-use irys_config::StorageSubmodulesConfig;
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::{command, Parser, Subcommand};
+use irys_chain::{
+    admin_client::{default_admin_url, send_admin_command, AdminCommand},
+    chain::{start, storage_config_from_config},
+    genesis::generate_genesis,
+};
+use irys_config::{decode_hex, genesis::IrysGenesis, IrysNodeConfig, StorageSubmodulesConfig};
+use irys_types::{irys::IrysSigner, CONFIG};
 use reth_tracing::tracing_subscriber::fmt::SubscriberBuilder;
 use reth_tracing::tracing_subscriber::util::SubscriberInitExt;
+use tracing::info;
 use tracing_subscriber::EnvFilter;
 
 #[derive(Parser, Debug)]
@@ -11,22 +22,84 @@ struct Args {
     /// Name of the person to greet
     #[arg(short, long, default_value = "./database")]
     database: String,
+
+    /// Genesis file of the network to join, a new genesis is built at startup if not set
+    #[arg(long)]
+    genesis: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generates the genesis file of a new network from the node's config
+    Genesis {
+        /// Where to write the genesis file
+        #[arg(short, long, default_value = "genesis.json")]
+        output: PathBuf,
+
+        /// Genesis timestamp, in milliseconds since `UNIX_EPOCH` (defaults to now)
+        #[arg(long)]
+        timestamp: Option<u128>,
+    },
+    /// Controls a running node through its admin API
+    Admin {
+        /// Admin API root, defaults to the local node's
+        #[arg(long)]
+        url: Option<String>,
+
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
 }
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
+    let args = Args::parse();
     StorageSubmodulesConfig::load();
 
-    // TODO: fix this, we used to await the reth node exit future but can't anymore
-    // so we need another near-infinite blocking future
     let _ = SubscriberBuilder::default()
         .with_env_filter(EnvFilter::from_default_env())
         .finish()
         .try_init();
 
-    let handle = start().await?;
+    if let Some(Command::Admin { url, command }) = &args.command {
+        let url = match url {
+            Some(url) => url.clone(),
+            None => default_admin_url()?,
+        };
+        let body = send_admin_command(&url, command).await?;
+        if !body.is_empty() {
+            println!("{}", body);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Genesis { output, timestamp }) = args.command {
+        let config = IrysNodeConfig {
+            mining_signer: IrysSigner::mainnet_from_slice(&decode_hex(CONFIG.mining_key)?),
+            ..IrysNodeConfig::mainnet()
+        };
+        let timestamp = timestamp.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis()
+        });
+        let genesis = generate_genesis(&config, &storage_config_from_config(&config), timestamp)?;
+        genesis.write(&output)?;
+        info!(
+            "Wrote genesis {} to {:?}",
+            &genesis.header.block_hash, &output
+        );
+        return Ok(());
+    }
+
+    let genesis = args.genesis.map(IrysGenesis::load).transpose()?;
+    let handle = start(genesis).await?;
     handle.actor_addresses.start_mining()?;
-    std::thread::park();
+    handle.stopped().await;
 
     Ok(())
 }
@@ -0,0 +1,220 @@
+use base58::ToBase58;
+use eyre::bail;
+use irys_database::{block_header_by_hash, BlockIndex, BlockIndexItem, Initialized};
+use irys_storage::{ii, ChunkType, StorageModule};
+use irys_types::{app_state::DatabaseProvider, IrysBlockHeader};
+use reth_db::Database as _;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Rolls the block index back to the latest block that survived the last run,
+/// i.e. whose header is stored and whose EVM block reth has. Reth persists its
+/// blocks lazily, so after a crash it can be behind the block index.
+///
+/// Only the block index is rolled back, the rest of the node's state follows it:
+/// - the epoch service replays the epoch blocks of the block index at startup
+/// - [`reset_chunks_past_ledger_end`] resets the chunks the storage modules
+///   stored for the dropped blocks
+/// - tx inclusions recorded for the dropped blocks are left in place, readers
+///   check the blocks are still in the block index
+///
+/// Returns the latest block left in the index, `None` if the index is empty.
+pub fn reconcile_block_index(
+    block_index: &mut BlockIndex<Initialized>,
+    db: &DatabaseProvider,
+    has_evm_block: impl Fn(&IrysBlockHeader) -> eyre::Result<bool>,
+) -> eyre::Result<Option<IrysBlockHeader>> {
+    let num_blocks = block_index.num_blocks();
+    let tx = db.tx()?;
+    for height in (0..num_blocks).rev() {
+        let item = block_index
+            .get_item(height as usize)
+            .expect("height is below the number of blocks");
+        match block_header_by_hash(&tx, &item.block_hash)? {
+            Some(header) if has_evm_block(&header)? => {
+                if height + 1 < num_blocks {
+                    warn!(
+                        "Rolling the block index back from height {} to {}",
+                        num_blocks - 1,
+                        height
+                    );
+                    block_index.truncate(height + 1)?;
+                }
+                return Ok(Some(header));
+            }
+            Some(_) => warn!(
+                "Reth is missing the EVM block of block {} at height {}",
+                item.block_hash.0.to_base58(),
+                height
+            ),
+            None => warn!(
+                "Missing the header of block {} at height {}",
+                item.block_hash.0.to_base58(),
+                height
+            ),
+        }
+    }
+
+    if num_blocks > 0 {
+        bail!("The genesis block can't be recovered, the node's data doesn't match reth's");
+    }
+    Ok(None)
+}
+
+/// Marks the data chunks the storage modules hold past the end of their ledger as
+/// uninitialized, so they get repacked. Only blocks in the block index migrate
+/// chunks, so these were stored for blocks the block index was rolled back from.
+///
+/// Returns the number of chunks reset.
+pub fn reset_chunks_past_ledger_end(
+    storage_modules: &[Arc<StorageModule>],
+    latest_item: Option<&BlockIndexItem>,
+) -> eyre::Result<u64> {
+    let mut reset = 0;
+    for sm in storage_modules {
+        let Some(ledger_id) = sm.partition_assignment.and_then(|pa| pa.ledger_id) else {
+            continue;
+        };
+        let ledger_end = latest_item
+            .and_then(|item| item.ledgers.get(ledger_id as usize))
+            .map_or(0, |ledger| ledger.max_chunk_offset);
+        let local_end = ledger_end.saturating_sub(sm.get_storage_module_range()?.start());
+        let Ok(local_end) = u32::try_from(local_end) else {
+            continue;
+        };
+
+        for interval in sm.get_intervals(ChunkType::Data) {
+            if interval.end() < local_end {
+                continue;
+            }
+            let past_end = ii(interval.start().max(local_end), interval.end());
+            warn!(
+                "Resetting chunks {}..={} of storage module {}, past the end of ledger {}",
+                past_end.start(),
+                past_end.end(),
+                sm.id,
+                ledger_id
+            );
+            sm.mark_uninitialized(past_end)?;
+            reset += (past_end.end() - past_end.start() + 1) as u64;
+        }
+    }
+    Ok(reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use irys_config::IrysNodeConfig;
+    use irys_database::{
+        insert_block_header, open_or_create_db, tables::IrysTables, Ledger, LedgerIndexItem,
+    };
+    use irys_storage::{initialize_storage_files, StorageModuleInfo};
+    use irys_types::{partition::PartitionAssignment, StorageConfig, H256};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn rolls_back_to_the_latest_recoverable_block() -> eyre::Result<()> {
+        let dir = tempdir()?;
+        let db = DatabaseProvider(Arc::new(open_or_create_db(
+            dir.path().join("db"),
+            IrysTables::ALL,
+            None,
+        )?));
+        let config = Arc::new(IrysNodeConfig {
+            base_directory: dir.path().to_path_buf(),
+            ..IrysNodeConfig::default()
+        });
+        let mut block_index = BlockIndex::default().init(config.clone()).await?;
+
+        // reth has the EVM blocks of heights 0 & 1, the header of height 3 was lost
+        let headers: Vec<IrysBlockHeader> = (0..4)
+            .map(|height| IrysBlockHeader {
+                height,
+                block_hash: H256::random(),
+                ..IrysBlockHeader::new()
+            })
+            .collect();
+        db.update_eyre(|tx| {
+            headers[..3]
+                .iter()
+                .try_for_each(|header| insert_block_header(tx, header))
+        })?;
+        for header in &headers {
+            block_index.push_item(&BlockIndexItem {
+                block_hash: header.block_hash,
+                num_ledgers: 0,
+                ledgers: vec![],
+            });
+        }
+
+        let latest = reconcile_block_index(&mut block_index, &db, |header| Ok(header.height < 2))?;
+        assert_eq!(latest, Some(headers[1].clone()));
+        assert_eq!(block_index.num_blocks(), 2);
+        // the rollback is persisted
+        let reloaded = BlockIndex::default().init(config).await?;
+        assert_eq!(reloaded.num_blocks(), 2);
+
+        assert!(reconcile_block_index(&mut block_index, &db, |_| Ok(false)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn resets_chunks_past_the_ledger_end() -> eyre::Result<()> {
+        let dir = tempdir()?;
+        let base_path = dir.path().to_path_buf();
+        // the second slot of the Submit ledger, i.e. ledger offsets 10..20
+        let info = StorageModuleInfo {
+            id: 0,
+            partition_assignment: Some(PartitionAssignment {
+                ledger_id: Some(Ledger::Submit as u32),
+                slot_index: Some(1),
+                ..PartitionAssignment::default()
+            }),
+            submodules: vec![(ii(0, 9), "hdd0".into())],
+        };
+        initialize_storage_files(&base_path, &vec![info.clone()], &vec![])?;
+        let storage_config = StorageConfig {
+            chunk_size: 32,
+            num_chunks_in_partition: 10,
+            ..StorageConfig::default()
+        };
+        let sm = Arc::new(StorageModule::new(&base_path, &info, storage_config)?);
+        for offset in 0..8 {
+            sm.write_chunk(offset, vec![0xff; 32], ChunkType::Data);
+        }
+        sm.flush_pending_chunks()?;
+
+        let latest_item = |submit_end| BlockIndexItem {
+            block_hash: H256::random(),
+            num_ledgers: 2,
+            ledgers: [0, submit_end]
+                .into_iter()
+                .map(|max_chunk_offset| LedgerIndexItem {
+                    max_chunk_offset,
+                    ..LedgerIndexItem::default()
+                })
+                .collect(),
+        };
+        let storage_modules = [sm.clone()];
+
+        // the Submit ledger ends at offset 15, the partition's chunk 5
+        let reset = reset_chunks_past_ledger_end(&storage_modules, Some(&latest_item(15)))?;
+        assert_eq!(reset, 3);
+        assert_eq!(sm.get_intervals(ChunkType::Data), [ii(0, 4)]);
+        assert_eq!(sm.get_intervals(ChunkType::Uninitialized), [ii(5, 9)]);
+
+        // nothing is past the end of a ledger covering the partition
+        assert_eq!(
+            reset_chunks_past_ledger_end(&storage_modules, Some(&latest_item(20)))?,
+            0
+        );
+        // an empty block index has no data
+        assert_eq!(reset_chunks_past_ledger_end(&storage_modules, None)?, 5);
+        assert!(sm.get_intervals(ChunkType::Data).is_empty());
+        Ok(())
+    }
+}
@@ -1,35 +1,44 @@
 use actix::Addr;
 use irys_actors::{
     broadcast_mining_service::{BroadcastMiningSeed, BroadcastMiningService},
-    vdf_service::{VdfSeed, VdfService},
+    vdf_service::{VdfSeed, VdfService, VdfStepsReadGuard},
 };
 use irys_types::{
     block_production::Seed,
     vdf_config::{AtomicVdfStepNumber, VDFStepsConfig},
     H256List, H256, U256,
 };
-use irys_vdf::{apply_reset_seed, step_number_to_salt_number, vdf_sha};
-use sha2::{Digest, Sha256};
+use irys_vdf::{
+    apply_reset_seed, step_number_to_salt_number, vdf_sha_with_backend,
+    vdf_steps_fast_forward_is_valid, VdfResetSchedule, VdfStepsFastForward,
+};
+use nodit::interval::ii;
+use reth_metrics::metrics::histogram;
 use std::sync::mpsc::Receiver;
 use std::time::Instant;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 pub fn run_vdf(
     config: VDFStepsConfig,
     global_step_number: u64,
     seed: H256,
-    initial_reset_seed: H256,
-    new_seed_listener: Receiver<H256>,
+    initial_vdf_difficulty: u64,
+    initial_reset_schedule: VdfResetSchedule,
+    reset_schedule_listener: Receiver<VdfResetSchedule>,
+    fast_forward_listener: Receiver<VdfStepsFastForward>,
     shutdown_listener: Receiver<()>,
     broadcast_mining_service: Addr<BroadcastMiningService>,
     vdf_service: Addr<VdfService>,
+    vdf_steps_guard: VdfStepsReadGuard,
     atomic_vdf_global_step: AtomicVdfStepNumber,
 ) {
-    let mut hasher = Sha256::new();
     let mut hash: H256 = seed;
+    // output of the latest step, before any reset seed is applied to it
+    let mut last_output: H256 = seed;
     let mut checkpoints: Vec<H256> = vec![H256::default(); config.num_checkpoints_in_vdf_step];
     let mut global_step_number = global_step_number;
-    let mut reset_seed = initial_reset_seed;
+    let mut vdf_difficulty = initial_vdf_difficulty;
+    let mut reset_schedule = initial_reset_schedule;
     info!(
         "VDF thread started at global_step_number: {}",
         global_step_number
@@ -37,24 +46,69 @@ pub fn run_vdf(
     let nonce_limiter_reset_frequency = config.vdf_reset_frequency as u64;
 
     loop {
+        // Jump ahead if we've been handed verified steps beyond our current head
+        while let Ok(fast_forward) = fast_forward_listener.try_recv() {
+            let Some(checkpoints) = verify_fast_forward(
+                &config,
+                &fast_forward,
+                global_step_number,
+                last_output,
+                &vdf_steps_guard,
+            ) else {
+                continue;
+            };
+
+            let new_steps = (fast_forward.global_step_number - global_step_number) as usize;
+            let skip = fast_forward.steps.len() - new_steps;
+            for step in fast_forward.steps.iter().skip(skip) {
+                vdf_service.do_send(VdfSeed(Seed(*step)));
+            }
+
+            global_step_number = fast_forward.global_step_number;
+            atomic_vdf_global_step.store(global_step_number, std::sync::atomic::Ordering::Relaxed);
+            last_output = *fast_forward.steps.0.last().unwrap();
+            hash = last_output;
+            vdf_difficulty = fast_forward.step_vdf_difficulty(&config, global_step_number);
+
+            info!(
+                "VDF fast forwarded {} steps to step number {}",
+                new_steps, global_step_number
+            );
+            broadcast_mining_service.do_send(BroadcastMiningSeed {
+                seed: Seed(hash),
+                checkpoints,
+                global_step: global_step_number,
+            });
+
+            while let Ok(schedule) = reset_schedule_listener.try_recv() {
+                debug!("New reset schedule {:?}", schedule);
+                reset_schedule = schedule;
+            }
+            if global_step_number % nonce_limiter_reset_frequency == 0 {
+                hash = apply_reset_seed(hash, reset_schedule.next_seed);
+            }
+        }
+
         let now = Instant::now();
 
         let mut salt = U256::from(step_number_to_salt_number(&config, global_step_number));
 
-        vdf_sha(
-            &mut hasher,
+        vdf_sha_with_backend(
+            config.vdf_backend,
             &mut salt,
             &mut hash,
             config.num_checkpoints_in_vdf_step,
-            config.vdf_difficulty,
+            vdf_difficulty,
             &mut checkpoints, // TODO: need to send also checkpoints to block producer for last_step_checkpoints ?
         );
 
         global_step_number += 1;
         atomic_vdf_global_step.store(global_step_number, std::sync::atomic::Ordering::Relaxed);
+        last_output = hash;
 
         let elapsed = now.elapsed();
         debug!("Vdf step duration: {:.2?}", elapsed);
+        histogram!("irys_vdf_step_duration_seconds").record(elapsed.as_secs_f64());
 
         info!(
             "Seed created {} step number {}",
@@ -68,22 +122,88 @@ pub fn run_vdf(
             global_step: global_step_number,
         });
 
+        // Pick up the reset seed and difficulty of the most recently confirmed
+        // block before they're needed at the reset line
+        while let Ok(schedule) = reset_schedule_listener.try_recv() {
+            debug!("New reset schedule {:?}", schedule);
+            reset_schedule = schedule;
+        }
+
         if global_step_number % nonce_limiter_reset_frequency == 0 {
             info!(
                 "Reset seed {:?} applied to step {}",
-                global_step_number, reset_seed
+                reset_schedule.next_seed, global_step_number
             );
-            hash = apply_reset_seed(hash, reset_seed);
+            hash = apply_reset_seed(hash, reset_schedule.next_seed);
+            if let Some(next_vdf_difficulty) = reset_schedule.next_vdf_difficulty {
+                if next_vdf_difficulty != vdf_difficulty {
+                    info!(
+                        "VDF difficulty changed from {} to {} at step {}",
+                        vdf_difficulty, next_vdf_difficulty, global_step_number
+                    );
+                }
+                vdf_difficulty = next_vdf_difficulty;
+            }
         }
 
         if shutdown_listener.try_recv().is_ok() {
             // Shutdown signal received
             break;
         };
+    }
+}
+
+/// Checks a fast forward range reaches past our head, connects to a step we
+/// already know, and recomputes it in parallel. Returns the checkpoints of the
+/// last step in the range when it can be applied, `None` otherwise.
+fn verify_fast_forward(
+    config: &VDFStepsConfig,
+    fast_forward: &VdfStepsFastForward,
+    global_step_number: u64,
+    last_output: H256,
+    vdf_steps_guard: &VdfStepsReadGuard,
+) -> Option<H256List> {
+    if fast_forward.global_step_number <= global_step_number {
+        debug!(
+            "Ignoring VDF fast forward to step {}, already at step {}",
+            fast_forward.global_step_number, global_step_number
+        );
+        return None;
+    }
+
+    let prev_step_number = fast_forward.prev_step_number();
+    if prev_step_number > global_step_number {
+        warn!(
+            "Unable to fast forward VDF: steps start at {} but we are at step {}",
+            prev_step_number + 1,
+            global_step_number
+        );
+        return None;
+    }
+
+    // The range must be anchored to a step we computed or already accepted
+    let anchor = if prev_step_number == global_step_number {
+        Some(last_output)
+    } else {
+        vdf_steps_guard
+            .read()
+            .get_steps(ii(prev_step_number, prev_step_number))
+            .ok()
+            .map(|steps| steps[0])
+    };
+    if anchor != Some(fast_forward.prev_output) {
+        warn!(
+            "Unable to fast forward VDF: prev_output {} does not match local step {}",
+            fast_forward.prev_output, prev_step_number
+        );
+        return None;
+    }
 
-        if let Ok(h) = new_seed_listener.try_recv() {
-            debug!("New Send Seed {}", h); // TODO: wire new seed injections from chain accepted blocks message BlockConfirmedMessage
-            reset_seed = h;
+    match vdf_steps_fast_forward_is_valid(fast_forward, config) {
+        Ok(checkpoints) => Some(checkpoints),
+        Err(e) => {
+            warn!("Rejecting VDF fast forward: {}", e);
+            None
         }
     }
 }
@@ -94,8 +214,9 @@ mod tests {
     use actix::*;
     use irys_actors::vdf_service::{GetVdfStateMessage, VdfStepsReadGuard};
     use irys_types::*;
-    use irys_vdf::{vdf_sha_verification, vdf_steps_are_valid};
+    use irys_vdf::{vdf_sha, vdf_sha_verification, vdf_steps_are_valid};
     use nodit::interval::ii;
+    use sha2::{Digest, Sha256};
     use std::{
         sync::{atomic::AtomicU64, mpsc, Arc},
         time::Duration,
@@ -148,6 +269,43 @@ mod tests {
         assert_eq!(checkpoints, checkpoints2, "Should be equal");
     }
 
+    #[test]
+    fn test_vdf_backends_agree() {
+        let config = VDFStepsConfig {
+            vdf_difficulty: 100,
+            ..VDFStepsConfig::default()
+        };
+        let seed = H256::random();
+
+        for step_number in [0, 1, 2, config.vdf_reset_frequency as u64 + 1] {
+            for num_iterations in [1, 2, config.vdf_difficulty] {
+                let results: Vec<(H256, U256, Vec<H256>)> =
+                    [VdfBackend::Sha2, VdfBackend::OpenSsl, VdfBackend::Native]
+                        .into_iter()
+                        .map(|backend| {
+                            let mut salt =
+                                U256::from(step_number_to_salt_number(&config, step_number));
+                            let mut hash = seed;
+                            let mut checkpoints =
+                                vec![H256::default(); config.num_checkpoints_in_vdf_step];
+                            vdf_sha_with_backend(
+                                backend,
+                                &mut salt,
+                                &mut hash,
+                                config.num_checkpoints_in_vdf_step,
+                                num_iterations,
+                                &mut checkpoints,
+                            );
+                            (hash, salt, checkpoints)
+                        })
+                        .collect();
+
+                assert_eq!(results[0], results[1], "sha2 and openssl differ");
+                assert_eq!(results[0], results[2], "sha2 and native differ");
+            }
+        }
+    }
+
     #[actix_rt::test]
     async fn test_vdf_service() {
         let seed = H256::random();
@@ -166,21 +324,27 @@ mod tests {
         let vdf_steps: VdfStepsReadGuard = vdf_service.send(GetVdfStateMessage).await.unwrap();
 
         let vdf_config2 = vdf_config.clone();
-        let (_new_seed_tx, new_seed_rx) = mpsc::channel::<H256>();
+        let vdf_difficulty = vdf_config.vdf_difficulty;
+        let (_reset_schedule_tx, reset_schedule_rx) = mpsc::channel::<VdfResetSchedule>();
+        let (_fast_forward_tx, fast_forward_rx) = mpsc::channel::<VdfStepsFastForward>();
         let (shutdown_tx, shutdown_rx) = mpsc::channel();
 
         let atomic_global_step_number = Arc::new(AtomicU64::new(0));
+        let vdf_steps_guard = vdf_steps.clone();
 
         let vdf_thread_handler = std::thread::spawn(move || {
             run_vdf(
                 vdf_config2,
                 0,
                 seed,
-                reset_seed,
-                new_seed_rx,
+                vdf_difficulty,
+                reset_schedule(reset_seed),
+                reset_schedule_rx,
+                fast_forward_rx,
                 shutdown_rx,
                 broadcast_mining_service,
                 vdf_service,
+                vdf_steps_guard,
                 atomic_global_step_number,
             )
         });
@@ -238,4 +402,128 @@ mod tests {
         // Wait for vdf thread to finish
         vdf_thread_handler.join().unwrap();
     }
+
+    /// A reset schedule that keeps the configured difficulty
+    const fn reset_schedule(next_seed: H256) -> VdfResetSchedule {
+        VdfResetSchedule {
+            next_seed,
+            next_vdf_difficulty: None,
+        }
+    }
+
+    /// Sequentially computes `count` steps following `seed` at `start_step`,
+    /// the same way `run_vdf` does, returning the step outputs and the
+    /// checkpoints of the last step.
+    fn compute_steps(
+        config: &VDFStepsConfig,
+        seed: H256,
+        reset_seed: H256,
+        start_step: u64,
+        count: usize,
+    ) -> (H256List, H256List) {
+        let mut hasher = Sha256::new();
+        let mut hash = seed;
+        let mut checkpoints = vec![H256::default(); config.num_checkpoints_in_vdf_step];
+        let mut steps = H256List::new();
+        for step_number in start_step..start_step + count as u64 {
+            if step_number > 0 && step_number % config.vdf_reset_frequency as u64 == 0 {
+                hash = apply_reset_seed(hash, reset_seed);
+            }
+            let mut salt = U256::from(step_number_to_salt_number(config, step_number));
+            vdf_sha(
+                &mut hasher,
+                &mut salt,
+                &mut hash,
+                config.num_checkpoints_in_vdf_step,
+                config.vdf_difficulty,
+                &mut checkpoints,
+            );
+            steps.push(hash);
+        }
+        (steps, H256List(checkpoints))
+    }
+
+    #[actix_rt::test]
+    async fn test_vdf_fast_forward() {
+        let seed = H256::random();
+        let reset_seed = H256::random();
+
+        let vdf_config = VDFStepsConfig {
+            vdf_reset_frequency: 2, // cross a few reset points
+            vdf_difficulty: 1,
+            ..VDFStepsConfig::default()
+        };
+
+        init_tracing();
+
+        let (steps, last_step_checkpoints) = compute_steps(&vdf_config, seed, reset_seed, 0, 6);
+        let fast_forward = VdfStepsFastForward {
+            global_step_number: 6,
+            prev_output: seed,
+            steps: steps.clone(),
+            reset_seed,
+            vdf_difficulty: None,
+            next_vdf_difficulty: None,
+        };
+
+        let checkpoints = vdf_steps_fast_forward_is_valid(&fast_forward, &vdf_config).unwrap();
+        assert_eq!(checkpoints, last_step_checkpoints);
+
+        // Any tampered step invalidates the range
+        let mut tampered = fast_forward.clone();
+        tampered.steps.0[3] = H256::random();
+        assert!(vdf_steps_fast_forward_is_valid(&tampered, &vdf_config).is_err());
+
+        // So does the wrong reset seed
+        let mut tampered = fast_forward.clone();
+        tampered.reset_seed = H256::random();
+        assert!(vdf_steps_fast_forward_is_valid(&tampered, &vdf_config).is_err());
+
+        // The VDF thread picks up the range before computing its first step
+        let broadcast_mining_service = BroadcastMiningService::from_registry();
+        let vdf_service = VdfService::from_registry();
+        let vdf_steps: VdfStepsReadGuard = vdf_service.send(GetVdfStateMessage).await.unwrap();
+
+        let (_reset_schedule_tx, reset_schedule_rx) = mpsc::channel::<VdfResetSchedule>();
+        let (fast_forward_tx, fast_forward_rx) = mpsc::channel::<VdfStepsFastForward>();
+        let (shutdown_tx, shutdown_rx) = mpsc::channel();
+        fast_forward_tx.send(fast_forward).unwrap();
+
+        let atomic_global_step_number = Arc::new(AtomicU64::new(0));
+        let vdf_steps_guard = vdf_steps.clone();
+        let vdf_config2 = vdf_config.clone();
+        let vdf_difficulty = vdf_config.vdf_difficulty;
+
+        let vdf_thread_handler = std::thread::spawn(move || {
+            run_vdf(
+                vdf_config2,
+                0,
+                seed,
+                vdf_difficulty,
+                reset_schedule(reset_seed),
+                reset_schedule_rx,
+                fast_forward_rx,
+                shutdown_rx,
+                broadcast_mining_service,
+                vdf_service,
+                vdf_steps_guard,
+                atomic_global_step_number,
+            )
+        });
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(
+            vdf_steps.read().global_step > 6,
+            "Should have moved past step 6"
+        );
+        assert_eq!(vdf_steps.read().get_steps(ii(1, 6)).unwrap(), steps);
+
+        // Continued local computation extends the fast forwarded chain
+        let (next_steps, _) = compute_steps(&vdf_config, steps[5], reset_seed, 6, 1);
+        assert_eq!(vdf_steps.read().get_steps(ii(7, 7)).unwrap(), next_steps);
+
+        shutdown_tx.send(()).unwrap();
+        vdf_thread_handler.join().unwrap();
+    }
 }
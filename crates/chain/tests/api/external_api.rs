@@ -138,6 +138,7 @@ async fn external_api() -> eyre::Result<()> {
         arc_config.mining_signer.clone(),
         storage_config.clone(),
         storage_modules.clone(),
+        None,
     );
     SystemRegistry::set(mempool_service.start());
     let mempool_addr = MempoolService::from_registry();
@@ -160,6 +161,10 @@ async fn external_api() -> eyre::Result<()> {
     let app_state = ApiState {
         reth_provider: None,
         block_index: None,
+        partition_assignments: None,
+        ledgers: None,
+        vdf_steps: None,
+        actor_addresses: None,
         block_tree: None,
         db: arc_db.clone(),
         mempool: mempool_addr.clone(),
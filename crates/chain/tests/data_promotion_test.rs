@@ -82,6 +82,10 @@ async fn data_promotion_test() {
     let app_state = ApiState {
         reth_provider: None,
         block_index: None,
+        partition_assignments: None,
+        ledgers: None,
+        vdf_steps: None,
+        actor_addresses: None,
         block_tree: None,
         db: node_context.db.clone(),
         mempool: node_context.actor_addresses.mempool,
@@ -0,0 +1,107 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use eyre::{ensure, Context as _};
+use irys_primitives::{Address, Commitments, Genesis};
+use irys_types::{partition::PartitionAssignment, IrysBlockHeader, H256};
+use reth_chainspec::{ChainSpec, ChainSpecBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::chain::chain::IRYS_MAINNET;
+
+/// The genesis of an Irys network. Generated once for a network launch and
+/// distributed to its nodes as `genesis.json`, so all of them start from the
+/// same genesis block and agree on its hash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrysGenesis {
+    /// The Irys genesis block header, `block_hash` is the hash of its contents
+    pub header: IrysBlockHeader,
+    /// The EVM genesis
+    pub evm: Genesis,
+    /// The initial stake & pledge commitments of each account, these have to match
+    /// the commitments of the accounts in the EVM alloc
+    pub commitments: BTreeMap<Address, Commitments>,
+    /// The partition assignments made by the genesis epoch, sorted by partition hash
+    pub partition_assignments: Vec<PartitionAssignment>,
+}
+
+impl IrysGenesis {
+    /// Reads and validates a genesis file
+    pub fn load(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Unable to read genesis file {:?}", path))?;
+        let genesis: Self = serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("Invalid genesis file {:?}", path))?;
+        genesis.chain_spec()?;
+        Ok(genesis)
+    }
+
+    /// Writes the genesis file to `path`
+    pub fn write(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The hash of a genesis header's contents, used as its `block_hash`
+    pub fn header_hash(header: &IrysBlockHeader) -> eyre::Result<H256> {
+        Ok(H256(header.signature_hash()?))
+    }
+
+    /// Builds the EVM chain spec, checking the genesis header is consistent with
+    /// itself and with the EVM genesis
+    pub fn chain_spec(&self) -> eyre::Result<ChainSpec> {
+        ensure!(self.header.height == 0, "Genesis header height isn't 0");
+        ensure!(
+            self.header.block_hash == Self::header_hash(&self.header)?,
+            "Genesis block hash doesn't match the genesis header"
+        );
+        let chain_spec = ChainSpecBuilder {
+            chain: Some(IRYS_MAINNET.chain),
+            genesis: Some(self.evm.clone()),
+            hardforks: IRYS_MAINNET.hardforks.clone(),
+        }
+        .build();
+        ensure!(
+            self.header.evm_block_hash == chain_spec.genesis_hash(),
+            "Genesis EVM block hash doesn't match the EVM genesis"
+        );
+        ensure!(
+            self.commitments == Self::alloc_commitments(&self.evm),
+            "Genesis commitments don't match the EVM alloc"
+        );
+        Ok(chain_spec)
+    }
+
+    /// The commitments of the accounts in the EVM alloc, accounts without any are skipped
+    pub fn alloc_commitments(evm: &Genesis) -> BTreeMap<Address, Commitments> {
+        evm.alloc
+            .iter()
+            .filter_map(|(address, account)| {
+                account
+                    .commitments
+                    .clone()
+                    .filter(|commitments| !commitments.0.is_empty())
+                    .map(|commitments| (*address, commitments))
+            })
+            .collect()
+    }
+
+    /// Checks partition assignments derived from the genesis header match the
+    /// ones recorded in the genesis file, including the miner each partition is
+    /// assigned to. Until pledging exists the epoch service assigns every partition
+    /// to the local miner, so only the genesis miner can start from a genesis file.
+    pub fn verify_partition_assignments(
+        &self,
+        mut partition_assignments: Vec<PartitionAssignment>,
+    ) -> eyre::Result<()> {
+        let mut expected = self.partition_assignments.clone();
+        partition_assignments.sort_unstable_by_key(|assignment| assignment.partition_hash);
+        expected.sort_unstable_by_key(|assignment| assignment.partition_hash);
+        ensure!(
+            partition_assignments == expected,
+            "Genesis partition assignments don't match the genesis file"
+        );
+        Ok(())
+    }
+}
@@ -6,12 +6,14 @@ use std::{
 };
 
 use chain::chainspec::IrysChainSpecBuilder;
+use genesis::IrysGenesis;
 use irys_primitives::GenesisAccount;
 use irys_types::{irys::IrysSigner, Address, CONFIG};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
 pub mod chain;
+pub mod genesis;
 
 // TODO: convert this into a set of clap args
 
@@ -28,6 +30,9 @@ pub struct IrysNodeConfig {
     pub base_directory: PathBuf,
     /// `ChainSpec` builder - used to generate `ChainSpec`, which defines most of the chain-related parameters
     pub chainspec_builder: IrysChainSpecBuilder,
+    /// The network's genesis, loaded from its `genesis.json`. When unset the genesis is built
+    /// from `chainspec_builder` at startup (dev & testing)
+    pub genesis: Option<IrysGenesis>,
 }
 
 /// "sane" default configuration
@@ -48,6 +53,7 @@ impl Default for IrysNodeConfig {
             mining_signer: IrysSigner::random_signer(),
             instance_number: 1,
             base_directory: base_dir,
+            genesis: None,
         }
     }
 }
@@ -68,6 +74,7 @@ impl IrysNodeConfig {
                 .expect("Unable to determine working dir, aborting")
                 .join(".irys"),
             chainspec_builder: IrysChainSpecBuilder::mainnet(),
+            genesis: None,
         }
     }
 
@@ -10,6 +10,7 @@ use std::fs::{self, remove_file, File, OpenOptions};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::ops::{Index, IndexMut};
 use std::sync::Arc;
+use tracing::warn;
 
 /// This struct represents the `Uninitialized` `block_index` type state.
 #[derive(Debug)]
@@ -131,6 +132,15 @@ impl BlockIndex<Initialized> {
         self.items = items_vec.into();
     }
 
+    /// Drops every item from height `num_blocks` on, in memory and on disk
+    pub fn truncate(&mut self, num_blocks: u64) -> eyre::Result<()> {
+        let num_blocks = (num_blocks as usize).min(self.items.len());
+        let items = &self.items[..num_blocks];
+        save_block_index(items, self.config.as_ref().unwrap())?;
+        self.items = items.into();
+        Ok(())
+    }
+
     /// For a given byte offset in a ledger, what block was responsible for adding
     /// that byte to the data ledger?
     pub fn get_block_bounds(&self, ledger: Ledger, chunk_offset: u64) -> BlockBounds {
@@ -300,18 +310,21 @@ impl BlockIndexItem {
     }
 }
 
-#[allow(dead_code)]
+/// Replaces the index file, writing to a temporary file first so a crash can't
+/// leave it half written
 fn save_block_index(
     block_index_items: &[BlockIndexItem],
     config: &IrysNodeConfig,
 ) -> io::Result<()> {
     let path = config.block_index_dir().join(FILE_NAME);
-    let mut file = File::create(path)?;
+    let tmp_path = path.with_extension("dat.tmp");
+    let mut file = File::create(&tmp_path)?;
     for item in block_index_items {
         let bytes = item.to_bytes();
         file.write_all(&bytes)?;
     }
-    Ok(())
+    file.sync_all()?;
+    fs::rename(tmp_path, path)
 }
 
 #[allow(dead_code)]
@@ -377,6 +390,16 @@ fn load_index_from_file(config: &IrysNodeConfig) -> io::Result<Vec<BlockIndexIte
         offset += item_size;
     }
 
+    // Drop a partially written item, left by a crash mid append, so the next
+    // append doesn't land after it
+    if offset < buffer.len() {
+        warn!(
+            "Dropping {} trailing bytes of a partially written block index item",
+            buffer.len() - offset
+        );
+        file.set_len(offset as u64)?;
+    }
+
     Ok(block_index_items)
 }
 
@@ -393,6 +416,8 @@ mod tests {
     use assert_matches::assert_matches;
     use irys_config::IrysNodeConfig;
     use irys_types::H256;
+    use std::fs::OpenOptions;
+    use std::io::Write;
 
     #[tokio::test]
     async fn read_and_write_block_index() -> eyre::Result<()> {
@@ -452,7 +477,7 @@ mod tests {
 
         // Load the items from disk
         let block_index = BlockIndex::new();
-        let block_index = block_index.init(arc_config).await.unwrap();
+        let block_index = block_index.init(arc_config.clone()).await.unwrap();
 
         println!("{:?}", block_index.items);
 
@@ -488,6 +513,18 @@ mod tests {
         let item = block_index.get_item(2).unwrap();
         assert_eq!(*item, block_items[2]);
 
+        // A partially appended item is dropped on load
+        let path = arc_config.block_index_dir().join(super::FILE_NAME);
+        let mut file = OpenOptions::new().append(true).open(&path)?;
+        file.write_all(&block_items[0].to_bytes()[..40])?;
+        let mut block_index = BlockIndex::new().init(arc_config.clone()).await?;
+        assert_eq!(block_index.num_blocks(), 3);
+
+        block_index.truncate(2)?;
+        assert_eq!(block_index.get_latest_item(), Some(&block_items[1]));
+        let block_index = BlockIndex::new().init(arc_config).await?;
+        assert_eq!(block_index.num_blocks(), 2);
+
         Ok(())
     }
 }
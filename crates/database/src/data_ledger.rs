@@ -71,6 +71,14 @@ impl TermLedger {
         &self.slots
     }
 
+    /// Returns the epoch height at which a slot expires if no more data is
+    /// added to it
+    pub fn slot_expiry_height(&self, slot_index: usize) -> Option<u64> {
+        self.slots
+            .get(slot_index)
+            .map(|slot| slot.last_height + self.epoch_length * CONFIG.num_blocks_in_epoch)
+    }
+
     /// Returns indices of newly expired slots
     pub fn expire_old_slots(&mut self, epoch_height: u64) -> Vec<usize> {
         let mut expired_indices = Vec::new();
@@ -309,6 +317,15 @@ impl Ledgers {
         }
     }
 
+    /// Returns the epoch height at which a term ledger slot expires if no more
+    /// data is added to it, `None` for the permanent ledger
+    pub fn get_slot_expiry_height(&self, ledger: Ledger, slot_index: usize) -> Option<u64> {
+        match ledger {
+            Ledger::Publish => None,
+            Ledger::Submit => self.term[0].slot_expiry_height(slot_index),
+        }
+    }
+
     /// Get the slot needs for a given ledger.
     pub fn get_slot_needs(&self, ledger: Ledger) -> Vec<(usize, usize)> {
         match ledger {
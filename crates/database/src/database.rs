@@ -3,10 +3,12 @@ use std::path::Path;
 use crate::db_cache::{
     CachedChunk, CachedChunkIndexEntry, CachedChunkIndexMetadata, CachedDataRoot,
 };
+use crate::db_index::TxInclusion;
 use crate::tables::{
     CachedChunks, CachedChunksIndex, CachedDataRoots, IrysBlockHeaders, IrysTxHeaders,
-    PartitionHashes, PartitionHashesByDataRoot,
+    IrysTxInclusions, PartitionHashes, PartitionHashesByDataRoot,
 };
+use crate::Ledger;
 
 use irys_types::partition::PartitionHash;
 use irys_types::{
@@ -76,6 +78,29 @@ pub fn tx_header_by_txid<T: DbTx>(
         .map(IrysTransactionHeader::from))
 }
 
+/// Records that `block_hash` includes `txid` in `ledger`, see [`TxInclusion`]
+pub fn insert_tx_inclusion<T: DbTxMut + DbTx>(
+    tx: &T,
+    txid: IrysTransactionId,
+    ledger: Ledger,
+    block_hash: BlockHash,
+) -> eyre::Result<()> {
+    let mut inclusion = tx.get::<IrysTxInclusions>(txid)?.unwrap_or_default();
+    match ledger {
+        Ledger::Publish => inclusion.publish_block_hash = Some(block_hash),
+        Ledger::Submit => inclusion.submit_block_hash = Some(block_hash),
+    }
+    Ok(tx.put::<IrysTxInclusions>(txid, inclusion)?)
+}
+
+/// Gets the blocks that included a tx, see [`TxInclusion`]
+pub fn tx_inclusion_by_txid<T: DbTx>(
+    tx: &T,
+    txid: &IrysTransactionId,
+) -> eyre::Result<Option<TxInclusion>> {
+    Ok(tx.get::<IrysTxInclusions>(*txid)?)
+}
+
 /// Takes an [`IrysTransactionHeader`] and caches its `data_root` and tx.id in a
 /// cache database table ([`CachedDataRoots`]). Tracks all the tx.ids' that share the same `data_root`.
 pub fn cache_data_root<T: DbTx + DbTxMut>(
@@ -242,12 +267,18 @@ pub fn get_account_balance<T: DbTx>(tx: &T, address: Address) -> eyre::Result<U2
 
 #[cfg(test)]
 mod tests {
-    use irys_types::{IrysBlockHeader, IrysTransactionHeader};
+    use irys_types::{IrysBlockHeader, IrysTransactionHeader, H256};
     use reth_db::Database;
 
-    use crate::{block_header_by_hash, config::get_data_dir, tables::IrysTables};
+    use crate::{
+        block_header_by_hash, config::get_data_dir, db_index::TxInclusion, tables::IrysTables,
+        Ledger,
+    };
 
-    use super::{insert_block_header, insert_tx_header, open_or_create_db, tx_header_by_txid};
+    use super::{
+        insert_block_header, insert_tx_header, insert_tx_inclusion, open_or_create_db,
+        tx_header_by_txid, tx_inclusion_by_txid,
+    };
 
     #[test]
     fn insert_and_get_tests() -> eyre::Result<()> {
@@ -275,6 +306,23 @@ mod tests {
         let result = db.view_eyre(|tx| block_header_by_hash(tx, &block_header.block_hash))?;
         assert_eq!(result, Some(block_header));
 
+        // Record the blocks including the Tx, one ledger at a time
+        let _ = db.update(|tx| {
+            insert_tx_inclusion(tx, tx_header.id, Ledger::Submit, block_header.block_hash)
+        })?;
+        let promoting_block_hash = H256::random();
+        let _ = db.update(|tx| {
+            insert_tx_inclusion(tx, tx_header.id, Ledger::Publish, promoting_block_hash)
+        })?;
+        let result = db.view_eyre(|tx| tx_inclusion_by_txid(tx, &tx_header.id))?;
+        assert_eq!(
+            result,
+            Some(TxInclusion {
+                submit_block_hash: Some(block_header.block_hash),
+                publish_block_hash: Some(promoting_block_hash),
+            })
+        );
+
         Ok(())
     }
 
@@ -1,5 +1,18 @@
+use arbitrary::Arbitrary;
+use irys_types::{Compact, H256};
+use serde::{Deserialize, Serialize};
+
 /// Once data has been well confirmed by being part of a transaction that is
 /// in a block with several confirmations, it can move out of the `db_cache`
 /// and into a `db_index` that is able to store more properties (to support
 /// mining) now that the data is confirmed.
 const _X: u64 = 1;
+
+/// The blocks that included a tx in the Submit & Publish ledgers, recorded as
+/// each block is confirmed. A re-org can leave a hash of an orphaned block
+/// behind, so readers have to check the block is still canonical.
+#[derive(Clone, Debug, Eq, Default, PartialEq, Serialize, Deserialize, Arbitrary, Compact)]
+pub struct TxInclusion {
+    pub submit_block_hash: Option<H256>,
+    pub publish_block_hash: Option<H256>,
+}
@@ -12,6 +12,7 @@ use std::fmt;
 use crate::submodule::tables::RelativeStartOffsets;
 use crate::{
     db_cache::{CachedChunk, CachedChunkIndexEntry, CachedDataRoot},
+    db_index::TxInclusion,
     submodule::tables::{ChunkOffsets, ChunkPathHashes},
 };
 
@@ -84,7 +85,8 @@ impl_compression_for_compact!(
     ChunkOffsets,
     ChunkPathHashes,
     PartitionHashes,
-    RelativeStartOffsets
+    RelativeStartOffsets,
+    TxInclusion
 );
 
 tables! {
@@ -111,6 +113,9 @@ tables! {
     /// Common case is a 1:1, but 1:N is possible
     table PartitionHashesByDataRoot<Key = DataRoot, Value = PartitionHashes>;
 
+    /// Indexes the blocks that included a tx in the Submit & Publish ledgers, by tx id
+    table IrysTxInclusions<Key = H256, Value = TxInclusion>;
+
 
 }
 
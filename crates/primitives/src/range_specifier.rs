@@ -31,6 +31,7 @@ impl TryFrom<u8> for PdAccessListArgsTypeId {
     }
 }
 
+/// A PD storage key in a transaction's access list, declaring data the transaction will read.
 pub enum PdAccessListArg {
     ChunkRead(ChunkRangeSpecifier),
     ByteRead(ByteRangeSpecifier),
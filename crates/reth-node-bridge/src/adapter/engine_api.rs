@@ -1,5 +1,6 @@
 use crate::adapter::traits::PayloadEnvelopeExt;
 use alloy_primitives::B256;
+use alloy_rpc_types::engine::ExecutionPayloadV1Irys;
 use jsonrpsee::{
     core::client::ClientT,
     http_client::{transport::HttpBackend, HttpClient},
@@ -9,7 +10,7 @@ use reth::{
     providers::CanonStateNotificationStream,
     rpc::{
         api::EngineApiClient,
-        types::engine::{ForkchoiceState, PayloadStatusEnum},
+        types::engine::{ForkchoiceState, PayloadStatus, PayloadStatusEnum},
     },
 };
 use reth_payload_builder::PayloadId;
@@ -72,19 +73,30 @@ impl<E: EngineTypes> EngineApiContext<E> {
         let envelope_v3: <E as EngineTypes>::ExecutionPayloadV1Irys = payload.into();
 
         // submit payload to engine api
-        let submission = EngineApiClient::<E>::submit_new_payload_irys(
-            &self.engine_api_client,
-            envelope_v3.execution_payload(),
-            // versioned_hashes,
-            // payload_builder_attributes.parent_beacon_block_root().unwrap(),
-        )
-        .await?;
+        let submission = self
+            .submit_execution_payload(envelope_v3.execution_payload())
+            .await?;
 
         assert_eq!(submission.status, expected_status);
 
         Ok(submission.latest_valid_hash.unwrap_or_default())
     }
 
+    /// Submits an execution payload, e.g. one received alongside a block from
+    /// another node, to the engine api and returns the status reth reports for it
+    pub async fn submit_execution_payload(
+        &self,
+        execution_payload: ExecutionPayloadV1Irys,
+    ) -> eyre::Result<PayloadStatus> {
+        Ok(EngineApiClient::<E>::submit_new_payload_irys(
+            &self.engine_api_client,
+            execution_payload,
+            // versioned_hashes,
+            // payload_builder_attributes.parent_beacon_block_root().unwrap(),
+        )
+        .await?)
+    }
+
     /// Sends forkchoice update to the engine api
     pub async fn update_forkchoice(&self, current_head: B256, new_head: B256) -> eyre::Result<()> {
         EngineApiClient::<E>::fork_choice_updated_v1_irys(
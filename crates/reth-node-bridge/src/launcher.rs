@@ -37,12 +37,14 @@ use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use reth_node_core::irys_ext::IrysExt;
 
+use crate::precompile::prefetch::prefetch_pool_pd_chunks;
+
 use reth_node_builder::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
@@ -63,6 +65,9 @@ pub struct CustomEngineNodeLauncher {
     pub engine_tree_config: TreeConfig,
 
     pub irys_provider: IrysRethProvider,
+
+    /// Set once the [`IrysExt`] is created, for the RPC modules registered before launch
+    pub irys_ext: Arc<OnceLock<IrysExt>>,
 }
 
 impl CustomEngineNodeLauncher {
@@ -72,11 +77,13 @@ impl CustomEngineNodeLauncher {
         data_dir: ChainPath<DataDirPath>,
         engine_tree_config: TreeConfig,
         irys_provider: IrysRethProvider,
+        irys_ext: Arc<OnceLock<IrysExt>>,
     ) -> Self {
         Self {
             ctx: LaunchContext::new(task_executor, data_dir),
             engine_tree_config,
             irys_provider,
+            irys_ext,
         }
     }
 }
@@ -103,6 +110,7 @@ where
             ctx,
             engine_tree_config,
             irys_provider,
+            irys_ext: irys_ext_cell,
         } = self;
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
@@ -129,6 +137,9 @@ where
             reload: Arc::new(RwLock::new(reload_tx)),
             provider: irys_provider,
         };
+        irys_ext_cell
+            .set(irys_ext.clone())
+            .expect("Unable to set IrysExt OnceLock");
 
         // TODO: move tree_config and canon_state_notification_sender
         // initialization to with_blockchain_db once the engine revamp is done
@@ -171,6 +182,12 @@ where
             }, tree_config, canon_state_notification_sender)?
             .with_components(components_builder, on_component_initialized, Some(irys_ext.clone())).await?;
 
+        // prefetch the chunks read by PD transactions as they enter the pool
+        ctx.task_executor().spawn(prefetch_pool_pd_chunks(
+            ctx.components().pool().clone(),
+            irys_ext.provider.clone(),
+        ));
+
         // spawn exexs
         let exex_manager_handle = ExExLauncher::new(
             ctx.head(),
@@ -1,6 +1,7 @@
 pub mod genesis;
 pub mod launcher;
 pub mod node;
+pub mod pool;
 pub mod rpc;
 pub use node::run_node;
 pub mod adapter;
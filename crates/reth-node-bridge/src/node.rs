@@ -1,9 +1,16 @@
 use core::fmt;
-use std::{fs::canonicalize, future::Future, ops::Deref, sync::Arc};
+use std::{
+    fs::canonicalize,
+    future::Future,
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
 
 use clap::{command, Args, Parser};
 use irys_config::IrysNodeConfig;
 use irys_storage::reth_provider::IrysRethProvider;
+use irys_types::CONFIG;
+use jsonrpsee::Methods;
 use reth::{
     chainspec::EthereumChainSpecParser,
     cli::{Cli, Commands},
@@ -36,9 +43,11 @@ use tracing::info;
 
 use crate::{
     launcher::CustomEngineNodeLauncher,
+    pool::{IrysPoolBuilder, IrysTransactionPool},
     precompile::irys_executor::{
         IrysEvmConfig, IrysExecutorBuilder, IrysPayloadBuilder, PrecompileStateProvider,
     },
+    rpc::{AccountStateExt, AccountStateExtApiServer as _, IrysDevApiServer as _, IrysDevExt},
 };
 
 // use crate::node_launcher::CustomNodeLauncher;
@@ -62,14 +71,8 @@ pub type RethNode = NodeAdapter<
             NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>,
             BlockchainProvider2<NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>>,
         >,
-        Pool<
-            TransactionValidationTaskExecutor<
-                EthTransactionValidator<
-                    BlockchainProvider2<NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>>,
-                    EthPooledTransaction,
-                >,
-            >,
-            CoinbaseTipOrdering<EthPooledTransaction>,
+        IrysTransactionPool<
+            BlockchainProvider2<NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>>,
             DiskFileBlobStore,
         >,
         IrysEvmConfig,
@@ -135,6 +138,7 @@ pub async fn run_node<T: HasName + HasTableType>(
     irys_config: Arc<IrysNodeConfig>,
     tables: &[T],
     provider: IrysRethProvider,
+    irys_rpc: Methods,
 ) -> eyre::Result<RethNodeExitHandle> {
     let mut os_args: Vec<String> = std::env::args().collect();
     let bp = os_args.remove(0);
@@ -251,6 +255,8 @@ pub async fn run_node<T: HasName + HasTableType>(
         Arc::new(init_db(db_path.clone(), db.database_args())?.with_metrics_and_tables(tables));
 
     let irys_provider = provider;
+    let irys_ext = Arc::new(OnceLock::new());
+    let irys_ext_rpc = irys_ext.clone();
 
     if with_unused_ports {
         node_config = node_config.with_unused_ports();
@@ -278,24 +284,29 @@ pub async fn run_node<T: HasName + HasTableType>(
                 EthereumNode::components()
                 .executor(IrysExecutorBuilder{ precompile_state_provider: PrecompileStateProvider { provider: irys_provider.clone()}})
                 .payload(IrysPayloadBuilder::default())
+                .pool(IrysPoolBuilder::default())
             )
             // .with_components(EthereumNode::components())
             .with_add_ons(EthereumAddOns::default())
-            // .extend_rpc_modules(move |ctx| {
-            //     let provider = ctx.provider().clone();
-            //     let irys_ext = ctx.node().components.irys_ext.clone();
-            //     let network = ctx.network().clone();
-            //     let ext = AccountStateExt { provider, irys_ext, network };
-            //     let rpc = ext.into_rpc();
-            //     ctx.modules.merge_configured(rpc)?;
-            //     Ok(())
-            // })
+            .extend_rpc_modules(move |ctx| {
+                let provider = ctx.provider().clone();
+                let network = ctx.network().clone();
+                let account_ext = AccountStateExt { provider: provider.clone(), network: network.clone() };
+                ctx.modules.merge_configured(account_ext.into_rpc())?;
+                ctx.modules.merge_configured(irys_rpc)?;
+                if CONFIG.dev_mode {
+                    let dev_ext = IrysDevExt { provider, network, irys_ext: irys_ext_rpc };
+                    ctx.modules.merge_configured(dev_ext.into_rpc())?;
+                }
+                Ok(())
+            })
             .launch_with_fn(|builder| {
                 let launcher = CustomEngineNodeLauncher::new(
                     builder.task_executor().clone(),
                     builder.config().datadir(),
                     engine_tree_config,
-                    irys_provider
+                    irys_provider,
+                    irys_ext
                 );
                 builder.launch_with(launcher)
             })
@@ -0,0 +1,565 @@
+use std::{fmt, marker::PhantomData};
+
+use alloy_primitives::U256;
+use alloy_rpc_types::AccessListItem;
+use eyre::{eyre, OptionExt};
+use irys_database::Ledger;
+use irys_storage::{
+    get_storage_module_at_offset,
+    reth_provider::{IrysRethProvider, IrysRethProviderInner},
+};
+use reth::builder::{components::PoolBuilder, BuilderContext};
+use reth_chainspec::ChainSpec;
+use reth_node_api::{FullNodeTypes, NodeTypes};
+use reth_primitives::SealedBlock;
+use reth_provider::{BlockReaderIdExt, CanonStateSubscriptions, StateProviderFactory};
+use reth_transaction_pool::{
+    blobstore::DiskFileBlobStore,
+    error::{InvalidPoolTransactionError, PoolTransactionError},
+    maintain::{
+        backup_local_transactions_task, maintain_transaction_pool_future,
+        LocalTransactionBackupConfig,
+    },
+    EthPoolTransaction, EthPooledTransaction, EthTransactionValidator, Pool, PoolTransaction,
+    Priority, TransactionOrdering, TransactionOrigin, TransactionValidationOutcome,
+    TransactionValidationTaskExecutor, TransactionValidator,
+};
+use revm::interpreter::gas::validate_initial_tx_gas;
+use revm_primitives::SpecId;
+use tracing::{debug, info};
+
+pub use irys_storage::pd_chunk_cache::{MAX_PD_CHUNKS_PER_BLOCK, MAX_PD_CHUNKS_PER_TX};
+
+use crate::precompile::{
+    entrypoint::PRECOMPILE_ADDRESS,
+    gas::pd_access_list_intrinsic_gas,
+    utils::{ledger_chunk_range, parse_access_list},
+};
+
+/// Priority fee (per gas) PD transactions have to pay on top of regular
+/// transactions to be ordered the same, covering the cost of reading and
+/// unpacking their chunks
+pub const PD_FEE_PREMIUM_PER_GAS: u128 = 1_000_000_000;
+
+/// Whether the access list declares any PD reads
+pub fn is_pd_access_list(access_list: &[AccessListItem]) -> bool {
+    access_list
+        .iter()
+        .any(|item| item.address == PRECOMPILE_ADDRESS)
+}
+
+/// A PD transaction, or the PD transactions of a block, reading more chunks
+/// than allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PdChunkLimitError {
+    #[error("PD transaction reads {chunks} chunks, at most {max} are allowed")]
+    Transaction { chunks: u64, max: u64 },
+    #[error("PD transactions of the block read {chunks} chunks, at most {max} are allowed")]
+    Block { chunks: u64, max: u64 },
+}
+
+/// Checks a transaction reading `chunks` chunks is within [`MAX_PD_CHUNKS_PER_TX`]
+pub fn check_tx_pd_chunks(chunks: u64) -> Result<(), PdChunkLimitError> {
+    if chunks > MAX_PD_CHUNKS_PER_TX {
+        return Err(PdChunkLimitError::Transaction {
+            chunks,
+            max: MAX_PD_CHUNKS_PER_TX,
+        });
+    }
+    Ok(())
+}
+
+/// Checks the PD transactions of a block are within the per transaction and per
+/// block chunk limits, returning the number of chunks they read. Malformed PD
+/// access lists don't read any chunks, they fail in the precompile instead.
+pub fn check_block_pd_chunks<'a>(
+    access_lists: impl IntoIterator<Item = &'a [AccessListItem]>,
+) -> Result<u64, PdChunkLimitError> {
+    let mut total = 0;
+    for access_list in access_lists {
+        let chunks = parse_access_list(access_list).map_or(0, |parsed| parsed.chunk_count());
+        check_tx_pd_chunks(chunks)?;
+        total += chunks;
+    }
+    if total > MAX_PD_CHUNKS_PER_BLOCK {
+        return Err(PdChunkLimitError::Block {
+            chunks: total,
+            max: MAX_PD_CHUNKS_PER_BLOCK,
+        });
+    }
+    Ok(total)
+}
+
+/// Error returned for PD transactions that break the pool admission rules
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid PD transaction: {0}")]
+pub struct InvalidPdTransaction(String);
+
+impl PoolTransactionError for InvalidPdTransaction {
+    fn is_bad_transaction(&self) -> bool {
+        // the referenced data may just not be stored by this node (yet)
+        false
+    }
+}
+
+/// Transaction validator that applies the PD admission rules before the regular
+/// Ethereum validation: every PD storage key must decode, the transaction can't
+/// read more than [`MAX_PD_CHUNKS_PER_TX`] chunks, the chunks it reads must be in
+/// the Publish ledger and stored by this node, and its gas limit must cover the
+/// intrinsic gas of its PD storage keys.
+#[derive(Debug, Clone)]
+pub struct IrysTransactionValidator<Client, Tx> {
+    inner: EthTransactionValidator<Client, Tx>,
+    provider: IrysRethProvider,
+}
+
+impl<Client, Tx> IrysTransactionValidator<Client, Tx> {
+    /// Wraps the regular Ethereum transaction validator
+    pub const fn new(
+        inner: EthTransactionValidator<Client, Tx>,
+        provider: IrysRethProvider,
+    ) -> Self {
+        Self { inner, provider }
+    }
+
+    /// Validates the PD reads declared by an access list
+    pub fn validate_pd_access_list(&self, access_list: &[AccessListItem]) -> eyre::Result<()> {
+        if !is_pd_access_list(access_list) {
+            return Ok(());
+        }
+        let provider = self
+            .provider
+            .get()
+            .ok_or_eyre("Irys provider uninitialised")?;
+        validate_pd_reads(provider, access_list)
+    }
+}
+
+/// Validates the PD reads declared by an access list: every PD storage key must
+/// decode, the reads must be within [`MAX_PD_CHUNKS_PER_TX`], and the chunks must be
+/// in the Publish ledger and stored by this node
+pub fn validate_pd_reads(
+    provider: &IrysRethProviderInner,
+    access_list: &[AccessListItem],
+) -> eyre::Result<()> {
+    let parsed = parse_access_list(access_list)?;
+    check_tx_pd_chunks(parsed.chunk_count())?;
+    if let Some(byte_read) = parsed
+        .byte_reads
+        .iter()
+        .find(|byte_read| byte_read.index as usize >= parsed.chunk_reads.len())
+    {
+        return Err(eyre!(
+            "Byte read references chunk range {} of {}",
+            byte_read.index,
+            parsed.chunk_reads.len()
+        ));
+    }
+
+    let publish_chunks = provider
+        .block_index
+        .read()
+        .unwrap()
+        .get_latest_item()
+        .map_or(0, |item| item.ledgers[Ledger::Publish].max_chunk_offset);
+    let chunk_provider = &provider.chunk_provider;
+    for chunk_range in &parsed.chunk_reads {
+        let ledger_range = ledger_chunk_range(&chunk_provider.storage_config, chunk_range)
+            .map_err(|e| eyre!("Invalid chunk range {:?}: {:?}", chunk_range, e))?;
+        if ledger_range.end > publish_chunks {
+            return Err(eyre!(
+                "Chunks {}..{} are past the end of the Publish ledger ({} chunks)",
+                ledger_range.start,
+                ledger_range.end,
+                publish_chunks
+            ));
+        }
+        if let Some(offset) = ledger_range.clone().find(|offset| {
+            get_storage_module_at_offset(&chunk_provider.storage_modules, Ledger::Publish, *offset)
+                .is_none()
+        }) {
+            return Err(eyre!("Chunk {} is not stored by this node", offset));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the gas limit of a transaction covers its intrinsic gas, including the
+/// PD storage keys of its access list that the EVM charges on top of the regular
+/// intrinsic gas, so PD transactions that can't execute don't enter the pool
+pub fn check_pd_intrinsic_gas<T: PoolTransaction>(transaction: &T) -> eyre::Result<()> {
+    let access_list = transaction
+        .access_list()
+        .map_or(&[][..], |access_list| access_list.0.as_slice());
+    let pd_gas = pd_access_list_intrinsic_gas(access_list);
+    if pd_gas == 0 {
+        return Ok(());
+    }
+    let intrinsic_gas = validate_initial_tx_gas(
+        SpecId::LATEST,
+        transaction.input(),
+        transaction.kind().is_create(),
+        access_list,
+        transaction.authorization_count() as u64,
+    )
+    .saturating_add(pd_gas);
+    if intrinsic_gas > transaction.gas_limit() {
+        return Err(eyre!(
+            "Gas limit {} is below the intrinsic gas {} ({} for PD storage keys)",
+            transaction.gas_limit(),
+            intrinsic_gas,
+            pd_gas
+        ));
+    }
+    Ok(())
+}
+
+impl<Client, Tx> TransactionValidator for IrysTransactionValidator<Client, Tx>
+where
+    Client: StateProviderFactory + BlockReaderIdExt,
+    Tx: EthPoolTransaction,
+{
+    type Transaction = Tx;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        if let Some(access_list) = transaction.access_list() {
+            if let Err(e) = self
+                .validate_pd_access_list(&access_list.0)
+                .and_then(|_| check_pd_intrinsic_gas(&transaction))
+            {
+                debug!("Rejecting PD tx {}: {}", transaction.hash(), &e);
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::Other(Box::new(InvalidPdTransaction(
+                        e.to_string(),
+                    ))),
+                );
+            }
+        }
+        self.inner.validate_transaction(origin, transaction).await
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.inner.on_new_head_block(new_tip_block)
+    }
+}
+
+/// Orders transactions by their effective tip like
+/// [`CoinbaseTipOrdering`](reth_transaction_pool::CoinbaseTipOrdering), except
+/// PD transactions have [`PD_FEE_PREMIUM_PER_GAS`] deducted from their tip
+pub struct PdFeePremiumOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for PdFeePremiumOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        let Some(tip) = transaction.effective_tip_per_gas(base_fee) else {
+            return Priority::None;
+        };
+        let is_pd = transaction
+            .access_list()
+            .is_some_and(|access_list| is_pd_access_list(&access_list.0));
+        let premium = if is_pd { PD_FEE_PREMIUM_PER_GAS } else { 0 };
+        Priority::Value(U256::from(tip.saturating_sub(premium)))
+    }
+}
+
+impl<T> Default for PdFeePremiumOrdering<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Clone for PdFeePremiumOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<T> fmt::Debug for PdFeePremiumOrdering<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PdFeePremiumOrdering").finish()
+    }
+}
+
+/// The transaction pool of the Irys reth node
+pub type IrysTransactionPool<Client, S> = Pool<
+    TransactionValidationTaskExecutor<IrysTransactionValidator<Client, EthPooledTransaction>>,
+    PdFeePremiumOrdering<EthPooledTransaction>,
+    S,
+>;
+
+/// Builds the regular Ethereum transaction pool, validating transactions with
+/// [`IrysTransactionValidator`] and ordering them with [`PdFeePremiumOrdering`]
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct IrysPoolBuilder;
+
+impl<Node> PoolBuilder<Node> for IrysPoolBuilder
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec>>,
+{
+    type Pool = IrysTransactionPool<Node::Provider, DiskFileBlobStore>;
+
+    async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
+        // from EthereumPoolBuilder::build_pool, with our validator & ordering
+        let provider = ctx.irys_ext.clone().unwrap().provider;
+        let data_dir = ctx.config().datadir();
+        let pool_config = ctx.pool_config();
+        let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        let eth_validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
+            .with_head_timestamp(ctx.head().timestamp)
+            .kzg_settings(ctx.kzg_settings()?)
+            .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
+            .build_with_tasks(
+                ctx.provider().clone(),
+                ctx.task_executor().clone(),
+                blob_store.clone(),
+            );
+        let validator = TransactionValidationTaskExecutor {
+            validator: IrysTransactionValidator::new(eth_validator.validator, provider),
+            to_validation_task: eth_validator.to_validation_task,
+        };
+
+        let transaction_pool = Pool::new(
+            validator,
+            PdFeePremiumOrdering::default(),
+            blob_store,
+            pool_config,
+        );
+        info!(target: "reth::cli", "Transaction pool initialized");
+        let transactions_path = data_dir.txpool_transactions();
+
+        // spawn txpool maintenance task
+        {
+            let pool = transaction_pool.clone();
+            let chain_events = ctx.provider().canonical_state_stream();
+            let client = ctx.provider().clone();
+            let transactions_backup_config =
+                LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+
+            ctx.task_executor()
+                .spawn_critical_with_graceful_shutdown_signal(
+                    "local transactions backup task",
+                    |shutdown| {
+                        backup_local_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            transactions_backup_config,
+                        )
+                    },
+                );
+
+            // spawn the maintenance task
+            ctx.task_executor().spawn_critical(
+                "txpool maintenance task",
+                maintain_transaction_pool_future(
+                    client,
+                    pool,
+                    chain_events,
+                    ctx.task_executor().clone(),
+                    Default::default(),
+                ),
+            );
+            debug!(target: "reth::cli", "Spawned txpool maintenance task");
+        }
+
+        Ok(transaction_pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompile::utils::tests::{chunk_read_key, test_provider};
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::{Address, Signature, B256};
+    use alloy_rpc_types::AccessList;
+    use irys_primitives::range_specifier::{ByteRangeSpecifier, PdAccessListArg, U18, U34};
+    use irys_testing_utils::utils::setup_tracing_and_temp_dir;
+    use reth_primitives::{Transaction, TransactionSigned};
+
+    const GWEI: u128 = 1_000_000_000;
+
+    fn pd_access_list(storage_keys: Vec<B256>) -> Vec<AccessListItem> {
+        vec![AccessListItem {
+            address: PRECOMPILE_ADDRESS,
+            storage_keys,
+        }]
+    }
+
+    #[test]
+    fn test_check_block_pd_chunks() {
+        let tx_limit = u16::try_from(MAX_PD_CHUNKS_PER_TX).unwrap();
+        let full_tx = pd_access_list(vec![chunk_read_key(0, 0, tx_limit)]);
+        let regular_tx: Vec<AccessListItem> = vec![];
+        // malformed PD keys don't count, the tx fails in the precompile
+        let malformed_tx = pd_access_list(vec![B256::repeat_byte(0xff)]);
+
+        let block = vec![full_tx.as_slice(); 4];
+        assert_eq!(
+            check_block_pd_chunks(
+                block
+                    .iter()
+                    .copied()
+                    .chain([regular_tx.as_slice(), malformed_tx.as_slice()])
+            ),
+            Ok(MAX_PD_CHUNKS_PER_BLOCK)
+        );
+
+        let block = vec![full_tx.as_slice(); 5];
+        assert_eq!(
+            check_block_pd_chunks(block),
+            Err(PdChunkLimitError::Block {
+                chunks: MAX_PD_CHUNKS_PER_TX * 5,
+                max: MAX_PD_CHUNKS_PER_BLOCK
+            })
+        );
+
+        let over_limit_tx = pd_access_list(vec![
+            chunk_read_key(0, 0, tx_limit),
+            chunk_read_key(1, 0, 1),
+        ]);
+        assert_eq!(
+            check_block_pd_chunks([over_limit_tx.as_slice()]),
+            Err(PdChunkLimitError::Transaction {
+                chunks: MAX_PD_CHUNKS_PER_TX + 1,
+                max: MAX_PD_CHUNKS_PER_TX
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_pd_reads() {
+        let tmp_dir = setup_tracing_and_temp_dir(Some("test_validate_pd_reads"), false);
+        let provider = test_provider(tmp_dir.path()).await;
+        let tx_limit = u16::try_from(MAX_PD_CHUNKS_PER_TX).unwrap();
+
+        let over_limit = pd_access_list(vec![
+            chunk_read_key(0, 0, tx_limit),
+            chunk_read_key(1, 0, 1),
+        ]);
+        let err = validate_pd_reads(&provider, &over_limit).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PdChunkLimitError>(),
+            Some(&PdChunkLimitError::Transaction {
+                chunks: MAX_PD_CHUNKS_PER_TX + 1,
+                max: MAX_PD_CHUNKS_PER_TX
+            })
+        );
+
+        let malformed = pd_access_list(vec![chunk_read_key(0, 0, 1), B256::repeat_byte(0xff)]);
+        assert!(validate_pd_reads(&provider, &malformed).is_err());
+
+        // the byte read references a chunk range that isn't declared
+        let byte_read = ByteRangeSpecifier {
+            index: 1,
+            chunk_offset: 0,
+            byte_offset: U18::from(0),
+            length: U34::from(32),
+        };
+        let dangling_byte_read = pd_access_list(vec![
+            chunk_read_key(0, 0, 1),
+            B256::from(PdAccessListArg::ByteRead(byte_read).encode()),
+        ]);
+        let err = validate_pd_reads(&provider, &dangling_byte_read).unwrap_err();
+        assert!(err.to_string().contains("Byte read references chunk range"));
+
+        // the Publish ledger is empty
+        let unknown_chunks = pd_access_list(vec![chunk_read_key(0, 0, 1)]);
+        let err = validate_pd_reads(&provider, &unknown_chunks).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("past the end of the Publish ledger"));
+    }
+
+    fn pooled_tx(max_priority_fee_per_gas: u128, access_list: AccessList) -> EthPooledTransaction {
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            max_fee_per_gas: 10 * GWEI,
+            max_priority_fee_per_gas,
+            access_list,
+            ..Default::default()
+        });
+        let signed = TransactionSigned::from_transaction_and_signature(
+            transaction,
+            Signature::test_signature(),
+        );
+        EthPooledTransaction::new(signed.with_signer(Address::random()), 200)
+    }
+
+    #[test]
+    fn test_check_pd_intrinsic_gas() {
+        let access_list = AccessList(pd_access_list(vec![chunk_read_key(0, 0, 1); 2]));
+        let intrinsic_gas = validate_initial_tx_gas(SpecId::LATEST, &[], false, &access_list.0, 0);
+        let pd_gas = pd_access_list_intrinsic_gas(&access_list.0);
+        let pd_tx = |gas_limit| {
+            let transaction = Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                gas_limit,
+                max_fee_per_gas: 10 * GWEI,
+                access_list: access_list.clone(),
+                ..Default::default()
+            });
+            let signed = TransactionSigned::from_transaction_and_signature(
+                transaction,
+                Signature::test_signature(),
+            );
+            EthPooledTransaction::new(signed.with_signer(Address::random()), 200)
+        };
+
+        assert!(check_pd_intrinsic_gas(&pd_tx(intrinsic_gas + pd_gas)).is_ok());
+        // covers the regular intrinsic gas, but not the PD storage keys
+        let err = check_pd_intrinsic_gas(&pd_tx(intrinsic_gas + pd_gas - 1)).unwrap_err();
+        assert!(err.to_string().contains("intrinsic gas"));
+        // regular txs are left to the Ethereum validator
+        assert!(check_pd_intrinsic_gas(&pooled_tx(GWEI, AccessList::default())).is_ok());
+    }
+
+    #[test]
+    fn test_pd_fee_premium_ordering() {
+        let ordering = PdFeePremiumOrdering::<EthPooledTransaction>::default();
+        let pd_access_list = AccessList(pd_access_list(vec![chunk_read_key(0, 0, 1)]));
+        let base_fee = GWEI as u64;
+
+        let regular = pooled_tx(2 * GWEI, AccessList::default());
+        let pd = pooled_tx(2 * GWEI, pd_access_list.clone());
+        let pd_with_premium = pooled_tx(2 * GWEI + PD_FEE_PREMIUM_PER_GAS, pd_access_list.clone());
+
+        assert_eq!(
+            ordering.priority(&regular, base_fee),
+            Priority::Value(U256::from(2 * GWEI))
+        );
+        assert_eq!(
+            ordering.priority(&pd, base_fee),
+            Priority::Value(U256::from(2 * GWEI - PD_FEE_PREMIUM_PER_GAS))
+        );
+        assert_eq!(
+            ordering.priority(&pd_with_premium, base_fee),
+            ordering.priority(&regular, base_fee)
+        );
+
+        // the tip can't go negative, and txs that can't pay the base fee have no priority
+        let pd_without_tip = pooled_tx(0, pd_access_list);
+        assert_eq!(
+            ordering.priority(&pd_without_tip, base_fee),
+            Priority::Value(U256::ZERO)
+        );
+        assert_eq!(
+            ordering.priority(&regular, 20 * GWEI as u64),
+            Priority::None
+        );
+    }
+}
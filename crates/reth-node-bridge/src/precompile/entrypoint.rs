@@ -5,6 +5,7 @@ use super::{
     functions::PdFunctionId,
     irys_executor::{CustomPrecompileWithAddress, PrecompileStateProvider},
     read_bytes::{read_bytes_range_by_index, read_partial_byte_range},
+    read_chunk::read_chunk_with_proof,
     utils::parse_access_list,
 };
 
@@ -16,7 +17,7 @@ pub const PROGRAMMABLE_DATA_PRECOMPILE: CustomPrecompileWithAddress =
 
 /// programmable data precompile
 /// this precompile is an 'actual' smart contract, with multiple subfunctions.
-// TODO: Gas pricing
+/// each subfunction charges gas for the chunks & bytes it reads, see [`super::gas`].
 fn programmable_data_precompile(
     call_data: &Bytes,
     gas_limit: u64,
@@ -30,18 +31,18 @@ fn programmable_data_precompile(
         .into());
     }
 
-    let access_list = &env.tx.access_list;
-    if access_list.is_empty() {
-        // this is a constant requirement across all execution paths, as we always need at least one PD chunk read range, which must be in the access list.
-        return Err(PrecompileError::Other("Transaction has no access list".to_string()).into());
-    }
-
     let call_data_vec = call_data.to_vec();
 
     // decode the first byte of the calldata
     let decoded_id = PdFunctionId::try_from(call_data_vec[0])
         .map_err(|e| PrecompileErrors::Error(PrecompileError::Other(format!("{}", &e))))?;
 
+    let access_list = &env.tx.access_list;
+    if access_list.is_empty() {
+        // every function needs at least one PD chunk read range, which must be in the access list.
+        return Err(PrecompileError::Other("Transaction has no access list".to_string()).into());
+    }
+
     let state_provider = state_provider
         .provider
         .get()
@@ -49,10 +50,11 @@ fn programmable_data_precompile(
             "Internal error - provider uninitialised".to_owned(),
         )))?;
 
-    let parsed = parse_access_list(access_list).map_err(|_| {
-        PrecompileErrors::Error(PrecompileError::Other(
-            "Internal error - unable to parse access list".to_owned(),
-        ))
+    let parsed = parse_access_list(access_list).map_err(|e| {
+        PrecompileErrors::Error(PrecompileError::Other(format!(
+            "Unable to parse access list - {}",
+            &e
+        )))
     })?;
 
     match decoded_id {
@@ -62,5 +64,8 @@ fn programmable_data_precompile(
         PdFunctionId::ReadPartialByteRange => {
             read_partial_byte_range(call_data, gas_limit, env, state_provider, parsed)
         }
+        PdFunctionId::ReadChunkWithProof => {
+            read_chunk_with_proof(call_data, gas_limit, env, state_provider, parsed)
+        }
     }
 }
@@ -4,6 +4,7 @@
 pub enum PdFunctionId {
     ReadFullByteRange = 0,
     ReadPartialByteRange,
+    ReadChunkWithProof,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -20,6 +21,7 @@ impl TryFrom<u8> for PdFunctionId {
         match id {
             0 => Ok(PdFunctionId::ReadFullByteRange),
             1 => Ok(PdFunctionId::ReadPartialByteRange),
+            2 => Ok(PdFunctionId::ReadChunkWithProof),
             _ => Err(PdFunctionIdDecodeError::UnknownPdFunctionId(id)),
         }
     }
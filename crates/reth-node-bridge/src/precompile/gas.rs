@@ -0,0 +1,105 @@
+use alloy_rpc_types::AccessListItem;
+use revm_primitives::{PrecompileError, PrecompileErrors};
+
+use super::entrypoint::PRECOMPILE_ADDRESS;
+
+/// Cost of reading a single chunk from storage
+pub const PD_CHUNK_READ_COST: u64 = 500;
+
+/// Cost of unpacking a single chunk read from storage, chunks are stored packed
+/// so each one needs its entropy recomputed before the data can be returned
+pub const PD_CHUNK_UNPACK_COST: u64 = 2_000;
+
+/// Cost of copying each 32 byte word of the requested byte range into the output
+pub const PD_BYTE_READ_WORD_COST: u64 = 3;
+
+/// Intrinsic cost of each PD storage key in the access list, on top of the
+/// regular access list storage key cost. Charged before execution as every key
+/// requires the node to resolve (and eventually prefetch) the range it specifies.
+pub const PD_ACCESS_LIST_KEY_COST: u64 = 1_000;
+
+/// Gas charged for reading `chunk_count` chunks and returning `byte_len` bytes from them
+pub const fn read_bytes_range_gas(chunk_count: u64, byte_len: u64) -> u64 {
+    let chunk_cost = chunk_count.saturating_mul(PD_CHUNK_READ_COST + PD_CHUNK_UNPACK_COST);
+    let word_count = byte_len.div_ceil(32);
+    chunk_cost.saturating_add(word_count.saturating_mul(PD_BYTE_READ_WORD_COST))
+}
+
+/// Intrinsic gas for the PD storage keys in an access list, only keys for the
+/// PD precompile are charged
+pub fn pd_access_list_intrinsic_gas(access_list: &[AccessListItem]) -> u64 {
+    let pd_keys = access_list
+        .iter()
+        .filter(|item| item.address == PRECOMPILE_ADDRESS)
+        .map(|item| item.storage_keys.len() as u64)
+        .sum::<u64>();
+    pd_keys.saturating_mul(PD_ACCESS_LIST_KEY_COST)
+}
+
+/// Returns `gas_used` if it fits within `gas_limit`, otherwise an out of gas error
+pub fn charge_gas(gas_used: u64, gas_limit: u64) -> Result<u64, PrecompileErrors> {
+    if gas_used > gas_limit {
+        return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
+    }
+    Ok(gas_used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256};
+
+    #[test]
+    fn test_read_bytes_range_gas() {
+        let chunk_cost = PD_CHUNK_READ_COST + PD_CHUNK_UNPACK_COST;
+        assert_eq!(read_bytes_range_gas(0, 0), 0);
+        assert_eq!(read_bytes_range_gas(1, 0), chunk_cost);
+        // partial words are charged as full words
+        assert_eq!(
+            read_bytes_range_gas(1, 1),
+            chunk_cost + PD_BYTE_READ_WORD_COST
+        );
+        assert_eq!(
+            read_bytes_range_gas(1, 32),
+            chunk_cost + PD_BYTE_READ_WORD_COST
+        );
+        assert_eq!(
+            read_bytes_range_gas(1, 33),
+            chunk_cost + 2 * PD_BYTE_READ_WORD_COST
+        );
+        assert_eq!(
+            read_bytes_range_gas(u16::MAX as u64, 0),
+            u16::MAX as u64 * chunk_cost
+        );
+        assert_eq!(read_bytes_range_gas(u64::MAX, 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_charge_gas_boundaries() {
+        assert_eq!(charge_gas(0, 0).unwrap(), 0);
+        assert_eq!(charge_gas(1_000, 1_000).unwrap(), 1_000);
+        assert!(matches!(
+            charge_gas(1_001, 1_000),
+            Err(PrecompileErrors::Error(PrecompileError::OutOfGas))
+        ));
+    }
+
+    #[test]
+    fn test_pd_access_list_intrinsic_gas() {
+        let access_list = vec![
+            AccessListItem {
+                address: PRECOMPILE_ADDRESS,
+                storage_keys: vec![B256::ZERO; 3],
+            },
+            AccessListItem {
+                address: Address::random(),
+                storage_keys: vec![B256::ZERO; 5],
+            },
+        ];
+        assert_eq!(
+            pd_access_list_intrinsic_gas(&access_list),
+            3 * PD_ACCESS_LIST_KEY_COST
+        );
+        assert_eq!(pd_access_list_intrinsic_gas(&[]), 0);
+    }
+}
@@ -24,11 +24,20 @@ use reth_node_api::{
 use reth_node_ethereum::{node::EthereumPayloadBuilder, EthEvmConfig, EthExecutorProvider};
 use reth_primitives::{Header, TransactionSigned};
 use revm::ContextPrecompile;
-use revm_primitives::StatefulPrecompile;
-use std::sync::Arc;
+use revm_primitives::{EVMError, InvalidTransaction, StatefulPrecompile};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use tracing::info;
 
-use crate::precompile::entrypoint::PROGRAMMABLE_DATA_PRECOMPILE;
+use crate::{
+    pool::{check_tx_pd_chunks, PdChunkLimitError, MAX_PD_CHUNKS_PER_BLOCK},
+    precompile::{
+        entrypoint::PROGRAMMABLE_DATA_PRECOMPILE, gas::pd_access_list_intrinsic_gas,
+        utils::parse_access_list,
+    },
+};
 
 // TODO: sometimes the EVM is initialized with spec ID CANCUN, and sometimes with MERGE
 // for now it doesn't matter much, but we do want to fix it eventually.
@@ -73,6 +82,51 @@ pub struct PrecompileStateProvider {
     pub provider: IrysRethProvider,
 }
 
+/// Running total of the chunks read by the PD transactions of the block being
+/// built. The payload builder can execute a block's transactions on more than one
+/// EVM, so the total is shared by the builder's EVMs and scoped to the block
+/// environment. Transactions are keyed by sender & nonce so executing one again
+/// (i.e. when the payload is rebuilt) doesn't count its chunks twice.
+#[derive(Debug, Default)]
+pub struct PdBlockChunks {
+    /// Number & timestamp of the block being built
+    block: Option<(U256, U256)>,
+    txs: HashMap<(Address, Option<u64>), u64>,
+}
+
+impl PdBlockChunks {
+    /// Adds the chunks read by a transaction to the block's total, unless they'd
+    /// take it over [`MAX_PD_CHUNKS_PER_BLOCK`]
+    pub fn add(
+        &mut self,
+        block: &BlockEnv,
+        tx: &TxEnv,
+        chunk_count: u64,
+    ) -> Result<(), PdChunkLimitError> {
+        let block = Some((block.number, block.timestamp));
+        if self.block != block {
+            self.block = block;
+            self.txs.clear();
+        }
+        let tx_key = (tx.caller, tx.nonce);
+        let others: u64 = self
+            .txs
+            .iter()
+            .filter(|(key, _)| **key != tx_key)
+            .map(|(_, chunks)| chunks)
+            .sum();
+        let total = others + chunk_count;
+        if total > MAX_PD_CHUNKS_PER_BLOCK {
+            return Err(PdChunkLimitError::Block {
+                chunks: total,
+                max: MAX_PD_CHUNKS_PER_BLOCK,
+            });
+        }
+        self.txs.insert(tx_key, chunk_count);
+        Ok(())
+    }
+}
+
 /// Custom EVM configuration
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -80,6 +134,8 @@ pub struct IrysEvmConfig {
     inner: EthEvmConfig,
     precompile_state_provider: PrecompileStateProvider,
     precompiles: Precompiles,
+    /// Shared by the payload builder's EVMs, `None` for block execution
+    pd_block_chunks: Option<Arc<Mutex<PdBlockChunks>>>,
 }
 
 impl IrysEvmConfig {
@@ -93,6 +149,7 @@ impl IrysEvmConfig {
             inner: EthEvmConfig::new(chain_spec),
             precompile_state_provider,
             precompiles,
+            pd_block_chunks: None,
         }
     }
 
@@ -128,6 +185,59 @@ impl IrysEvmConfig {
         handler.pre_execution.load_precompiles = Arc::new(move || loaded_precompiles.clone());
     }
 
+    /// Adds the intrinsic gas of PD access list storage keys to the initial gas
+    /// of each transaction, rejecting transactions whose gas limit can't cover it
+    pub fn set_pd_intrinsic_gas<EXT, DB>(handler: &mut EvmHandler<EXT, DB>)
+    where
+        DB: Database,
+    {
+        let initial_tx_gas = handler.validation.initial_tx_gas.clone();
+        handler.validation.initial_tx_gas = Arc::new(move |env: &Env| {
+            let initial_gas = initial_tx_gas(env)?
+                .saturating_add(pd_access_list_intrinsic_gas(&env.tx.access_list));
+            if initial_gas > env.tx.gas_limit {
+                return Err(EVMError::Transaction(
+                    InvalidTransaction::CallGasCostMoreThanGasLimit,
+                ));
+            }
+            Ok(initial_gas)
+        });
+    }
+
+    /// Limits the number of chunks a PD transaction can read to
+    /// [`MAX_PD_CHUNKS_PER_TX`](crate::pool::MAX_PD_CHUNKS_PER_TX), and when building
+    /// payloads, the chunks the PD transactions of the block being built read to
+    /// [`MAX_PD_CHUNKS_PER_BLOCK`]. Imported blocks have their per block total checked
+    /// before they're handed to reth, see [`check_block_pd_chunks`](crate::pool::check_block_pd_chunks).
+    pub fn set_pd_chunk_limits<EXT, DB>(
+        handler: &mut EvmHandler<EXT, DB>,
+        pd_block_chunks: Option<Arc<Mutex<PdBlockChunks>>>,
+    ) where
+        DB: Database,
+    {
+        let tx_against_state = handler.validation.tx_against_state.clone();
+        handler.validation.tx_against_state = Arc::new(move |context| {
+            tx_against_state(context)?;
+            // malformed access lists are rejected by the pool & fail in the precompile
+            let env = &context.evm.env;
+            let chunk_count =
+                parse_access_list(&env.tx.access_list).map_or(0, |parsed| parsed.chunk_count());
+            if chunk_count == 0 {
+                return Ok(());
+            }
+            let limits = check_tx_pd_chunks(chunk_count).and_then(|()| match &pd_block_chunks {
+                Some(block_chunks) => {
+                    block_chunks
+                        .lock()
+                        .unwrap()
+                        .add(&env.block, &env.tx, chunk_count)
+                }
+                None => Ok(()),
+            });
+            limits.map_err(|e| EVMError::Custom(e.to_string()))
+        });
+    }
+
     /// Given a [`CustomPrecompileWithAddress`] and cache for a specific precompile, create a new precompile
     /// that wraps the precompile with the provider.
     fn wrap_precompile<DB>(
@@ -218,6 +328,10 @@ impl ConfigureEvm for IrysEvmConfig {
                     self.precompiles.clone(),
                 )
             }))
+            .append_handler_register(IrysEvmConfig::set_pd_intrinsic_gas)
+            .append_handler_register_box(Box::new(move |handler| {
+                IrysEvmConfig::set_pd_chunk_limits(handler, self.pd_block_chunks.clone())
+            }))
             .build()
     }
 
@@ -264,6 +378,10 @@ impl ConfigureEvm for IrysEvmConfig {
                     self.precompiles.clone(),
                 )
             }))
+            .append_handler_register(IrysEvmConfig::set_pd_intrinsic_gas)
+            .append_handler_register_box(Box::new(move |handler| {
+                IrysEvmConfig::set_pd_chunk_limits(handler, self.pd_block_chunks.clone())
+            }))
             .append_handler_register(inspector_handle_register)
             .build()
     }
@@ -297,6 +415,7 @@ where
             inner: EthEvmConfig::new(ctx.chain_spec()),
             precompile_state_provider,
             precompiles: irys_precompiles(),
+            pd_block_chunks: None,
         };
         Ok((
             evm_config.clone(),
@@ -335,6 +454,7 @@ where
             inner: EthEvmConfig::new(ctx.chain_spec()),
             precompile_state_provider,
             precompiles: irys_precompiles(),
+            pd_block_chunks: Some(Default::default()),
         };
 
         self.inner.spawn(evm_config, ctx, pool)
@@ -344,3 +464,38 @@ where
 pub fn irys_precompiles() -> Precompiles {
     vec![PROGRAMMABLE_DATA_PRECOMPILE]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pd_block_chunks() {
+        let mut block_chunks = PdBlockChunks::default();
+        let block = BlockEnv::default();
+        let tx = |nonce| TxEnv {
+            caller: Address::repeat_byte(1),
+            nonce: Some(nonce),
+            ..Default::default()
+        };
+
+        block_chunks.add(&block, &tx(0), 1_000).unwrap();
+        // executing the same tx again, i.e. in a rebuilt payload, doesn't count it twice
+        block_chunks.add(&block, &tx(0), 1_000).unwrap();
+        block_chunks.add(&block, &tx(1), 24).unwrap();
+        assert_eq!(
+            block_chunks.add(&block, &tx(2), 1),
+            Err(PdChunkLimitError::Block {
+                chunks: MAX_PD_CHUNKS_PER_BLOCK + 1,
+                max: MAX_PD_CHUNKS_PER_BLOCK
+            })
+        );
+
+        // the total is per block
+        let next_block = BlockEnv {
+            number: block.number + U256::from(1),
+            ..block
+        };
+        block_chunks.add(&next_block, &tx(2), 1).unwrap();
+    }
+}
@@ -1,5 +1,8 @@
 pub mod entrypoint;
 pub mod functions;
+pub mod gas;
 pub mod irys_executor;
+pub mod prefetch;
 pub mod read_bytes;
+pub mod read_chunk;
 pub mod utils;
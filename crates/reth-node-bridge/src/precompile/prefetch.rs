@@ -0,0 +1,135 @@
+use alloy_primitives::Bytes;
+use alloy_rpc_types::AccessListItem;
+use eyre::eyre;
+use irys_storage::reth_provider::{IrysRethProvider, IrysRethProviderInner};
+use reth_transaction_pool::{PoolTransaction as _, TransactionPool};
+use tracing::{debug, warn};
+
+use super::{
+    entrypoint::PRECOMPILE_ADDRESS,
+    utils::{ledger_chunk_range, parse_access_list, payload_access_lists},
+};
+use crate::pool::{MAX_PD_CHUNKS_PER_BLOCK, MAX_PD_CHUNKS_PER_TX};
+
+/// Publish ledger offsets of every chunk declared by the PD chunk ranges of `access_list`
+fn pd_ledger_offsets(
+    provider: &IrysRethProviderInner,
+    access_list: &[AccessListItem],
+) -> eyre::Result<Vec<u64>> {
+    if !access_list
+        .iter()
+        .any(|item| item.address == PRECOMPILE_ADDRESS)
+    {
+        return Ok(vec![]);
+    }
+
+    let parsed = parse_access_list(access_list)?;
+    let storage_config = &provider.chunk_provider.storage_config;
+    let mut ledger_offsets = vec![];
+    for chunk_range in &parsed.chunk_reads {
+        ledger_offsets.extend(
+            ledger_chunk_range(storage_config, chunk_range)
+                .map_err(|e| eyre!("Invalid PD chunk range {:?}: {:?}", chunk_range, e))?,
+        );
+    }
+    Ok(ledger_offsets)
+}
+
+/// Fetches & unpacks every chunk declared by the PD chunk ranges of `access_list`
+/// into the PD chunk cache, so the precompile can read them without unpacking
+pub fn prefetch_pd_chunks(
+    provider: &IrysRethProviderInner,
+    access_list: &[AccessListItem],
+) -> eyre::Result<()> {
+    provider
+        .pd_chunk_cache
+        .prefetch(pd_ledger_offsets(provider, access_list)?)
+}
+
+/// Fetches & unpacks the chunks read by a block's transactions as a single batch,
+/// before the block executes. Transactions with malformed PD access lists are
+/// skipped, they fail in the precompile instead.
+pub fn prefetch_block_pd_chunks<'a>(
+    provider: &IrysRethProviderInner,
+    access_lists: impl IntoIterator<Item = &'a [AccessListItem]>,
+) -> eyre::Result<()> {
+    let mut ledger_offsets = vec![];
+    for access_list in access_lists {
+        match pd_ledger_offsets(provider, access_list) {
+            Ok(offsets) => ledger_offsets.extend(offsets),
+            Err(e) => debug!("Not prefetching malformed PD access list: {}", e),
+        }
+    }
+    provider.pd_chunk_cache.prefetch(ledger_offsets)
+}
+
+/// Prefetches the chunks read by the transactions of an execution payload, so
+/// they're unpacked before the payload is handed to reth for execution
+pub fn prefetch_payload_pd_chunks(
+    provider: &IrysRethProviderInner,
+    transactions: &[Bytes],
+) -> eyre::Result<()> {
+    let access_lists = payload_access_lists(transactions)?;
+    prefetch_block_pd_chunks(
+        provider,
+        access_lists
+            .iter()
+            .map(|access_list| access_list.0.as_slice()),
+    )
+}
+
+/// Prefetches the chunks read by the PD transactions the payload builder will
+/// pick from `pool`, in pool order and within the per block chunk limit, so they're
+/// unpacked before the payload is built
+pub fn prefetch_best_pd_chunks<P: TransactionPool>(
+    pool: &P,
+    provider: &IrysRethProviderInner,
+) -> eyre::Result<()> {
+    let mut block_chunks = 0;
+    let mut access_lists = vec![];
+    for tx in pool.best_transactions() {
+        let Some(access_list) = tx.transaction.access_list() else {
+            continue;
+        };
+        let Ok(parsed) = parse_access_list(access_list) else {
+            continue;
+        };
+        let chunk_count = parsed.chunk_count();
+        if chunk_count == 0
+            || chunk_count > MAX_PD_CHUNKS_PER_TX
+            || block_chunks + chunk_count > MAX_PD_CHUNKS_PER_BLOCK
+        {
+            continue;
+        }
+        block_chunks += chunk_count;
+        access_lists.push(access_list.0.clone());
+    }
+    prefetch_block_pd_chunks(provider, access_lists.iter().map(Vec::as_slice))
+}
+
+/// Prefetches the chunks read by PD transactions as they enter the pool, so
+/// they're already unpacked by the time a payload including them is built
+pub async fn prefetch_pool_pd_chunks<P: TransactionPool>(pool: P, provider: IrysRethProvider) {
+    let mut new_transactions = pool.new_transactions_listener();
+    while let Some(event) = new_transactions.recv().await {
+        let Some(access_list) = event.transaction.transaction.access_list().cloned() else {
+            continue;
+        };
+        if !access_list
+            .iter()
+            .any(|item| item.address == PRECOMPILE_ADDRESS)
+        {
+            continue;
+        }
+        let Some(provider) = provider.get().cloned() else {
+            continue;
+        };
+
+        let tx_hash = *event.transaction.hash();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = prefetch_pd_chunks(&provider, &access_list.0) {
+                warn!("Unable to prefetch PD chunks for tx {}: {}", &tx_hash, e);
+            }
+        });
+    }
+}
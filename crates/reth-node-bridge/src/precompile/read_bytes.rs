@@ -1,14 +1,14 @@
 use eyre::eyre;
-use irys_packing::unpack;
-use irys_primitives::range_specifier::{ByteRangeSpecifier, ChunkRangeSpecifier, U34};
+use irys_primitives::range_specifier::{ByteRangeSpecifier, U34};
 use irys_storage::reth_provider::IrysRethProviderInner;
 use revm_primitives::{
     Bytes, Env, PrecompileError, PrecompileErrors, PrecompileOutput, PrecompileResult,
 };
 
-use super::utils::ParsedAccessLists;
-
-const PD_CHUNK_READ_COST: u64 = 500;
+use super::{
+    gas::{charge_gas, read_bytes_range_gas},
+    utils::{ledger_chunk_range, read_unpacked_chunk, ParsedAccessLists},
+};
 
 struct ReadBytesRangeByIndexArgs {
     index: u8,
@@ -138,57 +138,29 @@ pub fn read_bytes_range(
             "Invalid byte read range chunk range index".to_owned(),
         )))?;
 
-    let ChunkRangeSpecifier {
-        partition_index,
-        offset,
-        chunk_count,
-    } = chunk_read_range;
+    // charge for every chunk we read & unpack and every byte we return before doing any work
+    let byte_len: u64 = length.try_into().map_err(|_| {
+        PrecompileErrors::Error(PrecompileError::Other(format!(
+            "length {} is out of range (u64)",
+            length,
+        )))
+    })?;
+    let gas_used = charge_gas(
+        read_bytes_range_gas(chunk_read_range.chunk_count as u64, byte_len),
+        gas_limit,
+    )?;
 
     // coordinate translation time!
 
     let storage_config = &state_provider.chunk_provider.storage_config;
-
-    // TODO: this will error if the partition_index > u64::MAX
-    // this is fine for testnet, but will need fixing later.
-
-    let translated_base_chunks_offset =
-        storage_config
-            .num_chunks_in_partition
-            .saturating_mul(partition_index.try_into().map_err(|_| {
-                PrecompileErrors::Error(PrecompileError::Other(format!(
-                    "partition_index {} is out of range (u64)",
-                    partition_index,
-                )))
-            })?);
-
-    let translated_chunks_start_offset =
-        translated_base_chunks_offset.saturating_add(*offset as u64);
-    let translated_chunks_end_offset =
-        translated_chunks_start_offset.saturating_add(*chunk_count as u64);
-
-    let mut bytes = Vec::with_capacity((*chunk_count as u64 * storage_config.chunk_size) as usize);
-    for i in translated_chunks_start_offset..translated_chunks_end_offset {
-        let chunk = state_provider
-            .chunk_provider
-            .get_chunk_by_ledger_offset(irys_database::Ledger::Publish, i)
-            .map_err(|e| {
-                PrecompileErrors::Error(PrecompileError::Other(format!(
-                    "Error reading chunk with part offset {} - {}",
-                    &i, &e
-                )))
-            })?
-            .ok_or(PrecompileErrors::Error(PrecompileError::Other(format!(
-                "Unable to read chunk with part offset {}",
-                &i,
-            ))))?;
-
-        // TODO: the mempool should request & unpack PD chunks in advance
-        let unpacked_chunk = unpack(
-            &chunk,
-            storage_config.entropy_packing_iterations,
-            storage_config.chunk_size as usize,
-        );
-        bytes.extend(unpacked_chunk.bytes.0)
+    let ledger_range = ledger_chunk_range(storage_config, chunk_read_range)?;
+
+    let mut bytes = Vec::with_capacity(
+        (chunk_read_range.chunk_count as u64 * storage_config.chunk_size) as usize,
+    );
+    for i in ledger_range {
+        let unpacked_chunk = read_unpacked_chunk(state_provider, i)?;
+        bytes.extend_from_slice(&unpacked_chunk.bytes.0)
     }
 
     // now we apply the bytes range
@@ -225,7 +197,7 @@ pub fn read_bytes_range(
     let extracted: Bytes = bytes.drain(offset..offset + truncated_len).collect();
 
     Ok(PrecompileOutput {
-        gas_used: (*chunk_count as u64) * PD_CHUNK_READ_COST,
+        gas_used,
         bytes: extracted,
     })
 }
@@ -0,0 +1,170 @@
+use alloy_primitives::B256;
+use alloy_sol_types::SolValue as _;
+use irys_storage::reth_provider::IrysRethProviderInner;
+use revm_primitives::{
+    Bytes, Env, PrecompileError, PrecompileErrors, PrecompileOutput, PrecompileResult,
+};
+
+use super::{
+    gas::{charge_gas, read_bytes_range_gas},
+    utils::{ledger_chunk_range, read_unpacked_chunk, ParsedAccessLists},
+};
+
+struct ReadChunkWithProofArgs {
+    index: u8,
+    chunk_offset: u16,
+}
+
+impl ReadChunkWithProofArgs {
+    const CALLDATA_LEN: usize = 1 + 1 + 2;
+
+    pub fn decode(bytes: &Bytes) -> Result<Self, PrecompileErrors> {
+        if bytes.len() != ReadChunkWithProofArgs::CALLDATA_LEN {
+            return Err(PrecompileErrors::Error(PrecompileError::Other(
+                "Invalid calldata".to_owned(),
+            )));
+        }
+        Ok(ReadChunkWithProofArgs {
+            index: u8::from_be(bytes[1]),
+            chunk_offset: u16::from_be_bytes([bytes[2], bytes[3]]),
+        })
+    }
+}
+
+/// Reads a single chunk from a chunk range in the access list, along with the
+/// merkle proof (`data_path`) connecting it to its transaction's `data_root`.
+/// Returns the ABI encoded
+/// `(bytes32 data_root, uint64 data_size, uint32 tx_offset, bytes data_path, bytes chunk)`
+pub fn read_chunk_with_proof(
+    call_data: &Bytes,
+    gas_limit: u64,
+    _env: &Env,
+    state_provider: &IrysRethProviderInner,
+    access_lists: ParsedAccessLists,
+) -> PrecompileResult {
+    let ReadChunkWithProofArgs {
+        index,
+        chunk_offset,
+    } = ReadChunkWithProofArgs::decode(call_data)?;
+
+    let chunk_read_range =
+        access_lists
+            .chunk_reads
+            .get(index as usize)
+            .ok_or(PrecompileErrors::Error(PrecompileError::Other(
+                "Invalid chunk range index".to_owned(),
+            )))?;
+
+    if chunk_offset >= chunk_read_range.chunk_count {
+        return Err(PrecompileErrors::Error(PrecompileError::Other(format!(
+            "Chunk offset {} is outside of the requested chunk range ({} chunks)",
+            chunk_offset, chunk_read_range.chunk_count
+        ))));
+    }
+
+    // charge for the read & unpack up front, the output size is only known once we have the chunk
+    charge_gas(read_bytes_range_gas(1, 0), gas_limit)?;
+
+    let storage_config = &state_provider.chunk_provider.storage_config;
+    let ledger_offset =
+        ledger_chunk_range(storage_config, chunk_read_range)?.start + chunk_offset as u64;
+    let chunk = read_unpacked_chunk(state_provider, ledger_offset)?;
+
+    let encoded = (
+        B256::from(chunk.data_root.0),
+        chunk.data_size,
+        chunk.tx_offset,
+        Bytes::copy_from_slice(&chunk.data_path.0),
+        Bytes::copy_from_slice(&chunk.bytes.0),
+    )
+        .abi_encode();
+    let gas_used = charge_gas(read_bytes_range_gas(1, encoded.len() as u64), gas_limit)?;
+
+    Ok(PrecompileOutput {
+        gas_used,
+        bytes: encoded.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompile::utils::tests::test_provider;
+    use alloy_primitives::aliases::U200;
+    use irys_primitives::range_specifier::ChunkRangeSpecifier;
+    use irys_testing_utils::utils::setup_tracing_and_temp_dir;
+    use irys_types::{Base64, UnpackedChunk, H256};
+
+    const GAS_LIMIT: u64 = 1_000_000;
+
+    fn call_data(index: u8, chunk_offset: u16) -> Bytes {
+        let mut call_data = vec![2_u8, index];
+        call_data.extend_from_slice(&chunk_offset.to_be_bytes());
+        call_data.into()
+    }
+
+    #[tokio::test]
+    async fn test_read_chunk_with_proof() {
+        let tmp_dir = setup_tracing_and_temp_dir(Some("test_read_chunk_with_proof"), false);
+        let provider = test_provider(tmp_dir.path()).await;
+        // covers Publish ledger chunks 23..27
+        let access_lists = ParsedAccessLists {
+            chunk_reads: vec![ChunkRangeSpecifier {
+                partition_index: U200::from(2),
+                offset: 3,
+                chunk_count: 4,
+            }],
+            byte_reads: vec![],
+        };
+
+        let chunk = UnpackedChunk {
+            data_root: H256::random(),
+            data_size: 100,
+            data_path: Base64(vec![1, 2, 3]),
+            bytes: Base64(vec![4; 32]),
+            tx_offset: 1,
+        };
+        provider.pd_chunk_cache.insert(24, chunk.clone());
+
+        let read = |index, chunk_offset| {
+            read_chunk_with_proof(
+                &call_data(index, chunk_offset),
+                GAS_LIMIT,
+                &Env::default(),
+                &provider,
+                access_lists.clone(),
+            )
+        };
+
+        let output = read(0, 1).unwrap();
+        assert_eq!(
+            <(B256, u64, u32, Bytes, Bytes)>::abi_decode(&output.bytes, true).unwrap(),
+            (
+                B256::from(chunk.data_root.0),
+                chunk.data_size,
+                chunk.tx_offset,
+                Bytes::from(chunk.data_path.0),
+                Bytes::from(chunk.bytes.0),
+            )
+        );
+
+        // not prefetched, outside the chunk range & unknown chunk range
+        for (index, chunk_offset) in [(0, 2), (0, 4), (1, 0)] {
+            assert!(matches!(
+                read(index, chunk_offset),
+                Err(PrecompileErrors::Error(_))
+            ));
+        }
+
+        assert!(matches!(
+            read_chunk_with_proof(
+                &Bytes::from(vec![2_u8, 0]),
+                GAS_LIMIT,
+                &Env::default(),
+                &provider,
+                access_lists.clone(),
+            ),
+            Err(PrecompileErrors::Error(_))
+        ));
+    }
+}
@@ -1,6 +1,14 @@
-use alloy_rpc_types::AccessListItem;
+use std::{ops::Range, sync::Arc};
+
+use alloy_eips::eip2718::Decodable2718 as _;
+use alloy_primitives::Bytes;
+use alloy_rpc_types::{AccessList, AccessListItem};
+use eyre::eyre;
 use irys_primitives::range_specifier::{ByteRangeSpecifier, ChunkRangeSpecifier, PdAccessListArg};
-use tracing::warn;
+use irys_storage::reth_provider::IrysRethProviderInner;
+use irys_types::{StorageConfig, UnpackedChunk};
+use reth_primitives::TransactionSigned;
+use revm_primitives::{PrecompileError, PrecompileErrors};
 
 use super::entrypoint::PRECOMPILE_ADDRESS;
 
@@ -10,7 +18,19 @@ pub struct ParsedAccessLists {
     pub byte_reads: Vec<ByteRangeSpecifier>,
 }
 
-pub fn parse_access_list(access_list: &Vec<AccessListItem>) -> eyre::Result<ParsedAccessLists> {
+impl ParsedAccessLists {
+    /// Total number of chunks read by the chunk ranges
+    pub fn chunk_count(&self) -> u64 {
+        self.chunk_reads
+            .iter()
+            .map(|chunk_range| chunk_range.chunk_count as u64)
+            .sum()
+    }
+}
+
+/// Decodes the PD storage keys of an access list, entries for other addresses are
+/// regular EIP-2930 entries and are ignored. Errors if any PD storage key is malformed.
+pub fn parse_access_list(access_list: &[AccessListItem]) -> eyre::Result<ParsedAccessLists> {
     // parse the access list into buckets
     let mut parsed = ParsedAccessLists {
         chunk_reads: vec![],
@@ -18,23 +38,209 @@ pub fn parse_access_list(access_list: &Vec<AccessListItem>) -> eyre::Result<Pars
     };
     for ali in access_list {
         if ali.address != PRECOMPILE_ADDRESS {
-            warn!("received unfiltered access list item {:?}", &ali);
             continue;
         }
         for key in &ali.storage_keys {
-            match PdAccessListArg::decode(key) {
-                Ok(dec) => match dec {
-                    PdAccessListArg::ChunkRead(range_specifier) => {
-                        parsed.chunk_reads.push(range_specifier)
-                    }
-                    PdAccessListArg::ByteRead(bytes_range_specifier) => {
-                        parsed.byte_reads.push(bytes_range_specifier)
-                    }
-                },
-                Err(_) => continue,
+            match PdAccessListArg::decode(key)
+                .map_err(|e| eyre!("Invalid PD access list key {}: {}", key, e))?
+            {
+                PdAccessListArg::ChunkRead(range_specifier) => {
+                    parsed.chunk_reads.push(range_specifier)
+                }
+                PdAccessListArg::ByteRead(bytes_range_specifier) => {
+                    parsed.byte_reads.push(bytes_range_specifier)
+                }
             }
         }
     }
 
     Ok(parsed)
 }
+
+/// Decodes the access lists of an execution payload's (EIP-2718 encoded) transactions
+pub fn payload_access_lists(transactions: &[Bytes]) -> eyre::Result<Vec<AccessList>> {
+    transactions
+        .iter()
+        .map(|tx| {
+            let tx = TransactionSigned::decode_2718(&mut tx.as_ref())
+                .map_err(|e| eyre!("Unable to decode payload transaction: {}", e))?;
+            Ok(tx.transaction.access_list().cloned().unwrap_or_default())
+        })
+        .collect()
+}
+
+/// Translates a partition relative [`ChunkRangeSpecifier`] into the range of
+/// Publish ledger chunk offsets it covers
+pub fn ledger_chunk_range(
+    storage_config: &StorageConfig,
+    chunk_range: &ChunkRangeSpecifier,
+) -> Result<Range<u64>, PrecompileErrors> {
+    let ChunkRangeSpecifier {
+        partition_index,
+        offset,
+        chunk_count,
+    } = chunk_range;
+
+    // TODO: this will error if the partition_index > u64::MAX
+    // this is fine for testnet, but will need fixing later.
+    let translated_base_chunks_offset = storage_config.num_chunks_in_partition.saturating_mul(
+        (*partition_index).try_into().map_err(|_| {
+            PrecompileErrors::Error(PrecompileError::Other(format!(
+                "partition_index {} is out of range (u64)",
+                partition_index,
+            )))
+        })?,
+    );
+
+    let start = translated_base_chunks_offset.saturating_add(*offset as u64);
+    let end = start.saturating_add(*chunk_count as u64);
+    Ok(start..end)
+}
+
+/// Reads the unpacked chunk at `ledger_offset` in the Publish ledger from the PD
+/// chunk cache. Chunks are never read & unpacked during execution, if the chunk
+/// wasn't prefetched the data isn't available locally and the read fails.
+pub fn read_unpacked_chunk(
+    state_provider: &IrysRethProviderInner,
+    ledger_offset: u64,
+) -> Result<Arc<UnpackedChunk>, PrecompileErrors> {
+    state_provider
+        .pd_chunk_cache
+        .get(ledger_offset)
+        .ok_or(PrecompileErrors::Error(PrecompileError::Other(format!(
+            "Chunk with ledger offset {} is not available locally",
+            &ledger_offset,
+        ))))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_primitives::{aliases::U200, Address, B256};
+    use irys_config::IrysNodeConfig;
+    use irys_database::{open_or_create_db, tables::IrysTables, BlockIndex};
+    use irys_primitives::range_specifier::{PdAccessListArg, U18, U34};
+    use irys_storage::{
+        pd_chunk_cache::{PdChunkCache, DEFAULT_PD_CHUNK_CACHE_CAPACITY},
+        ChunkProvider,
+    };
+    use irys_testing_utils::utils::setup_tracing_and_temp_dir;
+    use irys_types::DatabaseProvider;
+    use std::{path::Path, sync::RwLock};
+
+    pub(crate) const TEST_CHUNK_SIZE: u64 = 32;
+
+    /// A provider over an empty database, block index & PD chunk cache stored in `dir`
+    pub(crate) async fn test_provider(dir: &Path) -> IrysRethProviderInner {
+        let db = Arc::new(open_or_create_db(dir.join("db"), IrysTables::ALL, None).unwrap());
+        let storage_config = StorageConfig {
+            chunk_size: TEST_CHUNK_SIZE,
+            num_chunks_in_partition: 10,
+            ..Default::default()
+        };
+        let chunk_provider = Arc::new(ChunkProvider::new(
+            storage_config,
+            vec![],
+            DatabaseProvider(db.clone()),
+        ));
+        let config = Arc::new(IrysNodeConfig {
+            base_directory: dir.to_path_buf(),
+            ..IrysNodeConfig::default()
+        });
+        let block_index = BlockIndex::default().init(config).await.unwrap();
+        IrysRethProviderInner {
+            db,
+            pd_chunk_cache: Arc::new(PdChunkCache::new(
+                chunk_provider.clone(),
+                DEFAULT_PD_CHUNK_CACHE_CAPACITY,
+            )),
+            chunk_provider,
+            block_index: Arc::new(RwLock::new(block_index)),
+        }
+    }
+
+    pub(crate) fn chunk_read_key(partition_index: u64, offset: u32, chunk_count: u16) -> B256 {
+        B256::from(
+            PdAccessListArg::ChunkRead(ChunkRangeSpecifier {
+                partition_index: U200::from(partition_index),
+                offset,
+                chunk_count,
+            })
+            .encode(),
+        )
+    }
+
+    #[test]
+    fn test_parse_access_list() {
+        let byte_read = ByteRangeSpecifier {
+            index: 0,
+            chunk_offset: 1,
+            byte_offset: U18::from(2),
+            length: U34::from(3),
+        };
+        let mut access_list = vec![
+            AccessListItem {
+                address: PRECOMPILE_ADDRESS,
+                storage_keys: vec![
+                    chunk_read_key(1, 2, 3),
+                    B256::from(PdAccessListArg::ByteRead(byte_read).encode()),
+                ],
+            },
+            // regular EIP-2930 entries aren't decoded
+            AccessListItem {
+                address: Address::random(),
+                storage_keys: vec![B256::repeat_byte(0xff)],
+            },
+        ];
+
+        let parsed = parse_access_list(&access_list).unwrap();
+        assert_eq!(parsed.chunk_reads.len(), 1);
+        assert_eq!(parsed.chunk_reads[0].offset, 2);
+        assert_eq!(parsed.chunk_count(), 3);
+        assert_eq!(parsed.byte_reads, vec![byte_read]);
+
+        // a malformed PD key fails the whole access list
+        access_list[0].storage_keys.push(B256::repeat_byte(0xff));
+        assert!(parse_access_list(&access_list).is_err());
+    }
+
+    #[test]
+    fn test_ledger_chunk_range() {
+        let storage_config = StorageConfig {
+            num_chunks_in_partition: 10,
+            ..Default::default()
+        };
+        let chunk_range = ChunkRangeSpecifier {
+            partition_index: U200::from(2),
+            offset: 3,
+            chunk_count: 4,
+        };
+        assert_eq!(
+            ledger_chunk_range(&storage_config, &chunk_range).unwrap(),
+            23..27
+        );
+
+        let chunk_range = ChunkRangeSpecifier {
+            partition_index: U200::from(u64::MAX) + U200::from(1),
+            ..chunk_range
+        };
+        assert!(ledger_chunk_range(&storage_config, &chunk_range).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_unpacked_chunk() {
+        let tmp_dir = setup_tracing_and_temp_dir(Some("test_read_unpacked_chunk"), false);
+        let provider = test_provider(tmp_dir.path()).await;
+
+        // chunks are only read from the cache, never from storage
+        assert!(read_unpacked_chunk(&provider, 5).is_err());
+        provider.pd_chunk_cache.insert(
+            5,
+            UnpackedChunk {
+                tx_offset: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(read_unpacked_chunk(&provider, 5).unwrap().tx_offset, 1);
+    }
+}
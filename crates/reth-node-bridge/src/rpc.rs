@@ -1,7 +1,7 @@
 use alloy_rpc_types::BlockId;
 use alloy_signer_local::PrivateKeySigner;
 use foldhash::fast::RandomState;
-use irys_primitives::{Address, Genesis, ShadowReceipt};
+use irys_primitives::{Address, CommitmentStatus, Commitments, Genesis, IrysTxId, ShadowReceipt};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use jsonrpsee_core::async_trait;
 use jsonrpsee_types::ErrorObjectOwned;
@@ -24,7 +24,7 @@ use reth_node_core::irys_ext::IrysExt;
 use reth_node_ethereum::EthereumNode;
 use reth_provider::providers::BlockchainProvider2;
 
-use reth_provider::{BlockIdReader, StateProviderFactory};
+use reth_provider::{BlockIdReader, StateProviderBox, StateProviderFactory};
 use revm::db::State;
 use revm::JournaledState;
 
@@ -34,7 +34,7 @@ use revm_primitives::{Bytes, HashSet, SpecId, B256};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use tracing::debug;
 
@@ -73,9 +73,41 @@ pub trait AccountStateExtApi {
     //     block_hash: Option<BlockId>,
     // ) -> RpcResult<Option<Account>>;
 
+    /// Returns the stake & pledge commitments of an account
+    #[method(name = "getCommitments")]
+    fn get_commitments(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Option<Commitments>>;
+
     #[method(name = "ping")]
     fn ping(&self) -> RpcResult<String>;
 
+    #[method(name = "getPeerId")]
+    fn get_peer_id(&self) -> RpcResult<PeerId>;
+
+    // #[method(name = "getAccounts2")]
+    // fn get_accounts2(
+    //     &self,
+    //     addresses: Vec<Address>,
+    //     block_id: Option<BlockId>,
+    // ) -> RpcResult<HashMap<Address, Option<Account>>>;
+}
+
+/// The methods of [`IrysDevApi`]
+pub const DEV_RPC_METHODS: [&str; 6] = [
+    "irys_genesisBlock",
+    "irys_testApplyShadow",
+    "irys_createEthTx",
+    "irys_toEthAddress",
+    "irys_addPeer",
+    "irys_removePeer",
+];
+
+/// Dev & testing only methods, only registered when `CONFIG.dev_mode` is enabled
+#[rpc(server, client, namespace = "irys")]
+pub trait IrysDevApi {
     #[method(name = "genesisBlock")]
     fn add_genesis_block(
         &self,
@@ -87,33 +119,86 @@ pub trait AccountStateExtApi {
     #[method(name = "testApplyShadow")]
     fn test_apply_shadow(&self, parent: BlockId, shadow: ShadowTx) -> RpcResult<ShadowReceipt>;
 
+    #[method(name = "createEthTx")]
+    async fn create_eth_tx(&self, private_key: B256) -> RpcResult<Bytes>;
+
+    #[method(name = "toEthAddress")]
+    fn to_address(&self, private_key: B256) -> RpcResult<Address>;
+
     #[method(name = "addPeer")]
     fn add_peer(&self, peer_id: PeerId, addr: String) -> RpcResult<()>;
 
     #[method(name = "removePeer")]
     fn remove_peer(&self, peer_id: PeerId) -> RpcResult<()>;
+}
 
-    #[method(name = "getPeerId")]
-    fn get_peer_id(&self) -> RpcResult<PeerId>;
+type RethProvider = BlockchainProvider2<NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>>;
 
-    #[method(name = "createEthTx")]
-    async fn create_eth_tx(&self, private_key: B256) -> RpcResult<Bytes>;
+pub struct AccountStateExt {
+    pub provider: RethProvider,
+    pub network: NetworkHandle,
+}
 
-    #[method(name = "toEthAddress")]
-    fn to_address(&self, private_key: B256) -> RpcResult<Address>;
+pub struct IrysDevExt {
+    pub provider: RethProvider,
+    pub network: NetworkHandle,
+    /// Set by the launcher once the node is built, as it owns the reload channel
+    pub irys_ext: Arc<OnceLock<IrysExt>>,
+}
 
-    // #[method(name = "getAccounts2")]
-    // fn get_accounts2(
-    //     &self,
-    //     addresses: Vec<Address>,
-    //     block_id: Option<BlockId>,
-    // ) -> RpcResult<HashMap<Address, Option<Account>>>;
+/// Returns the state provider for `block_id`, or the latest state if `None`
+fn state_at(provider: &RethProvider, block_id: Option<BlockId>) -> RpcResult<StateProviderBox> {
+    // TODO: replace with proper errors/error codes
+    match block_id {
+        Some(block_id) => match block_id {
+            BlockId::Number(n) => provider.state_by_block_number_or_tag(n),
+            BlockId::Hash(h) => provider.state_by_block_hash(h.into()),
+        },
+        None => provider.latest(),
+    }
+    .map_err(|e| {
+        ErrorObjectOwned::owned::<String>(
+            -32071,
+            "error getting state provider",
+            Some(e.to_string()),
+        )
+    })
 }
 
-pub struct AccountStateExt {
-    pub provider: BlockchainProvider2<NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>>,
-    pub irys_ext: Option<IrysExt>,
-    pub network: NetworkHandle,
+/// Reads an account from the state at `block_id`, or the latest state if `None`
+fn account_at(
+    provider: &RethProvider,
+    address: Address,
+    block_id: Option<BlockId>,
+) -> RpcResult<Option<Account>> {
+    state_at(provider, block_id)?
+        .basic_account(address)
+        .map_err(|e| {
+            ErrorObjectOwned::owned::<String>(
+                -32072,
+                "error getting account info",
+                Some(e.to_string()),
+            )
+        })
+}
+
+/// Returns the status of the commitment `address` made with tx `tx_id`, as of the
+/// state at `block_id` (or the latest state if `None`)
+pub fn commitment_status_at(
+    provider: &RethProvider,
+    address: Address,
+    tx_id: IrysTxId,
+    block_id: Option<BlockId>,
+) -> RpcResult<Option<CommitmentStatus>> {
+    Ok(account_at(provider, address, block_id)?
+        .and_then(|account| account.commitments)
+        .and_then(|commitments| {
+            commitments
+                .0
+                .into_iter()
+                .find(|commitment| commitment.tx_id == tx_id)
+        })
+        .map(|commitment| commitment.status))
 }
 
 pub enum Either<L, R> {
@@ -124,25 +209,6 @@ pub enum Either<L, R> {
 #[async_trait]
 // impl<DB> AccountStateExtApiServer for AccountStateExt<DB> where DB: NodeTypesWithDB + StateProviderFactory  {
 impl AccountStateExtApiServer for AccountStateExt {
-    fn add_peer(&self, peer_id: PeerId, addr: String) -> RpcResult<()> {
-        dbg!(&addr);
-        let socket_addr = SocketAddr::from_str(addr.as_str()).map_err(|e| {
-            ErrorObjectOwned::owned::<String>(
-                -32073, // TOOD @JesseTheRobot add a RPC error registry so we don't overlap with error codes/numbers
-                "unable to convert address into SocketAddr",
-                Some(e.to_string()),
-            )
-        })?;
-        // TODO @JesseTheRobot - these don't confirm if the operation was successful
-        self.network.peers_handle().add_peer(peer_id, socket_addr);
-        Ok(())
-    }
-
-    fn remove_peer(&self, peer_id: PeerId) -> RpcResult<()> {
-        self.network.peers_handle().remove_peer(peer_id);
-        Ok(())
-    }
-
     fn get_peer_id(&self) -> RpcResult<PeerId> {
         Ok(*self.network.peer_id())
     }
@@ -152,30 +218,15 @@ impl AccountStateExtApiServer for AccountStateExt {
         address: Address,
         block_id: Option<BlockId>,
     ) -> RpcResult<Option<Account>> {
-        // get provider for latest state
-        let state = match block_id {
-            Some(block_id) => match block_id {
-                BlockId::Number(n) => self.provider.state_by_block_number_or_tag(n),
-                BlockId::Hash(h) => self.provider.state_by_block_hash(h.into()),
-            },
-            None => self.provider.latest(),
-        }
-        .map_err(|e| {
-            ErrorObjectOwned::owned::<String>(
-                -32071,
-                "error getting state provider",
-                Some(e.to_string()),
-            )
-        })?;
-        // TODO: replace with proper errors/error codes
+        account_at(&self.provider, address, block_id)
+    }
 
-        state.basic_account(address).map_err(|e| {
-            ErrorObjectOwned::owned::<String>(
-                -32072,
-                "error getting account info",
-                Some(e.to_string()),
-            )
-        })
+    fn get_commitments(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Option<Commitments>> {
+        Ok(account_at(&self.provider, address, block_id)?.and_then(|account| account.commitments))
     }
 
     // fn get_account2(
@@ -230,21 +281,7 @@ impl AccountStateExtApiServer for AccountStateExt {
         addresses: Vec<Address>,
         block_id: Option<BlockId>,
     ) -> RpcResult<HashMap<Address, Option<Account>>> {
-        // get provider for latest state
-        let state = match block_id {
-            Some(block_id) => match block_id {
-                BlockId::Number(n) => self.provider.state_by_block_number_or_tag(n),
-                BlockId::Hash(h) => self.provider.state_by_block_hash(h.into()),
-            },
-            None => self.provider.latest(),
-        }
-        .map_err(|e| {
-            ErrorObjectOwned::owned::<String>(
-                -32071,
-                "error getting state provider",
-                Some(e.to_string()),
-            )
-        })?;
+        let state = state_at(&self.provider, block_id)?;
         let mut hm = HashMap::new();
         for address in addresses.iter() {
             hm.insert(
@@ -329,9 +366,39 @@ impl AccountStateExtApiServer for AccountStateExt {
     fn ping(&self) -> RpcResult<String> {
         Ok("pong".to_string())
     }
+}
+
+#[async_trait]
+impl IrysDevApiServer for IrysDevExt {
+    fn add_peer(&self, peer_id: PeerId, addr: String) -> RpcResult<()> {
+        dbg!(&addr);
+        let socket_addr = SocketAddr::from_str(addr.as_str()).map_err(|e| {
+            ErrorObjectOwned::owned::<String>(
+                -32073, // TOOD @JesseTheRobot add a RPC error registry so we don't overlap with error codes/numbers
+                "unable to convert address into SocketAddr",
+                Some(e.to_string()),
+            )
+        })?;
+        // TODO @JesseTheRobot - these don't confirm if the operation was successful
+        self.network.peers_handle().add_peer(peer_id, socket_addr);
+        Ok(())
+    }
+
+    fn remove_peer(&self, peer_id: PeerId) -> RpcResult<()> {
+        self.network.peers_handle().remove_peer(peer_id);
+        Ok(())
+    }
 
     fn add_genesis_block(&self, info: GenesisInfo) -> RpcResult<Genesis> {
-        crate::genesis::add_genesis_block(&self.provider, &self.irys_ext.clone().unwrap(), info)
+        let irys_ext = self
+            .irys_ext
+            .get()
+            .ok_or(ErrorObjectOwned::owned::<String>(
+                -32092,
+                "node is still starting",
+                None,
+            ))?;
+        crate::genesis::add_genesis_block(&self.provider, irys_ext, info)
     }
 
     fn test_apply_shadow(&self, parent: BlockId, shadow: ShadowTx) -> RpcResult<ShadowReceipt> {
@@ -429,13 +496,10 @@ impl AccountStateExtApiServer for AccountStateExt {
         })?;
         debug!("Creating tx with owner: {}", wallet.address());
 
-        let account = self
-            .get_account(wallet.address(), Some(BlockId::latest()))?
-            .ok_or(ErrorObjectOwned::owned::<String>(
-                -32091,
-                "error executing shadow",
-                None,
-            ))?;
+        let account =
+            account_at(&self.provider, wallet.address(), Some(BlockId::latest()))?.ok_or(
+                ErrorObjectOwned::owned::<String>(-32091, "error executing shadow", None),
+            )?;
         let tx = tx(4096, 210000, None, None, account.nonce);
 
         let signed = TransactionTestContext::sign_tx(wallet, tx).await;
@@ -5,4 +5,5 @@ pub use nodit::Interval;
 pub mod chunk_provider;
 pub mod storage_module;
 pub use storage_module::*;
+pub mod pd_chunk_cache;
 pub mod reth_provider;
@@ -0,0 +1,176 @@
+use eyre::OptionExt;
+use irys_database::Ledger;
+use irys_packing::unpack;
+use irys_types::{LedgerChunkOffset, UnpackedChunk};
+use rayon::prelude::*;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, RwLock},
+};
+use tracing::debug;
+
+use crate::ChunkProvider;
+
+/// Maximum number of chunks a single PD transaction can read
+pub const MAX_PD_CHUNKS_PER_TX: u64 = 256;
+
+/// Maximum number of chunks the PD transactions of a block can read in total.
+/// A block's chunks are prefetched as one batch before it executes, so a
+/// [`PdChunkCache`] always holds at least this many chunks.
+pub const MAX_PD_CHUNKS_PER_BLOCK: u64 = 1_024;
+
+/// Default number of unpacked chunks held by a [`PdChunkCache`]
+pub const DEFAULT_PD_CHUNK_CACHE_CAPACITY: usize = 1_024;
+
+#[derive(Debug, Default)]
+struct PdChunkCacheInner {
+    chunks: HashMap<LedgerChunkOffset, Arc<UnpackedChunk>>,
+    /// Insertion order, used to evict the oldest chunks once over capacity
+    order: VecDeque<LedgerChunkOffset>,
+}
+
+/// Bounded cache of unpacked Publish ledger chunks read by programmable data
+/// (PD) transactions. Unpacking a chunk means recomputing its entropy, which is
+/// far too slow to do inside EVM execution, so chunks are fetched & unpacked
+/// ahead of time and the PD precompile only ever reads from this cache.
+#[derive(Debug)]
+pub struct PdChunkCache {
+    /// Where chunks missing from the cache are read from
+    chunk_provider: Arc<ChunkProvider>,
+    /// Maximum number of chunks held before the oldest are evicted
+    capacity: usize,
+    inner: RwLock<PdChunkCacheInner>,
+}
+
+impl PdChunkCache {
+    /// Creates a new, empty, cache holding at most `capacity` chunks, raised to
+    /// [`MAX_PD_CHUNKS_PER_BLOCK`] if lower so a block's chunks are never evicted
+    /// before it executes
+    pub fn new(chunk_provider: Arc<ChunkProvider>, capacity: usize) -> Self {
+        Self {
+            chunk_provider,
+            capacity: capacity.max(MAX_PD_CHUNKS_PER_BLOCK as usize),
+            inner: RwLock::new(PdChunkCacheInner::default()),
+        }
+    }
+
+    /// Returns the unpacked chunk at `ledger_offset` in the Publish ledger, if cached
+    pub fn get(&self, ledger_offset: LedgerChunkOffset) -> Option<Arc<UnpackedChunk>> {
+        self.inner
+            .read()
+            .unwrap()
+            .chunks
+            .get(&ledger_offset)
+            .cloned()
+    }
+
+    /// Fetches & unpacks, in parallel, any of the given Publish ledger chunks
+    /// that aren't cached yet. Chunks that were fetched are cached even if
+    /// others failed, the first error is returned.
+    pub fn prefetch(
+        &self,
+        ledger_offsets: impl IntoIterator<Item = LedgerChunkOffset>,
+    ) -> eyre::Result<()> {
+        let missing: Vec<LedgerChunkOffset> = {
+            let inner = self.inner.read().unwrap();
+            let mut missing: Vec<_> = ledger_offsets
+                .into_iter()
+                .filter(|offset| !inner.chunks.contains_key(offset))
+                .collect();
+            missing.sort_unstable();
+            missing.dedup();
+            missing
+        };
+        if missing.is_empty() {
+            return Ok(());
+        }
+        debug!("Prefetching {} PD chunks", missing.len());
+
+        let storage_config = &self.chunk_provider.storage_config;
+        let results: Vec<eyre::Result<(LedgerChunkOffset, UnpackedChunk)>> = missing
+            .into_par_iter()
+            .map(|ledger_offset| {
+                let chunk = self
+                    .chunk_provider
+                    .get_chunk_by_ledger_offset(Ledger::Publish, ledger_offset)?
+                    .ok_or_eyre(format!("Unable to read chunk at offset {}", ledger_offset))?;
+                let unpacked = unpack(
+                    &chunk,
+                    storage_config.entropy_packing_iterations,
+                    storage_config.chunk_size as usize,
+                );
+                Ok((ledger_offset, unpacked))
+            })
+            .collect();
+
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok((ledger_offset, chunk)) => self.insert(ledger_offset, chunk),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Caches an unpacked chunk, evicting the oldest chunks once over capacity
+    pub fn insert(&self, ledger_offset: LedgerChunkOffset, chunk: UnpackedChunk) {
+        let mut inner = self.inner.write().unwrap();
+        if inner
+            .chunks
+            .insert(ledger_offset, Arc::new(chunk))
+            .is_none()
+        {
+            inner.order.push_back(ledger_offset);
+        }
+        while inner.chunks.len() > self.capacity {
+            let Some(oldest) = inner.order.pop_front() else {
+                break;
+            };
+            inner.chunks.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use irys_database::{open_or_create_db, tables::IrysTables};
+    use irys_testing_utils::utils::setup_tracing_and_temp_dir;
+    use irys_types::{DatabaseProvider, StorageConfig};
+
+    #[test]
+    fn evicts_oldest_chunks_over_capacity() -> eyre::Result<()> {
+        let tmp_dir = setup_tracing_and_temp_dir(Some("evicts_oldest_chunks_over_capacity"), false);
+        let db = open_or_create_db(tmp_dir, IrysTables::ALL, None).unwrap();
+        let chunk_provider = ChunkProvider::new(
+            StorageConfig::default(),
+            vec![],
+            DatabaseProvider(Arc::new(db)),
+        );
+        // raised to the per block chunk limit
+        let cache = PdChunkCache::new(Arc::new(chunk_provider), 2);
+        let capacity = MAX_PD_CHUNKS_PER_BLOCK;
+
+        for ledger_offset in 0..capacity + 1 {
+            cache.insert(
+                ledger_offset,
+                UnpackedChunk {
+                    tx_offset: ledger_offset as u32,
+                    ..Default::default()
+                },
+            );
+        }
+
+        assert!(cache.get(0).is_none());
+        assert_eq!(cache.get(1).unwrap().tx_offset, 1);
+        assert_eq!(cache.get(capacity).unwrap().tx_offset, capacity as u32);
+
+        // cached chunks don't need to be read again, there's no storage module to read them from
+        cache.prefetch([1, capacity])?;
+        assert!(cache.prefetch([0]).is_err());
+        Ok(())
+    }
+}
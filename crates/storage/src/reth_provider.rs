@@ -1,13 +1,18 @@
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, OnceLock, RwLock};
 
+use irys_database::{BlockIndex, Initialized};
 use reth_db::DatabaseEnv;
 
-use crate::ChunkProvider;
+use crate::{pd_chunk_cache::PdChunkCache, ChunkProvider};
 
 #[derive(Debug, Clone)]
 pub struct IrysRethProviderInner {
     pub db: Arc<DatabaseEnv>,
     pub chunk_provider: Arc<ChunkProvider>,
+    /// Used to check the ledger ranges referenced by PD transactions exist
+    pub block_index: Arc<RwLock<BlockIndex<Initialized>>>,
+    /// Unpacked chunks read by PD transactions, prefetched ahead of execution
+    pub pd_chunk_cache: Arc<PdChunkCache>,
 }
 
 pub type IrysRethProvider = Arc<OnceLock<IrysRethProviderInner>>;
@@ -24,6 +24,7 @@ use nodit::{
 };
 use openssl::sha;
 use reth_db::Database;
+use reth_metrics::metrics::histogram;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -31,6 +32,7 @@ use std::{
     io::{Read, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
     sync::{Arc, Mutex, RwLock},
+    time::Instant,
 };
 use tracing::{debug, info};
 
@@ -115,6 +117,8 @@ pub struct StorageModuleInfo {
 /// Manages chunk storage on a single physical drive
 #[derive(Debug)]
 pub struct StorageSubmodule {
+    /// Directory holding the submodule's chunks file and database
+    pub path: PathBuf,
     /// Persistent storage handle
     file: Arc<Mutex<File>>,
     /// Persistent database env
@@ -196,6 +200,7 @@ impl StorageModule {
             map.insert_strict(
                 interval.clone(),
                 StorageSubmodule {
+                    path: sub_base_path,
                     file: chunks_file,
                     db: DatabaseProvider(Arc::new(submodule_db)),
                 },
@@ -303,7 +308,15 @@ impl StorageModule {
     /// The sync threshold is configured via `min_writes_before_sync` to optimize
     /// disk writes and minimize fragmentation.
     pub fn sync_pending_chunks(&self) -> eyre::Result<()> {
-        let threshold = self.storage_config.min_writes_before_sync;
+        self.sync_pending_chunks_with_threshold(self.storage_config.min_writes_before_sync)
+    }
+
+    /// Writes all pending chunks to disk, regardless of `min_writes_before_sync`
+    pub fn flush_pending_chunks(&self) -> eyre::Result<()> {
+        self.sync_pending_chunks_with_threshold(0)
+    }
+
+    fn sync_pending_chunks_with_threshold(&self, threshold: u64) -> eyre::Result<()> {
         let arc = self.pending_writes.clone();
 
         // First use read lock to check if we have work to do
@@ -329,6 +342,7 @@ impl StorageModule {
 
         // Only acquire write lock if we have work to do
         if !write_batch.is_empty() {
+            let started = Instant::now();
             let mut pending = arc.write().unwrap();
             for (chunk_offset, (bytes, chunk_type)) in write_batch {
                 self.write_chunk_internal(chunk_offset, bytes, chunk_type.clone())?;
@@ -343,16 +357,40 @@ impl StorageModule {
                 }
             }
 
-            {
-                // Save the updated intervals
-                let mut file = self.intervals_file.lock().unwrap();
-                let intervals = self.intervals.read().unwrap();
-                file.set_len(0)?;
-                file.seek(SeekFrom::Start(0))?;
-                file.write_all(serde_json::to_string(&*intervals)?.as_bytes())?;
-            }
+            self.save_intervals()?;
+            histogram!(
+                "irys_storage_sync_duration_seconds",
+                "storage_module" => self.id.to_string()
+            )
+            .record(started.elapsed().as_secs_f64());
+        }
+
+        Ok(())
+    }
+
+    /// Marks a range of chunks as uninitialized so they get repacked, dropping
+    /// any writes to them that are still pending
+    pub fn mark_uninitialized(&self, interval: Interval<u32>) -> eyre::Result<()> {
+        self.pending_writes
+            .write()
+            .unwrap()
+            .retain(|offset, _| !interval.contains_point(*offset));
+        {
+            let mut intervals = self.intervals.write().unwrap();
+            let _ = intervals.cut(interval);
+            let _ =
+                intervals.insert_merge_touching_if_values_equal(interval, ChunkType::Uninitialized);
         }
+        self.save_intervals()
+    }
 
+    /// Saves the storage intervals to the intervals file
+    fn save_intervals(&self) -> eyre::Result<()> {
+        let mut file = self.intervals_file.lock().unwrap();
+        let intervals = self.intervals.read().unwrap();
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serde_json::to_string(&*intervals)?.as_bytes())?;
         Ok(())
     }
 
@@ -436,6 +474,40 @@ impl StorageModule {
         set.into_iter().collect::<Vec<_>>()
     }
 
+    /// Returns the number of chunks in the given state
+    pub fn count_chunks(&self, chunk_type: ChunkType) -> u64 {
+        self.intervals
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, ct)| **ct == chunk_type)
+            .map(|(interval, _)| (interval.end() - interval.start()) as u64 + 1)
+            .sum()
+    }
+
+    /// Returns the storage state of every chunk range in the module, in ascending order
+    pub fn intervals(&self) -> Vec<(Interval<u32>, ChunkType)> {
+        self.intervals
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(interval, chunk_type)| (*interval, chunk_type.clone()))
+            .collect()
+    }
+
+    /// Returns the chunk range and directory of each submodule
+    pub fn submodule_paths(&self) -> Vec<(Interval<u32>, PathBuf)> {
+        self.submodules
+            .iter()
+            .map(|(interval, submodule)| (*interval, submodule.path.clone()))
+            .collect()
+    }
+
+    /// Returns the number of chunks waiting to be written to disk
+    pub fn pending_writes_count(&self) -> usize {
+        self.pending_writes.read().unwrap().len()
+    }
+
     /// Queues chunk data for later disk write. Chunks are batched for efficiency
     /// and written during periodic sync operations.
     pub fn write_chunk(
@@ -1232,4 +1304,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn flush_and_mark_uninitialized_test() -> eyre::Result<()> {
+        let infos = vec![StorageModuleInfo {
+            id: 0,
+            partition_assignment: None,
+            submodules: vec![(ii(0, 9), "hdd0".into())],
+        }];
+        let tmp_dir = setup_tracing_and_temp_dir(Some("flush_and_mark_uninitialized_test"), false);
+        let base_path = tmp_dir.path().to_path_buf();
+        initialize_storage_files(&base_path, &infos, &vec![])?;
+        let config = StorageConfig {
+            min_writes_before_sync: 5,
+            chunk_size: 32,
+            num_chunks_in_partition: 10,
+            ..Default::default()
+        };
+        let storage_module = StorageModule::new(&base_path, &infos[0], config)?;
+
+        // Below the sync threshold nothing is written until the writes are flushed
+        for offset in 0..3 {
+            storage_module.write_chunk(offset, vec![0xff; 32], ChunkType::Entropy);
+        }
+        storage_module.sync_pending_chunks()?;
+        assert_eq!(storage_module.pending_writes_count(), 3);
+        storage_module.flush_pending_chunks()?;
+        assert_eq!(storage_module.pending_writes_count(), 0);
+        assert_eq!(storage_module.get_intervals(ChunkType::Entropy), [ii(0, 2)]);
+
+        // Resetting a range drops its pending writes and marks it for repacking
+        storage_module.write_chunk(3, vec![0xff; 32], ChunkType::Entropy);
+        storage_module.mark_uninitialized(ii(2, 3))?;
+        assert_eq!(storage_module.pending_writes_count(), 0);
+        assert_eq!(storage_module.get_intervals(ChunkType::Entropy), [ii(0, 1)]);
+        assert_eq!(
+            storage_module.get_intervals(ChunkType::Uninitialized),
+            [ii(2, 9)]
+        );
+        Ok(())
+    }
 }
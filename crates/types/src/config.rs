@@ -19,6 +19,15 @@ pub struct Config {
     pub vdf_parallel_verification_thread_limit: usize,
     pub num_checkpoints_in_vdf_step: usize,
     pub vdf_sha_1s: u64,
+    /// SHA256 implementation used to compute VDF steps: "sha2", "openssl" or "native"
+    pub vdf_backend: &'static str,
+    /// Step duration the VDF difficulty retargeting aims for, in milliseconds
+    pub vdf_target_step_time_ms: u64,
+    /// Whether blocks retarget `next_vdf_difficulty` from observed step timing.
+    /// This is a consensus rule, every node on a network must use the same value
+    pub vdf_difficulty_retarget: bool,
+    /// How far ahead of the local clock a block's timestamp may be, in milliseconds
+    pub max_future_block_time_ms: u64,
     pub entropy_packing_iterations: u32,
     pub irys_chain_id: u64,
     /// Scaling factor for the capacity projection curve
@@ -42,6 +51,28 @@ pub struct Config {
     // TODO: enable this after fixing option in toml
     pub num_capacity_partitions: Option<u64>,
     pub port: u16,
+    /// Enables the dev-only `irys_` RPC methods (i.e. creating & signing txs from a
+    /// private key sent over RPC). Never enable this on a public node.
+    pub dev_mode: bool,
+    /// Comma separated JSON-RPC methods forwarded by the execution RPC proxy, a
+    /// trailing `*` matches any method starting with the prefix (i.e. `eth_*`)
+    pub rpc_proxy_allowed_methods: &'static str,
+    /// Comma separated JSON-RPC methods rejected by the execution RPC proxy, even
+    /// if allowed. Dev methods are always rejected unless `dev_mode` is enabled.
+    pub rpc_proxy_denied_methods: &'static str,
+    /// Comma separated origins allowed to make cross-origin API requests, `*` allows any
+    pub api_cors_allowed_origins: &'static str,
+    /// Largest JSON request body the API accepts, in bytes
+    pub api_json_body_limit: usize,
+    /// Tx & chunk posts allowed per client IP per minute, 0 disables the limit
+    pub api_ingress_rate_limit_per_ip: u32,
+    /// Txs accepted per signer per minute, 0 disables the limit
+    pub api_ingress_rate_limit_per_signer: u32,
+    /// Chunks for a data root without a known tx accepted per minute, before
+    /// the tx is posted. 0 disables the limit
+    pub api_max_chunks_per_unknown_data_root: u32,
+    /// Port of the admin API, only bound on localhost. 0 disables it
+    pub admin_port: u16,
 }
 
 pub const DEFAULT_BLOCK_TIME: u64 = 5;
@@ -61,6 +92,10 @@ pub const CONFIG: Config = load_toml!(
         vdf_parallel_verification_thread_limit: 4,
         num_checkpoints_in_vdf_step: 25, // 25 checkpoints 40 ms each = 1000 ms
         vdf_sha_1s: 530_000,
+        vdf_backend: "sha2",
+        vdf_target_step_time_ms: 1_000,
+        vdf_difficulty_retarget: false, // keeps local chains on the fixed VDF difficulty
+        max_future_block_time_ms: 15_000,
         entropy_packing_iterations: 22_500_000,
         irys_chain_id: 1275, // mainnet chainID (testnet is 1270)
         capacity_scalar: 100,
@@ -72,7 +107,16 @@ pub const CONFIG: Config = load_toml!(
         chunk_migration_depth: 1, // Number of confirmations before moving chunks to storage modules
         mining_key: "db793353b633df950842415065f769699541160845d73db902eadee6bc5042d0", // Burner PrivateKey (PK)
         num_capacity_partitions: None,
-        port: 80
+        port: 80,
+        dev_mode: false,
+        rpc_proxy_allowed_methods: "*",
+        rpc_proxy_denied_methods: "admin_*,debug_*,irys_addPeer,irys_removePeer",
+        api_cors_allowed_origins: "*",
+        api_json_body_limit: 1024 * 1024, // 1MB
+        api_ingress_rate_limit_per_ip: 0,
+        api_ingress_rate_limit_per_signer: 0,
+        api_max_chunks_per_unknown_data_root: 0,
+        admin_port: 0,
     }
 );
 
@@ -1,9 +1,42 @@
-use std::sync::{atomic::AtomicU64, Arc};
+use std::{
+    str::FromStr,
+    sync::{atomic::AtomicU64, Arc},
+};
 
 use crate::*;
 
 pub type AtomicVdfStepNumber = Arc<AtomicU64>;
 
+/// SHA256 implementation used to compute VDF checkpoints. All backends produce
+/// identical checkpoints, they only differ in speed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VdfBackend {
+    /// The pure Rust `sha2` crate
+    #[default]
+    Sha2,
+    /// OpenSSL's SHA256
+    OpenSsl,
+    /// The C implementation in `irys_c::vdf`, compiled with `-march=native` so it
+    /// picks up the CPU's SHA extensions
+    Native,
+}
+
+impl FromStr for VdfBackend {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha2" => Ok(Self::Sha2),
+            "openssl" => Ok(Self::OpenSsl),
+            "native" => Ok(Self::Native),
+            _ => Err(eyre::eyre!(
+                "Unknown VDF backend {:?}, expected one of \"sha2\", \"openssl\" or \"native\"",
+                s
+            )),
+        }
+    }
+}
+
 /// Allows for overriding of the vdf steps generation parameters
 #[derive(Debug, Clone)]
 pub struct VDFStepsConfig {
@@ -11,6 +44,12 @@ pub struct VDFStepsConfig {
     pub vdf_reset_frequency: usize,
     pub vdf_difficulty: u64,
     pub vdf_parallel_verification_thread_limit: usize,
+    /// Step duration the VDF difficulty retargeting aims for, in milliseconds
+    pub vdf_target_step_time_ms: u64,
+    /// Whether blocks retarget `next_vdf_difficulty` from observed step timing
+    pub vdf_difficulty_retarget: bool,
+    /// SHA256 implementation used to compute VDF steps
+    pub vdf_backend: VdfBackend,
 }
 
 impl Default for VDFStepsConfig {
@@ -24,6 +63,12 @@ impl Default for VDFStepsConfig {
                 CONFIG.vdf_sha_1s
             },
             vdf_parallel_verification_thread_limit: CONFIG.vdf_parallel_verification_thread_limit,
+            vdf_target_step_time_ms: CONFIG.vdf_target_step_time_ms,
+            vdf_difficulty_retarget: CONFIG.vdf_difficulty_retarget,
+            vdf_backend: CONFIG
+                .vdf_backend
+                .parse()
+                .expect("invalid vdf_backend in config"),
         }
     }
 }
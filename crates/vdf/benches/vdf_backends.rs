@@ -0,0 +1,43 @@
+//! Compares how many VDF steps per second each `VdfBackend` computes at the
+//! configured difficulty.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use irys_types::{VDFStepsConfig, VdfBackend, H256, U256};
+use irys_vdf::{step_number_to_salt_number, vdf_sha_with_backend};
+
+fn vdf_step(c: &mut Criterion) {
+    let config = VDFStepsConfig::default();
+    let mut group = c.benchmark_group("vdf_step");
+    // A step takes around a second at production difficulty
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+
+    for backend in [VdfBackend::Sha2, VdfBackend::OpenSsl, VdfBackend::Native] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{:?}", backend)),
+            &backend,
+            |b, &backend| {
+                let mut hash = H256::random();
+                let mut checkpoints = vec![H256::default(); config.num_checkpoints_in_vdf_step];
+                let mut step_number = 1;
+                b.iter(|| {
+                    let mut salt = U256::from(step_number_to_salt_number(&config, step_number));
+                    vdf_sha_with_backend(
+                        backend,
+                        &mut salt,
+                        &mut hash,
+                        config.num_checkpoints_in_vdf_step,
+                        config.vdf_difficulty,
+                        &mut checkpoints,
+                    );
+                    step_number += 1;
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, vdf_step);
+criterion_main!(benches);
@@ -1,7 +1,9 @@
 //! This crate provides functions and utilities for VDF (Verifiable Delay Function) operations,
 //! including checkpoint validation and seed application.
 
-use irys_types::{H256List, VDFLimiterInfo, VDFStepsConfig, H256, U256};
+use irys_types::{
+    H256List, IrysBlockHeader, VDFLimiterInfo, VDFStepsConfig, VdfBackend, H256, U256,
+};
 
 use openssl::sha;
 use rayon::prelude::*;
@@ -21,6 +23,197 @@ pub const fn step_number_to_salt_number(config: &VDFStepsConfig, step_number: u6
     }
 }
 
+/// Returns the first reset step crossed when the VDF advances from
+/// `prev_step_number` to `step_number`, if any.
+///
+/// The reset seed is applied to the output of a reset step before computing the
+/// step that follows it, so a reset step `R` is crossed when
+/// `prev_step_number <= R < step_number`.
+pub const fn entropy_reset_step(
+    config: &VDFStepsConfig,
+    prev_step_number: u64,
+    step_number: u64,
+) -> Option<u64> {
+    let frequency = config.vdf_reset_frequency as u64;
+    let reset_step = if prev_step_number == 0 {
+        frequency
+    } else {
+        prev_step_number.div_ceil(frequency) * frequency
+    };
+    if reset_step < step_number {
+        Some(reset_step)
+    } else {
+        None
+    }
+}
+
+/// Calculates the `(seed, next_seed)` pair for a block at `step_number` built
+/// on top of `previous_block`.
+///
+/// When the block crosses a reset line the previous block's `next_seed` becomes
+/// the `seed` applied at that reset, and the previous block's hash is scheduled
+/// as the `next_seed` for the following reset line. Otherwise both are inherited
+/// from the previous block.
+pub fn calculate_seeds(
+    config: &VDFStepsConfig,
+    step_number: u64,
+    previous_block: &IrysBlockHeader,
+) -> (H256, H256) {
+    let prev_info = &previous_block.vdf_limiter_info;
+    match entropy_reset_step(config, prev_info.global_step_number, step_number) {
+        Some(_) => (prev_info.next_seed, previous_block.block_hash),
+        None => (prev_info.seed, prev_info.next_seed),
+    }
+}
+
+/// Moves a scheduled VDF difficulty a quarter of the way towards the observed
+/// difficulty on each retarget, so a single unusually fast or slow block can't
+/// swing it.
+pub const VDF_DIFFICULTY_RETARGET_SMOOTHING: u128 = 4;
+
+/// A single retarget can at most double, or halve, the VDF difficulty.
+pub const VDF_DIFFICULTY_MAX_ADJUSTMENT_FACTOR: u64 = 2;
+
+/// Returns the VDF difficulty used to compute the step following `step_number`
+/// for a range of steps starting after `prev_step_number`.
+///
+/// Steps up to the first reset line crossed are computed with `vdf_difficulty`,
+/// steps after it with `next_vdf_difficulty`. Missing difficulties fall back to
+/// the configured `vdf_difficulty`.
+const fn difficulty_for_step(
+    config: &VDFStepsConfig,
+    prev_step_number: u64,
+    vdf_difficulty: Option<u64>,
+    next_vdf_difficulty: Option<u64>,
+    step_number: u64,
+) -> u64 {
+    let vdf_difficulty = match vdf_difficulty {
+        Some(vdf_difficulty) => vdf_difficulty,
+        None => config.vdf_difficulty,
+    };
+    match (
+        entropy_reset_step(config, prev_step_number, step_number + 1),
+        next_vdf_difficulty,
+    ) {
+        (Some(_), Some(next_vdf_difficulty)) => next_vdf_difficulty,
+        _ => vdf_difficulty,
+    }
+}
+
+/// Returns the VDF difficulty (SHA256 iterations per checkpoint) used to compute
+/// the step following `step_number`, according to the difficulties recorded in
+/// `vdf_info`. Also holds for steps past the end of `vdf_info`'s range, up to the
+/// next reset line that isn't covered by it.
+pub const fn step_vdf_difficulty(
+    config: &VDFStepsConfig,
+    vdf_info: &VDFLimiterInfo,
+    step_number: u64,
+) -> u64 {
+    difficulty_for_step(
+        config,
+        vdf_info
+            .global_step_number
+            .saturating_sub(vdf_info.steps.0.len() as u64),
+        vdf_info.vdf_difficulty,
+        vdf_info.next_vdf_difficulty,
+        step_number,
+    )
+}
+
+/// Schedules a new VDF difficulty from the observed time it took to compute
+/// `steps` steps at `vdf_difficulty`, aiming for `vdf_target_step_time_ms` per
+/// step. Returns `scheduled` unchanged when retargeting is disabled or there is
+/// nothing to observe.
+pub fn retarget_vdf_difficulty(
+    config: &VDFStepsConfig,
+    vdf_difficulty: u64,
+    scheduled: u64,
+    steps: u64,
+    elapsed_ms: u128,
+) -> u64 {
+    if !config.vdf_difficulty_retarget || steps == 0 || elapsed_ms == 0 {
+        return scheduled;
+    }
+
+    // The difficulty that would have made each observed step take exactly the target time
+    let observed = vdf_difficulty as u128 * config.vdf_target_step_time_ms as u128 * steps as u128
+        / elapsed_ms;
+
+    let scheduled_u128 = scheduled as u128;
+    let smoothed = if observed >= scheduled_u128 {
+        scheduled_u128 + (observed - scheduled_u128) / VDF_DIFFICULTY_RETARGET_SMOOTHING
+    } else {
+        scheduled_u128 - (scheduled_u128 - observed) / VDF_DIFFICULTY_RETARGET_SMOOTHING
+    };
+
+    let min = (vdf_difficulty / VDF_DIFFICULTY_MAX_ADJUSTMENT_FACTOR).max(1);
+    let max = vdf_difficulty.saturating_mul(VDF_DIFFICULTY_MAX_ADJUSTMENT_FACTOR);
+    smoothed.clamp(min as u128, max as u128) as u64
+}
+
+/// Calculates the `(vdf_difficulty, next_vdf_difficulty)` pair for a block at
+/// `step_number` produced at `timestamp` on top of `previous_block`.
+///
+/// Like the reset seeds, the scheduled `next_vdf_difficulty` only changes once
+/// per reset period so the VDF thread can pick it up from any confirmed block
+/// before it reaches the reset line:
+/// - A block crossing a reset line keeps the previous block's schedule, which
+///   the VDF applied at that line.
+/// - The first block after a crossing retargets the schedule from the time
+///   taken by the steps since the crossing block.
+/// - Every other block inherits both difficulties.
+pub fn calculate_vdf_difficulties(
+    config: &VDFStepsConfig,
+    step_number: u64,
+    timestamp: u128,
+    previous_block: &IrysBlockHeader,
+) -> (u64, u64) {
+    let prev_info = &previous_block.vdf_limiter_info;
+    let prev_step_number = prev_info.global_step_number;
+    let vdf_difficulty = step_vdf_difficulty(config, prev_info, prev_step_number);
+    let scheduled = prev_info.next_vdf_difficulty.unwrap_or(vdf_difficulty);
+
+    let previous_crossed_reset = entropy_reset_step(
+        config,
+        prev_step_number.saturating_sub(prev_info.steps.len() as u64),
+        prev_step_number,
+    )
+    .is_some();
+
+    if entropy_reset_step(config, prev_step_number, step_number).is_none() && previous_crossed_reset
+    {
+        let next_vdf_difficulty = retarget_vdf_difficulty(
+            config,
+            vdf_difficulty,
+            scheduled,
+            step_number.saturating_sub(prev_step_number),
+            timestamp.saturating_sub(previous_block.timestamp),
+        );
+        (vdf_difficulty, next_vdf_difficulty)
+    } else {
+        (vdf_difficulty, scheduled)
+    }
+}
+
+/// What the VDF thread applies when it reaches the next reset line, taken from
+/// the most recently confirmed block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VdfResetSchedule {
+    /// Seed applied to the output of the reset step
+    pub next_seed: H256,
+    /// VDF difficulty used for the steps after the reset line
+    pub next_vdf_difficulty: Option<u64>,
+}
+
+impl VdfResetSchedule {
+    pub fn from_limiter_info(vdf_info: &VDFLimiterInfo) -> Self {
+        Self {
+            next_seed: vdf_info.next_seed,
+            next_vdf_difficulty: vdf_info.next_vdf_difficulty.or(vdf_info.vdf_difficulty),
+        }
+    }
+}
+
 /// Takes a checkpoint seed and applies the SHA256 block hash seed to it as
 /// entropy. First it SHA256 hashes the `reset_seed` then SHA256 hashes the
 /// output together with the `seed` hash.
@@ -70,6 +263,91 @@ pub fn vdf_sha(
     }
 }
 
+/// Computes `num_checkpoints` checkpoints of a VDF step with the given backend.
+/// Behaves exactly like [`vdf_sha`]: `salt` and `seed` are advanced past the
+/// step and the checkpoints are written into `checkpoints`.
+pub fn vdf_sha_with_backend(
+    backend: VdfBackend,
+    salt: &mut U256,
+    seed: &mut H256,
+    num_checkpoints: usize,
+    num_iterations: u64,
+    checkpoints: &mut Vec<H256>,
+) {
+    match backend {
+        VdfBackend::Sha2 => vdf_sha(
+            &mut Sha256::new(),
+            salt,
+            seed,
+            num_checkpoints,
+            num_iterations,
+            checkpoints,
+        ),
+        VdfBackend::OpenSsl => {
+            let results =
+                vdf_sha_verification(*salt, *seed, num_checkpoints, num_iterations as usize);
+            checkpoints[..num_checkpoints].copy_from_slice(&results);
+            *seed = results[num_checkpoints - 1];
+            *salt = *salt + num_checkpoints;
+        }
+        VdfBackend::Native => {
+            vdf_sha_native(salt, seed, num_checkpoints, num_iterations, checkpoints)
+        }
+    }
+}
+
+/// Computes VDF checkpoints with the C implementation in `irys_c::vdf`.
+///
+/// `vdf_sha2` increments its salt buffer as a big endian number, while our salts
+/// are little endian, so it's driven one checkpoint at a time. It also always
+/// hashes at least twice per checkpoint, so a single iteration falls back to
+/// [`vdf_sha`].
+fn vdf_sha_native(
+    salt: &mut U256,
+    seed: &mut H256,
+    num_checkpoints: usize,
+    num_iterations: u64,
+    checkpoints: &mut Vec<H256>,
+) {
+    if num_iterations < 2 {
+        vdf_sha(
+            &mut Sha256::new(),
+            salt,
+            seed,
+            num_checkpoints,
+            num_iterations,
+            checkpoints,
+        );
+        return;
+    }
+
+    let hashing_iterations: i32 = num_iterations
+        .try_into()
+        .expect("VDF difficulty doesn't fit the native implementation");
+    let mut salt_bytes: [u8; 32] = [0; 32];
+
+    for checkpoint in checkpoints.iter_mut().take(num_checkpoints) {
+        salt.to_little_endian(&mut salt_bytes);
+        let mut out = H256::zero();
+        unsafe {
+            // With no intermediate checkpoints `vdf_sha2` only hashes into `out`
+            // and never touches the `outCheckpoint` buffer
+            irys_c::vdf::vdf_sha2(
+                salt_bytes.as_mut_ptr(),
+                seed.as_bytes_mut().as_mut_ptr(),
+                out.as_bytes_mut().as_mut_ptr(),
+                std::ptr::null_mut(),
+                0,
+                0,
+                hashing_iterations,
+            );
+        }
+        *seed = out;
+        *checkpoint = out;
+        *salt = *salt + 1;
+    }
+}
+
 /// Vdf verification code
 pub fn vdf_sha_verification(
     salt: U256,
@@ -116,6 +394,154 @@ pub fn vdf_sha_verification(
     checkpoints
 }
 
+/// A contiguous range of VDF steps computed elsewhere (taken from a block's
+/// `vdf_limiter_info` or supplied by a peer) that the local VDF thread can
+/// verify and jump forward to instead of recomputing every step sequentially.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VdfStepsFastForward {
+    /// The global step number of the last step in `steps`
+    pub global_step_number: u64,
+    /// The output of the step preceding the first step in `steps`
+    pub prev_output: H256,
+    /// Outputs of each step in the range, in ascending step order
+    pub steps: H256List,
+    /// The seed applied to the step output at each `vdf_reset_frequency` boundary
+    pub reset_seed: H256,
+    /// The VDF difficulty of the steps up to the first reset line in the range
+    pub vdf_difficulty: Option<u64>,
+    /// The VDF difficulty of the steps after the first reset line in the range
+    pub next_vdf_difficulty: Option<u64>,
+}
+
+impl VdfStepsFastForward {
+    /// Builds a fast-forward range out of the steps carried by a block header
+    pub fn from_limiter_info(vdf_info: &VDFLimiterInfo) -> Self {
+        Self {
+            global_step_number: vdf_info.global_step_number,
+            prev_output: vdf_info.prev_output,
+            steps: vdf_info.steps.clone(),
+            reset_seed: vdf_info.seed,
+            vdf_difficulty: vdf_info.vdf_difficulty,
+            next_vdf_difficulty: vdf_info.next_vdf_difficulty,
+        }
+    }
+
+    /// The global step number whose output is `prev_output`
+    pub fn prev_step_number(&self) -> u64 {
+        self.global_step_number
+            .saturating_sub(self.steps.len() as u64)
+    }
+
+    /// The VDF difficulty used to compute the step following `step_number`
+    pub const fn step_vdf_difficulty(&self, config: &VDFStepsConfig, step_number: u64) -> u64 {
+        difficulty_for_step(
+            config,
+            self.global_step_number
+                .saturating_sub(self.steps.0.len() as u64),
+            self.vdf_difficulty,
+            self.next_vdf_difficulty,
+            step_number,
+        )
+    }
+}
+
+/// Verifies a range of externally supplied VDF steps by recomputing them in
+/// parallel, see `compute_steps`.
+///
+/// Returns the checkpoints of the last step in the range if every step is valid.
+pub fn vdf_steps_fast_forward_is_valid(
+    fast_forward: &VdfStepsFastForward,
+    config: &VDFStepsConfig,
+) -> eyre::Result<H256List> {
+    if fast_forward.steps.is_empty() {
+        return Err(eyre::eyre!("No VDF steps to fast forward to"));
+    }
+    if fast_forward.global_step_number < fast_forward.steps.len() as u64 {
+        return Err(eyre::eyre!(
+            "VDF step range ending at {} can't contain {} steps",
+            fast_forward.global_step_number,
+            fast_forward.steps.len()
+        ));
+    }
+
+    let prev_step_number = fast_forward.prev_step_number();
+    let (test, last_step_checkpoints) = compute_steps(
+        config,
+        prev_step_number,
+        fast_forward.prev_output,
+        &fast_forward.steps,
+        fast_forward.reset_seed,
+        |step_number| fast_forward.step_vdf_difficulty(config, step_number),
+    )?;
+
+    if test != fast_forward.steps {
+        warn_mismatches(&test, &fast_forward.steps);
+        return Err(eyre::eyre!(
+            "VDF steps {}..={} are invalid!",
+            prev_step_number + 1,
+            fast_forward.global_step_number
+        ));
+    }
+
+    Ok(last_step_checkpoints)
+}
+
+/// Recomputes the `steps` following `prev_output`, the output of step
+/// `prev_step_number`, applying `reset_seed` at each reset line. Steps only
+/// depend on the output of the step before them, so they are all computed in
+/// parallel, limited to `vdf_parallel_verification_thread_limit` threads.
+///
+/// Returns the computed output of each step and the checkpoints of the last one.
+fn compute_steps(
+    config: &VDFStepsConfig,
+    prev_step_number: u64,
+    prev_output: H256,
+    steps: &H256List,
+    reset_seed: H256,
+    step_vdf_difficulty: impl Fn(u64) -> u64 + Sync,
+) -> eyre::Result<(H256List, H256List)> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.vdf_parallel_verification_thread_limit)
+        .build()?;
+
+    let mut results: Vec<Vec<H256>> = pool.install(|| {
+        (0..steps.len())
+            .into_par_iter()
+            .map(|i| {
+                let step_number = prev_step_number + i as u64;
+                let mut seed = if i == 0 { prev_output } else { steps[i - 1] };
+                if step_number > 0 && step_number % config.vdf_reset_frequency as u64 == 0 {
+                    info!(
+                        "Applying reset seed {:?} to step number {}",
+                        reset_seed, step_number
+                    );
+                    seed = apply_reset_seed(seed, reset_seed);
+                }
+                let mut salt = U256::from(step_number_to_salt_number(config, step_number));
+                let mut checkpoints = vec![H256::default(); config.num_checkpoints_in_vdf_step];
+                vdf_sha_with_backend(
+                    config.vdf_backend,
+                    &mut salt,
+                    &mut seed,
+                    config.num_checkpoints_in_vdf_step,
+                    step_vdf_difficulty(step_number),
+                    &mut checkpoints,
+                );
+                checkpoints
+            })
+            .collect()
+    });
+
+    let outputs = H256List(
+        results
+            .iter()
+            .map(|checkpoints| *checkpoints.last().unwrap())
+            .collect(),
+    );
+    let last_step_checkpoints = H256List(results.pop().unwrap_or_default());
+    Ok((outputs, last_step_checkpoints))
+}
+
 /// Validates VDF `last_step_checkpoints` in parallel across available cores.
 ///
 /// Takes a `VDFLimiterInfo` from a block header and verifies each checkpoint by:
@@ -159,8 +585,8 @@ pub async fn last_step_checkpoints_is_valid(
 
     let global_step_number: usize = vdf_info.global_step_number as usize;
 
-    // If the vdf reset happened on this step, apply the entropy to the seed
-    if global_step_number % config.vdf_reset_frequency == 0 {
+    // If the vdf reset happened on the previous step, apply the entropy to the seed
+    if global_step_number > 1 && (global_step_number - 1) % config.vdf_reset_frequency == 0 {
         let reset_seed = vdf_info.seed;
         seed = apply_reset_seed(seed, reset_seed);
     }
@@ -174,6 +600,7 @@ pub async fn last_step_checkpoints_is_valid(
         config,
         (global_step_number - 1) as u64,
     ));
+    let num_iterations = step_vdf_difficulty(config, vdf_info, (global_step_number - 1) as u64);
     let config = config.clone();
 
     let test = actix_rt::task::spawn_blocking(move || {
@@ -183,7 +610,6 @@ pub async fn last_step_checkpoints_is_valid(
             .build()
             .unwrap();
 
-        let num_iterations = config.vdf_difficulty;
         let test: Vec<H256> = pool.install(|| {
             (0..config.num_checkpoints_in_vdf_step)
                 .into_par_iter()
@@ -236,74 +662,17 @@ pub fn vdf_steps_are_valid(vdf_info: &VDFLimiterInfo, config: &VDFStepsConfig) -
         vdf_info.prev_output, vdf_info.seed
     );
 
-    let reset_seed = vdf_info.seed;
-
-    let mut step_hashes = vdf_info.steps.clone();
-
-    // Add the seed from the previous nonce info to the steps
-    let previous_seed = vdf_info.prev_output;
-    step_hashes.0.insert(0, previous_seed);
-
-    // Make a read only copy for parallel iterating
-    let steps = step_hashes.clone();
-
     // Calculate the step number of the first step in the blocks sequence
     let start_step_number: u64 = vdf_info.global_step_number - vdf_info.steps.len() as u64;
 
-    // We must calculate the checkpoint iterations for each step sequentially
-    // because we only have the first and last checkpoint of each step, but we
-    // can calculate each of the steps in parallel
-    // Limit threads number to avoid overloading the system using configuration limit
-    let pool = rayon::ThreadPoolBuilder::new()
-        .num_threads(config.vdf_parallel_verification_thread_limit)
-        .build()
-        .unwrap();
-    let test: Vec<(H256, Option<H256List>)> = pool.install(|| {
-        (0..steps.len() - 1)
-            .into_par_iter()
-            .map(|i| {
-                let mut hasher = Sha256::new();
-                let mut salt = U256::from(step_number_to_salt_number(
-                    config,
-                    start_step_number + i as u64,
-                ));
-                let mut seed = steps[i];
-                let mut checkpoints: Vec<H256> =
-                    vec![H256::default(); config.num_checkpoints_in_vdf_step];
-                if start_step_number + i as u64 > 0
-                    && (start_step_number + i as u64) % config.vdf_reset_frequency as u64 == 0
-                {
-                    info!(
-                        "Applying reset seed {:?} to step number {}",
-                        reset_seed,
-                        start_step_number + i as u64
-                    );
-                    seed = apply_reset_seed(seed, reset_seed);
-                }
-                vdf_sha(
-                    &mut hasher,
-                    &mut salt,
-                    &mut seed,
-                    config.num_checkpoints_in_vdf_step,
-                    config.vdf_difficulty,
-                    &mut checkpoints,
-                );
-                (
-                    *checkpoints.last().unwrap(),
-                    if i == steps.len() - 2 {
-                        // If this is the last step, return the last checkpoint
-                        Some(H256List(checkpoints))
-                    } else {
-                        // Otherwise, return just the seed for the next step
-                        None
-                    },
-                )
-            })
-            .collect()
-    });
-
-    let last_step_checkpoints = test.last().unwrap().1.clone();
-    let test: H256List = H256List(test.into_iter().map(|par| par.0).collect());
+    let (test, last_step_checkpoints) = compute_steps(
+        config,
+        start_step_number,
+        vdf_info.prev_output,
+        &vdf_info.steps,
+        vdf_info.seed,
+        |step_number| step_vdf_difficulty(config, vdf_info, step_number),
+    )?;
 
     let steps_are_valid = test == vdf_info.steps;
 
@@ -313,15 +682,9 @@ pub fn vdf_steps_are_valid(vdf_info: &VDFLimiterInfo, config: &VDFStepsConfig) -
         return Err(eyre::eyre!("VDF steps are invalid!"));
     }
 
-    let last_step_checkpoints_are_valid = last_step_checkpoints
-        .as_ref()
-        .is_some_and(|cks| *cks == vdf_info.last_step_checkpoints);
-
-    if !last_step_checkpoints_are_valid {
+    if last_step_checkpoints != vdf_info.last_step_checkpoints {
         // Compare the original list with the calculated one
-        if let Some(cks) = last_step_checkpoints {
-            warn_mismatches(&cks, &vdf_info.last_step_checkpoints)
-        }
+        warn_mismatches(&last_step_checkpoints, &vdf_info.last_step_checkpoints);
         return Err(eyre::eyre!("VDF last step checkpoints are invalid!"));
     }
 
@@ -8,5 +8,5 @@ address constant PD_READ_PRECOMPILE_ADDRESS = address(0x500);
 uint8 constant READ_FULL_BYTE_RANGE = 0;
 // read part of a byte range, by index with an offset (range offset + provided offset) and an overriden length
 uint8 constant READ_PARTIAL_BYTE_RANGE = 1;
-// // read using a custom byte range
-// uint8 constant READ_CUSTOM_BYTE_RANGE = 2;
+// read a single chunk from a chunk range, by index and chunk offset, along with its merkle proof (data_path)
+uint8 constant READ_CHUNK_WITH_PROOF = 2;
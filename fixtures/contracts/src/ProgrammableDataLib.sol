@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "./Precompiles.sol";
+
+/**
+ * @notice Wraps every function of the programmable data (PD) precompile
+ * @dev Byte & chunk ranges are referenced by their index in the access list, with all other types filtered out.
+ */
+library ProgrammableDataLib {
+    struct Chunk {
+        bytes32 dataRoot;
+        uint64 dataSize;
+        // index of the chunk in its transaction
+        uint32 txOffset;
+        // merkle proof connecting the chunk to dataRoot
+        bytes dataPath;
+        bytes data;
+    }
+
+    /**
+     * @notice Reads all bytes from a BytesRange in the access list
+     * @param byte_range_index The index of the BytesRange in the access list to read from
+     */
+    function readByteRange(
+        uint8 byte_range_index
+    ) internal view returns (bool success, bytes memory data) {
+        return
+            address(PD_READ_PRECOMPILE_ADDRESS).staticcall(
+                bytes.concat(
+                    bytes1(READ_FULL_BYTE_RANGE),
+                    bytes1(byte_range_index)
+                )
+            );
+    }
+
+    /**
+     * @notice Reads `length` bytes from a BytesRange in the access list, starting `start_offset` bytes into the range
+     * @param byte_range_index The index of the BytesRange in the access list to read from
+     */
+    function readByteRange(
+        uint8 byte_range_index,
+        uint32 start_offset,
+        uint32 length
+    ) internal view returns (bool success, bytes memory data) {
+        return
+            address(PD_READ_PRECOMPILE_ADDRESS).staticcall(
+                bytes.concat(
+                    bytes1(READ_PARTIAL_BYTE_RANGE),
+                    bytes1(byte_range_index),
+                    bytes4(start_offset),
+                    bytes4(length)
+                )
+            );
+    }
+
+    /**
+     * @notice Reads a single chunk from a ChunkRange in the access list, along with its merkle proof
+     * @param chunk_range_index The index of the ChunkRange in the access list to read from
+     * @param chunk_offset The offset of the chunk within the ChunkRange
+     */
+    function readChunkWithProof(
+        uint8 chunk_range_index,
+        uint16 chunk_offset
+    ) internal view returns (bool success, Chunk memory chunk) {
+        bytes memory data;
+        (success, data) = address(PD_READ_PRECOMPILE_ADDRESS).staticcall(
+            bytes.concat(
+                bytes1(READ_CHUNK_WITH_PROOF),
+                bytes1(chunk_range_index),
+                bytes2(chunk_offset)
+            )
+        );
+        if (success) {
+            (
+                chunk.dataRoot,
+                chunk.dataSize,
+                chunk.txOffset,
+                chunk.dataPath,
+                chunk.data
+            ) = abi.decode(data, (bytes32, uint64, uint32, bytes, bytes));
+        }
+    }
+}